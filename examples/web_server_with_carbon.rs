@@ -4,18 +4,17 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-/// An example of sending data to a Prometheus server with a local webserver
+//! An example of sending data to a Prometheus server with a local webserver
 
 extern crate iron;
 extern crate metrics;
-extern crate histogram;
 extern crate hyper;
 
 use iron::prelude::*;
 use iron::status;
-use metrics::metrics::{Counter, Gauge, Meter, Metric, StdCounter, StdGauge, StdMeter};
+use metrics::metrics::{Counter, Gauge, Histogram, Meter, Metric, StdCounter, StdGauge,
+                       StdHistogram, StdMeter};
 use metrics::reporter::CarbonReporter;
-use histogram::Histogram;
 use std::thread;
 use metrics::reporter::Reporter;
 
@@ -31,13 +30,8 @@ fn main() {
         let g = StdGauge::new();
         g.set(10);
 
-        let mut h = Histogram::configure()
-            .max_value(100)
-            .precision(1)
-            .build()
-            .unwrap();
-
-        h.increment_by(1, 1).unwrap();
+        let h = StdHistogram::new();
+        h.update(1);
 
         println!("Starting carbon recorder at carbon_graphite:2003");
         let mut reporter =
@@ -45,7 +39,7 @@ fn main() {
         let _ = reporter.add("meter1", Metric::Meter(m.clone()));
         let _ = reporter.add("counter1", Metric::Counter(c.clone()));
         let _ = reporter.add("gauge1", Metric::Gauge(g.clone()));
-        let _ = reporter.add("histogram", Metric::Histogram(h));
+        let _ = reporter.add("histogram", Metric::Histogram(h.clone()));
 
         loop { c.inc() }
     });
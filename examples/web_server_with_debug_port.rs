@@ -4,25 +4,21 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-/// An example of sending data to a Prometheus server with a local webserver
+//! An example of sending data to a Prometheus server with a local webserver
 
 extern crate iron;
 extern crate metrics;
-extern crate histogram;
-
-
-use iron::prelude::*;
-use iron::status;
-use metrics::metrics::{Counter, Gauge, Meter, Metric, StdCounter, StdGauge, StdMeter};
-use std::collections::HashMap;
-use histogram::Histogram;
-use metrics::reporter::Reporter;
 
 #[cfg(not(feature = "prometheus"))]
 fn main() {}
 #[cfg(feature = "prometheus")]
 fn main() {
-    use metrics::reporter::PrometheusReporter;
+    use iron::prelude::*;
+    use iron::status;
+    use metrics::metrics::{Counter, Gauge, Histogram, Meter, Metric, StdCounter, StdGauge,
+                           StdHistogram, StdMeter};
+    use metrics::reporter::{PrometheusReporter, Reporter};
+    use std::collections::HashMap;
     println!("WebServer Starting");
     extern crate hyper;
     let m = StdMeter::new();
@@ -34,23 +30,20 @@ fn main() {
     let g = StdGauge::new();
     g.set(32);
 
-    let mut h = Histogram::configure()
-        .max_value(100)
-        .precision(1)
-        .build()
-        .unwrap();
-
-    h.increment_by(1, 1).unwrap();
+    let h = StdHistogram::new();
+    h.update(1);
 
     let mut _labels = HashMap::new();
     _labels.insert(String::from("test"), String::from("test"));
     let labels = Some(_labels);
     let mut reporter =
-        PrometheusReporter::new("test", "0.0.0.0:8080", 1024);
+        PrometheusReporter::new("test", "0.0.0.0:8080", "example", "web_server");
+    reporter.describe("counter1", "", "How many times counter1 was incremented");
+    reporter.describe("histogram", "bytes", "The size of the responses");
     reporter.addl("meter1", Metric::Meter(m.clone()), labels.clone()).unwrap();
     reporter.addl("counter1", Metric::Counter(c.clone()), labels.clone()).unwrap();
     reporter.addl("gauge1", Metric::Gauge(g.clone()), labels.clone()).unwrap();
-    reporter.addl("histogram", Metric::Histogram(h), labels.clone()).unwrap();
+    reporter.addl("histogram", Metric::Histogram(h.clone()), labels.clone()).unwrap();
     Iron::new(|_: &mut Request| Ok(Response::with(status::NotFound)))
         .http("0.0.0.0:3000")
         .unwrap();
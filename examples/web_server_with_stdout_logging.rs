@@ -1,14 +1,13 @@
-/// An example of sending data to a Prometheus server with a local webserver
+//! An example of sending data to a Prometheus server with a local webserver
 
 extern crate iron;
 extern crate metrics;
-extern crate histogram;
 
 use iron::prelude::*;
 use iron::status;
-use metrics::metrics::{Counter, Gauge, Meter, Metric, StdCounter, StdGauge, StdMeter};
+use metrics::metrics::{Counter, Gauge, Histogram, Meter, Metric, StdCounter, StdGauge,
+                       StdHistogram, StdMeter};
 use metrics::reporter::ConsoleReporter;
-use histogram::*;
 use std::thread;
 use metrics::reporter::Reporter;
 
@@ -24,14 +23,14 @@ fn main() {
 
         let g = StdGauge::new();
         g.set(1);
-        let mut h = Histogram::configure().max_value(100).precision(1).build().unwrap();
-        h.increment_by(1, 1).unwrap();
+        let h = StdHistogram::new();
+        h.update(1);
 
         let mut reporter = ConsoleReporter::new("test",100);
         let _ = reporter.add("meter",Metric::Meter(m.clone()));
         let _ = reporter.add("counter",Metric::Counter(c.clone()));
         let _ = reporter.add("guage",Metric::Gauge(g.clone()));
-        let _ = reporter.add("histo",Metric::Histogram(h));
+        let _ = reporter.add("histo",Metric::Histogram(h.clone()));
         loop {
             c.inc()
         }
@@ -6,101 +6,341 @@
 // is fully designed for prometheus with the minimal
 // dependencies and overhead.
 //
-// We currently provide no abstraction around collecting metrics.
-// This Reporter eventually needs to handle the multiplexing and
-// organization of metrics.
+// Metric families are either added as they are or produced by
+// collectors, which are called on every scrape so the values served
+// are always the current ones. Jobs that do not live long enough to
+// be scraped can push them to a Pushgateway, or send them to a
+// remote_write endpoint, instead.
 
-extern crate iron;
-extern crate router;
-extern crate persistent;
+extern crate hyper;
+#[macro_use]
+extern crate log;
 extern crate protobuf; // depend on rust-protobuf runtime
-extern crate lru_cache;
+extern crate snap;
 extern crate time;
 
+pub mod openmetrics;
 pub mod promo_proto;
-use router::Router;
-use iron::typemap::Key;
-use iron::prelude::*;
-use iron::status;
-use lru_cache::LruCache;
+pub mod pushgateway;
+pub mod remote_proto;
+pub mod remote_write;
+pub mod text_format;
 use protobuf::Message;
-use std::sync::{Arc, RwLock};
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use pushgateway::Pushgateway;
+use remote_write::{Queue, RemoteWrite};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, RwLock};
 use std::thread;
-use std::hash::{Hash, Hasher};
-use std::collections::hash_map::DefaultHasher;
-
-// http handler storage
-#[derive(Copy, Clone)]
-struct HandlerStorage;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 // refer to https://prometheus.io/docs/instrumenting/exposition_formats/
-const CONTENT_TYPE: &'static str = "application/vnd.google.protobuf; \
-                                    proto=io.prometheus.client.MetricFamily;
+const CONTENT_TYPE: &str = "application/vnd.google.protobuf; \
+                                    proto=io.prometheus.client.MetricFamily; \
                                     encoding=delimited";
 
-impl Key for HandlerStorage {
-    type Value = Arc<RwLock<LruCache<u64, promo_proto::MetricFamily>>>;
+// The exposition formats we can serve
+#[derive(Debug, PartialEq)]
+enum Format {
+    OpenMetrics,
+    Protobuf,
+    Text,
+}
+
+impl Format {
+    // Pick the format the scraper prefers according to the q-values of its Accept header. The
+    // text format is served when nothing better matches, as it is the one every client reads.
+    fn from_accept(accept: Option<&str>) -> Format {
+        let mut best = (Format::Text, 0.0);
+        for media_range in accept.unwrap_or("").split(',') {
+            let mut parts = media_range.split(';').map(|part| part.trim());
+            let media_type = parts.next().unwrap_or("").to_lowercase();
+            let mut q = 1.0;
+            let mut proto = None;
+            let mut encoding = None;
+            for param in parts {
+                let mut kv = param.splitn(2, '=');
+                let key = kv.next().unwrap_or("").trim().to_lowercase();
+                let value = kv.next().unwrap_or("").trim().trim_matches('"');
+                match key.as_str() {
+                    "q" => q = value.parse().unwrap_or(0.0),
+                    "proto" => proto = Some(value),
+                    "encoding" => encoding = Some(value),
+                    _ => {}
+                }
+            }
+            let format = match media_type.as_str() {
+                "application/vnd.google.protobuf" if
+                    proto == Some("io.prometheus.client.MetricFamily") &&
+                    encoding == Some("delimited") => Format::Protobuf,
+                "application/openmetrics-text" => Format::OpenMetrics,
+                "text/plain" | "text/*" | "*/*" => Format::Text,
+                _ => continue,
+            };
+            if q > best.1 {
+                best = (format, q);
+            }
+        }
+        best.0
+    }
+
+    fn content_type(&self) -> &'static str {
+        match *self {
+            Format::OpenMetrics => openmetrics::CONTENT_TYPE,
+            Format::Protobuf => CONTENT_TYPE,
+            Format::Text => text_format::CONTENT_TYPE,
+        }
+    }
+}
+
+/// Produces metric families from live values whenever `/metrics` is scraped.
+pub trait Collector: Send + Sync {
+    fn collect(&self) -> Vec<promo_proto::MetricFamily>;
+}
+
+impl<F> Collector for F
+    where F: Fn() -> Vec<promo_proto::MetricFamily> + Send + Sync
+{
+    fn collect(&self) -> Vec<promo_proto::MetricFamily> {
+        self()
+    }
+}
+
+// What the handler serves
+#[derive(Default)]
+struct Storage {
+    // Added families by name
+    families: BTreeMap<String, promo_proto::MetricFamily>,
+    collectors: Vec<Box<dyn Collector>>,
 }
 
+impl Storage {
+    fn scrape(&self) -> Vec<promo_proto::MetricFamily> {
+        let mut families: Vec<_> = self.families.values().cloned().collect();
+        for collector in &self.collectors {
+            families.extend(collector.collect());
+        }
+        families
+    }
+}
 
-fn families_to_u8(metric_families: Vec<(&u64, &promo_proto::MetricFamily)>) -> Vec<u8> {
+
+fn families_to_u8(metric_families: &[promo_proto::MetricFamily]) -> Vec<u8> {
     let mut buf = Vec::new();
-    for (_, family) in metric_families {
+    for family in metric_families {
         family.write_length_delimited_to_writer(&mut buf).unwrap();
     }
     buf
 }
 
-fn handler(req: &mut Request) -> IronResult<Response> {
-    match req.get::<persistent::Read<HandlerStorage>>() {
-        Ok(ts_and_metrics) => {
-            // TODO catch unwrap
-            let serialized: Vec<u8> =
-                families_to_u8((*ts_and_metrics).read().unwrap().iter().collect());
-            // TODO lifecycle out the metrics we sent up
-            Ok(Response::with((CONTENT_TYPE, status::Ok, serialized)))
+// Serves /metrics on its own thread until stopped
+struct Server {
+    stop: Arc<AtomicBool>,
+    join_handle: thread::JoinHandle<()>,
+}
+
+impl Server {
+    fn start(host_and_port: &str, storage: Arc<RwLock<Storage>>) -> io::Result<Server> {
+        let listener = TcpListener::bind(host_and_port)?;
+        // Accept without blocking, so the thread notices when it has to stop
+        listener.set_nonblocking(true)?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = stop.clone();
+        let join_handle = thread::spawn(move || {
+            while !stopped.load(Ordering::SeqCst) {
+                match listener.accept() {
+                    Ok((stream, _)) => {
+                        if let Err(e) = serve(stream, &storage) {
+                            warn!("Unable to answer a scrape {}", e);
+                        }
+                    }
+                    // Nobody is scraping, or a connection failed before we took it
+                    Err(_) => thread::sleep(Duration::from_millis(50)),
+                }
+            }
+        });
+        Ok(Server { stop, join_handle })
+    }
+
+    // Stop accepting and wait for the thread, which closes the listening socket
+    fn stop(self) {
+        self.stop.store(true, Ordering::SeqCst);
+        let _ = self.join_handle.join();
+    }
+}
+
+// Answer a single request and close the connection
+fn serve(stream: TcpStream, storage: &RwLock<Storage>) -> io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    stream.set_write_timeout(Some(Duration::from_secs(5)))?;
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Every Accept header counts, as if they were one
+    let mut accept: Option<String> = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        let mut header = header.splitn(2, ':');
+        if header.next().unwrap_or("").trim().eq_ignore_ascii_case("accept") {
+            let value = header.next().unwrap_or("").trim();
+            accept = Some(match accept {
+                Some(accept) => format!("{},{}", accept, value),
+                None => value.to_string(),
+            });
         }
-        Err(_) => Ok(Response::with((status::InternalServerError, "ERROR"))),
     }
+    let mut request = request_line.split_whitespace();
+    let method = request.next().unwrap_or("");
+    let path = request.next().unwrap_or("").split('?').next().unwrap_or("");
+    let format = Format::from_accept(accept.as_deref());
+    let (status, content_type, body) = match (method, path) {
+        ("GET", "/metrics") => {
+            match storage.read() {
+                Ok(storage) => {
+                    let families = storage.scrape();
+                    let serialized: Vec<u8> = match format {
+                        Format::OpenMetrics => {
+                            openmetrics::families_to_openmetrics(&families).into_bytes()
+                        }
+                        Format::Protobuf => families_to_u8(&families),
+                        Format::Text => text_format::families_to_text(&families).into_bytes(),
+                    };
+                    ("200 OK", format.content_type(), serialized)
+                }
+                Err(_) => ("500 Internal Server Error", "text/plain", b"ERROR".to_vec()),
+            }
+        }
+        ("GET", _) => ("404 Not Found", "text/plain", Vec::new()),
+        _ => ("405 Method Not Allowed", "text/plain", Vec::new()),
+    };
+    let mut stream = reader.into_inner();
+    write!(stream,
+           "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+           status,
+           content_type,
+           body.len())?;
+    stream.write_all(&body)?;
+    stream.flush()
 }
 
+// The thread pushing the families, along with the channel stopping it
+type Pusher = (mpsc::Sender<()>, thread::JoinHandle<Result<(), String>>);
+
 // TODO perhaps we autodiscover the host and port
 pub struct PrometheusReporter {
-    cache: Arc<RwLock<LruCache<u64, promo_proto::MetricFamily>>>
+    storage: Arc<RwLock<Storage>>,
+    // Set when serving /metrics
+    server: Option<Server>,
+    // Set when pushing to a Pushgateway or remote_write endpoint rather than serving /metrics
+    pusher: Option<Pusher>,
 }
 
 impl PrometheusReporter {
     pub fn new(host_and_port: &'static str) -> Self {
-        let reporter = PrometheusReporter {
-            // TODO make it configurable
-            cache: Arc::new(RwLock::new(LruCache::new(86400)))
+        let storage = Arc::new(RwLock::new(Storage::default()));
+        let server = match Server::start(host_and_port, storage.clone()) {
+            Ok(server) => Some(server),
+            Err(x) => {
+                error!("Unable to start prometheus reporter {}", x);
+                None
+            }
         };
-        let mut router = Router::new();
-        router.get("/metrics", handler);
-        let mut chain = Chain::new(router);
-        chain.link_before(persistent::Read::<HandlerStorage>::one(reporter.cache.clone()));
-        // TODO get rid of the unwrap
-
-        thread::spawn(move ||{
-            match Iron::new(chain).http(host_and_port) {
-                Ok(_) => {}
-                Err(x) => panic!("Unable to start prometheus reporter {}",x),
+        PrometheusReporter {
+            storage,
+            server,
+            pusher: None,
+        }
+    }
+
+    /// Push the families to the Pushgateway every `delay_ms` instead of
+    /// serving them. They are pushed one last time on `stop`, or the group is
+    /// deleted if the Pushgateway is configured to.
+    pub fn pushing_to(gateway: Pushgateway, delay_ms: u64) -> Self {
+        PrometheusReporter::pushing_every(delay_ms, move |families, last| {
+            if last && gateway.deletes_on_stop() {
+                gateway.delete()
+            } else {
+                gateway.push(&families?)
+            }
+        })
+    }
+
+    /// Send the families to a remote_write endpoint every `delay_ms` instead
+    /// of serving them. Write requests that cannot be sent are queued and
+    /// sent along with the next ones, the queue is flushed on `stop`.
+    pub fn remote_writing(remote: RemoteWrite, delay_ms: u64) -> Self {
+        let mut queue = Queue::new(remote);
+        PrometheusReporter::pushing_every(delay_ms, move |families, _| {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+            queue.push(remote_write::write_request(&families?, now.as_millis() as i64));
+            let dropped = queue.dropped();
+            let flushed = queue.flush();
+            if queue.dropped() > dropped {
+                warn!("Dropped {} remote_write requests so far", queue.dropped());
+            }
+            flushed
+        })
+    }
+
+    // Scrape and push the families every delay_ms, then one last time when stopped
+    fn pushing_every<F>(delay_ms: u64, mut push: F) -> Self
+        where F: FnMut(Result<Vec<promo_proto::MetricFamily>, String>, bool) -> Result<(), String>,
+              F: Send + 'static
+    {
+        let storage = Arc::new(RwLock::new(Storage::default()));
+        let (tx, rx) = mpsc::channel();
+        let pushed = storage.clone();
+        let handle = thread::spawn(move || {
+            let scrape = || match pushed.read() {
+                Ok(storage) => Ok(storage.scrape()),
+                Err(e) => Err(format!("Unable to push {}", e)),
+            };
+            while let Err(mpsc::RecvTimeoutError::Timeout) =
+                rx.recv_timeout(Duration::from_millis(delay_ms)) {
+                // The next push will bring the other end up to date again
+                if let Err(e) = push(scrape(), false) {
+                    warn!("{}", e);
+                }
             }
+            push(scrape(), true)
         });
-        reporter
+        PrometheusReporter {
+            storage,
+            server: None,
+            pusher: Some((tx, handle)),
+        }
+    }
+
+    /// Stop serving `/metrics`, freeing its port, or stop pushing, pushing or
+    /// deleting one last time.
+    pub fn stop(&mut self) -> Result<(), String> {
+        if let Some(server) = self.server.take() {
+            server.stop();
+        }
+        match self.pusher.take() {
+            Some((tx, handle)) => {
+                // The pusher may be gone already, it is joined either way
+                let _ = tx.send(());
+                handle.join().map_err(|_| "The pusher panicked".to_string())?
+            }
+            None => Ok(()),
+        }
     }
+
     // TODO require start before add
     pub fn add(&mut self, metric_families: Vec<promo_proto::MetricFamily>) -> Result<i64, String> {
-        
-        match self.cache.write() {
-            Ok(mut cache) => {
+        match self.storage.write() {
+            Ok(mut storage) => {
                 let mut counter = 0;
                 for metric_family in metric_families {
-
-                    let mut hasher = DefaultHasher::new();
-                    metric_family.get_name().hash(&mut hasher);
-                    cache.insert(hasher.finish(), metric_family);
-                    counter = counter + 1;
+                    storage.families.insert(metric_family.get_name().to_string(), metric_family);
+                    counter += 1;
                 }
                 Ok(counter)
             }
@@ -109,15 +349,12 @@ impl PrometheusReporter {
     }
 
     pub fn remove(&mut self, metrics_to_remove: Vec<String>) -> Result<i64, String> {
-        match self.cache.write() {
-            Ok(mut cache) => {
+        match self.storage.write() {
+            Ok(mut storage) => {
                 let mut counter = 0;
                 for metric_name_to_remove in metrics_to_remove {
-
-                    let mut hasher = DefaultHasher::new();
-                    metric_name_to_remove.hash(&mut hasher);
-                    if let Some(_) = cache.remove(&hasher.finish()) {
-                        counter = counter + 1;
+                    if storage.families.remove(&metric_name_to_remove).is_some() {
+                        counter += 1;
                     }
                 }
                 Ok(counter)
@@ -125,13 +362,24 @@ impl PrometheusReporter {
             Err(y) => Err(format!("Unable to remove {}", y)),
         }
     }
+
+    /// Call the collector on every scrape, serving the families it returns
+    /// along with the added ones.
+    pub fn register<C: Collector + 'static>(&mut self, collector: C) -> Result<(), String> {
+        match self.storage.write() {
+            Ok(mut storage) => {
+                storage.collectors.push(Box::new(collector));
+                Ok(())
+            }
+            Err(y) => Err(format!("Unable to register {}", y)),
+        }
+    }
 }
 
 #[cfg(test)]
 mod test {
 
     extern crate hyper;
-    extern crate lru_cache;
     use std::thread;
     use std::time::Duration;
     use protobuf::repeated::RepeatedField;
@@ -178,25 +426,140 @@ mod test {
         0
     }
 
+    fn scrape(url: &str, accept: &str) -> hyper::client::Response {
+        let mut headers = hyper::header::Headers::new();
+        headers.set_raw("Accept", vec![accept.as_bytes().to_vec()]);
+        let client = hyper::client::Client::new();
+        client.get(url).headers(headers).send().unwrap()
+    }
+
+    fn content_type_of(res: &hyper::client::Response) -> String {
+        String::from_utf8(res.headers.get_raw("content-type").unwrap()[0].clone()).unwrap()
+    }
+
     #[test]
     fn add_some_stats_and_slurp_them_with_http() {
         let mut reporter = PrometheusReporter::new("0.0.0.0:8080");
         thread::sleep(Duration::from_millis(1024));
-        reporter.add(vec![a_metric_family()]);
-        let client = hyper::client::Client::new();
-        let mut res = client.get("http://127.0.0.1:8080/metrics").send().unwrap();
+        reporter.add(vec![a_metric_family()]).unwrap();
+        let mut res = scrape("http://127.0.0.1:8080/metrics",
+                             "application/vnd.google.protobuf;\
+                              proto=io.prometheus.client.MetricFamily;encoding=delimited;q=0.7,\
+                              text/plain;version=0.0.4;q=0.3,*/*;q=0.1");
         assert_eq!(res.status, hyper::Ok);
+        assert!(content_type_of(&res).starts_with("application/vnd.google.protobuf"));
         let mut buffer = Vec::new();
         let size_of_buffer = res.read_to_end(&mut buffer).unwrap();
         println!("{:?} size:{} ", buffer, size_of_buffer);
         assert_eq!(size_of_buffer, 53);
     }
 
+    #[test]
+    fn slurp_the_text_format_with_http() {
+        let mut reporter = PrometheusReporter::new("0.0.0.0:8082");
+        thread::sleep(Duration::from_millis(1024));
+        reporter.add(vec![a_metric_family()]).unwrap();
+        let mut res = scrape("http://127.0.0.1:8082/metrics", "*/*");
+        assert_eq!(res.status, hyper::Ok);
+        assert_eq!(content_type_of(&res), text_format::CONTENT_TYPE);
+        let mut body = String::new();
+        res.read_to_string(&mut body).unwrap();
+        assert_eq!(body,
+                   "# HELP MetricFamily Help\n\
+                    # TYPE MetricFamily gauge\n\
+                    MetricFamily{name=\"value\"} 0.1 0\n");
+    }
+
+    #[test]
+    fn collectors_are_called_on_every_scrape() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let mut reporter = PrometheusReporter::new("0.0.0.0:8083");
+        let scrapes = AtomicUsize::new(0);
+        reporter.register(move || {
+                let mut family = a_metric_family();
+                let scrape = scrapes.fetch_add(1, Ordering::SeqCst) + 1;
+                family.mut_metric()[0].mut_gauge().set_value(scrape as f64);
+                vec![family]
+            })
+            .unwrap();
+        thread::sleep(Duration::from_millis(1024));
+        for expected in 1..3 {
+            let mut res = scrape("http://127.0.0.1:8083/metrics", "text/plain");
+            let mut body = String::new();
+            res.read_to_string(&mut body).unwrap();
+            assert!(body.ends_with(&format!("MetricFamily{{name=\"value\"}} {} 0\n", expected)));
+        }
+    }
+
+    #[test]
+    fn push_on_an_interval_and_on_stop() {
+        let (url, requests) = pushgateway::test::stand_in("200 OK");
+        let mut reporter = PrometheusReporter::pushing_to(Pushgateway::new(&url, "batch"), 50);
+        reporter.add(vec![a_metric_family()]).unwrap();
+
+        let received = requests.recv().unwrap();
+        assert_eq!(received.method, "PUT");
+        assert_eq!(received.path, "/metrics/job/batch");
+        assert!(String::from_utf8(received.body).unwrap().contains("MetricFamily"));
+
+        reporter.stop().unwrap();
+        let last = requests.try_iter().last().unwrap();
+        assert_eq!(last.method, "PUT");
+    }
+
+    #[test]
+    fn remote_write_on_stop() {
+        let (url, requests) = pushgateway::test::stand_in("200 OK");
+        let mut reporter = PrometheusReporter::remote_writing(RemoteWrite::new(&url), 60000);
+        reporter.add(vec![a_metric_family()]).unwrap();
+        reporter.stop().unwrap();
+
+        let received = requests.recv().unwrap();
+        assert_eq!(received.method, "POST");
+        assert_eq!(received.content_type.unwrap(), "application/x-protobuf");
+    }
+
+    #[test]
+    fn delete_on_stop() {
+        let (url, requests) = pushgateway::test::stand_in("202 Accepted");
+        let gateway = Pushgateway::new(&url, "batch").delete_on_stop(true);
+        let mut reporter = PrometheusReporter::pushing_to(gateway, 60000);
+        reporter.stop().unwrap();
+        assert_eq!(requests.recv().unwrap().method, "DELETE");
+    }
+
+    #[test]
+    fn negotiate_the_format() {
+        assert_eq!(Format::from_accept(None), Format::Text);
+        assert_eq!(Format::from_accept(Some("*/*")), Format::Text);
+        assert_eq!(Format::from_accept(Some("application/json")), Format::Text);
+        assert_eq!(Format::from_accept(Some("application/vnd.google.protobuf;\
+                                              proto=io.prometheus.client.MetricFamily;\
+                                              encoding=delimited")),
+                   Format::Protobuf);
+        // Protobuf in another encoding is no good to us
+        assert_eq!(Format::from_accept(Some("application/vnd.google.protobuf;\
+                                              proto=io.prometheus.client.MetricFamily;\
+                                              encoding=text")),
+                   Format::Text);
+        assert_eq!(Format::from_accept(Some("application/openmetrics-text;version=1.0.0,\
+                                              application/openmetrics-text;version=0.0.1;q=0.75,\
+                                              text/plain;version=0.0.4;q=0.5,*/*;q=0.1")),
+                   Format::OpenMetrics);
+        // The highest q-value wins
+        assert_eq!(Format::from_accept(Some("application/vnd.google.protobuf;\
+                                              proto=io.prometheus.client.MetricFamily;\
+                                              encoding=delimited;q=0.3,\
+                                              text/plain;version=0.0.4;q=0.5")),
+                   Format::Text);
+    }
+
     #[test]
     fn add_and_remove_metric() {
         let mut reporter = PrometheusReporter::new("0.0.0.0:8081");
         thread::sleep(Duration::from_millis(1024));
-        reporter.add(vec![a_metric_family()]);
+        reporter.add(vec![a_metric_family()]).unwrap();
         if let Ok(number_removed) = reporter.remove(vec![a_metric_family_name()]) {
            assert_eq!(number_removed, 1);
         }
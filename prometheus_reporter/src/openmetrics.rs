@@ -0,0 +1,577 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+//
+// The OpenMetrics 1.0 text format.
+// refer to https://github.com/OpenObservability/OpenMetrics/blob/main/specification/OpenMetrics.md
+
+use promo_proto::{Exemplar, LabelPair, MetricFamily, MetricType};
+use protobuf::well_known_types::Timestamp;
+use std::f64;
+use std::fmt::Write;
+use text_format::{escape_label_value, format_float, write_labels};
+
+pub const CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// Render the metric families in the OpenMetrics text format, terminated by
+/// `# EOF`. Families without any metric are skipped.
+pub fn families_to_openmetrics<'a, I>(metric_families: I) -> String
+    where I: IntoIterator<Item = &'a MetricFamily>
+{
+    let mut text = String::new();
+    for family in metric_families {
+        if family.get_metric().is_empty() {
+            continue;
+        }
+        write_family(&mut text, family);
+    }
+    text.push_str("# EOF\n");
+    text
+}
+
+fn write_family(text: &mut String, family: &MetricFamily) {
+    let metric_type = family.get_field_type();
+    // Counter samples carry the _total suffix, the family itself does not
+    let name = match metric_type {
+        MetricType::COUNTER => {
+            family.get_name().strip_suffix("_total").unwrap_or_else(|| family.get_name())
+        }
+        _ => family.get_name(),
+    };
+    let _ = writeln!(text, "# TYPE {} {}", name, type_name(metric_type));
+    // The unit has to be the last part of the name, parsers reject the family otherwise
+    let unit = family.get_unit();
+    if !unit.is_empty() && name.ends_with(&format!("_{}", unit)) {
+        let _ = writeln!(text, "# UNIT {} {}", name, unit);
+    }
+    if family.has_help() {
+        let _ = writeln!(text, "# HELP {} {}", name, escape_help(family.get_help()));
+    }
+
+    for metric in family.get_metric() {
+        let sample = Sample {
+            labels: metric.get_label(),
+            timestamp: if metric.has_timestamp_ms() {
+                Some(metric.get_timestamp_ms() as f64 / 1000.0)
+            } else {
+                None
+            },
+        };
+        match metric_type {
+            MetricType::COUNTER => {
+                let counter = metric.get_counter();
+                sample.write(text,
+                             &format!("{}_total", name),
+                             None,
+                             counter.get_value(),
+                             exemplar(counter.has_exemplar(), counter.get_exemplar()));
+                if counter.has_created_timestamp() {
+                    sample.write_created(text, name, counter.get_created_timestamp());
+                }
+            }
+            MetricType::GAUGE => {
+                sample.write(text, name, None, metric.get_gauge().get_value(), None)
+            }
+            MetricType::UNTYPED => {
+                sample.write(text, name, None, metric.get_untyped().get_value(), None)
+            }
+            MetricType::SUMMARY => {
+                let summary = metric.get_summary();
+                for quantile in summary.get_quantile() {
+                    let q = canonical_float(quantile.get_quantile());
+                    sample.write(text, name, Some(("quantile", &q)), quantile.get_value(), None);
+                }
+                sample.write(text,
+                             &format!("{}_sum", name),
+                             None,
+                             summary.get_sample_sum(),
+                             None);
+                sample.write(text,
+                             &format!("{}_count", name),
+                             None,
+                             summary.get_sample_count() as f64,
+                             None);
+                if summary.has_created_timestamp() {
+                    sample.write_created(text, name, summary.get_created_timestamp());
+                }
+            }
+            MetricType::HISTOGRAM => {
+                let histogram = metric.get_histogram();
+                let bucket_name = format!("{}_bucket", name);
+                let mut has_inf_bucket = false;
+                for bucket in histogram.get_bucket() {
+                    has_inf_bucket |= bucket.get_upper_bound() == f64::INFINITY;
+                    let le = canonical_float(bucket.get_upper_bound());
+                    sample.write(text,
+                                 &bucket_name,
+                                 Some(("le", &le)),
+                                 bucket.get_cumulative_count() as f64,
+                                 exemplar(bucket.has_exemplar(), bucket.get_exemplar()));
+                }
+                // The +Inf bucket is mandatory and always holds every sample
+                if !has_inf_bucket {
+                    sample.write(text,
+                                 &bucket_name,
+                                 Some(("le", "+Inf")),
+                                 histogram.get_sample_count() as f64,
+                                 None);
+                }
+                sample.write(text,
+                             &format!("{}_count", name),
+                             None,
+                             histogram.get_sample_count() as f64,
+                             None);
+                sample.write(text,
+                             &format!("{}_sum", name),
+                             None,
+                             histogram.get_sample_sum(),
+                             None);
+                if histogram.has_created_timestamp() {
+                    sample.write_created(text, name, histogram.get_created_timestamp());
+                }
+            }
+        }
+    }
+}
+
+// What every sample of a metric shares
+struct Sample<'a> {
+    labels: &'a [LabelPair],
+    // In seconds, unlike the text format
+    timestamp: Option<f64>,
+}
+
+impl<'a> Sample<'a> {
+    fn write(&self,
+             text: &mut String,
+             name: &str,
+             extra_label: Option<(&str, &str)>,
+             value: f64,
+             exemplar: Option<&Exemplar>) {
+        text.push_str(name);
+        write_labels(text, self.labels, extra_label);
+        let _ = write!(text, " {}", format_float(value));
+        if let Some(timestamp) = self.timestamp {
+            let _ = write!(text, " {}", timestamp);
+        }
+        if let Some(exemplar) = exemplar {
+            text.push_str(" # ");
+            if exemplar.get_label().is_empty() {
+                text.push_str("{}");
+            }
+            write_labels(text, exemplar.get_label(), None);
+            let _ = write!(text, " {}", format_float(exemplar.get_value()));
+            if exemplar.has_timestamp() {
+                let _ = write!(text, " {}", seconds(exemplar.get_timestamp()));
+            }
+        }
+        text.push('\n');
+    }
+
+    fn write_created(&self, text: &mut String, name: &str, created: &Timestamp) {
+        self.write(text, &format!("{}_created", name), None, seconds(created), None);
+    }
+}
+
+fn exemplar(has_exemplar: bool, exemplar: &Exemplar) -> Option<&Exemplar> {
+    if has_exemplar { Some(exemplar) } else { None }
+}
+
+fn seconds(timestamp: &Timestamp) -> f64 {
+    timestamp.get_seconds() as f64 + timestamp.get_nanos() as f64 / 1e9
+}
+
+fn type_name(metric_type: MetricType) -> &'static str {
+    match metric_type {
+        MetricType::COUNTER => "counter",
+        MetricType::GAUGE => "gauge",
+        MetricType::SUMMARY => "summary",
+        MetricType::UNTYPED => "unknown",
+        MetricType::HISTOGRAM => "histogram",
+    }
+}
+
+// Unlike the text format, double quotes are escaped in the help too
+fn escape_help(help: &str) -> String {
+    escape_label_value(help)
+}
+
+// `le` and `quantile` values are always written with a fractional part, e.g. `1.0`
+fn canonical_float(value: f64) -> String {
+    if value.is_finite() {
+        format!("{:?}", value)
+    } else {
+        format_float(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use promo_proto::{Bucket, Counter, Gauge, Histogram, Metric, Quantile, Summary, Untyped};
+    use protobuf::repeated::RepeatedField;
+
+    fn label(name: &str, value: &str) -> LabelPair {
+        let mut label_pair = LabelPair::new();
+        label_pair.set_name(name.to_string());
+        label_pair.set_value(value.to_string());
+        label_pair
+    }
+
+    fn timestamp(seconds: i64, millis: i32) -> Timestamp {
+        let mut timestamp = Timestamp::new();
+        timestamp.set_seconds(seconds);
+        timestamp.set_nanos(millis * 1_000_000);
+        timestamp
+    }
+
+    fn family(name: &str, metric_type: MetricType, metrics: Vec<Metric>) -> MetricFamily {
+        let mut family = MetricFamily::new();
+        family.set_name(name.to_string());
+        family.set_field_type(metric_type);
+        family.set_metric(RepeatedField::from_vec(metrics));
+        family
+    }
+
+    fn some_families() -> Vec<MetricFamily> {
+        let mut trace = Exemplar::new();
+        trace.set_label(RepeatedField::from_vec(vec![label("trace_id", "KOO5S4vxi0o")]));
+        trace.set_value(0.67);
+        trace.set_timestamp(timestamp(1520879607, 789));
+
+        let mut counter = Counter::new();
+        counter.set_value(1027.0);
+        counter.set_exemplar(trace.clone());
+        counter.set_created_timestamp(timestamp(1520430000, 123));
+        let mut requests = Metric::new();
+        requests.set_counter(counter);
+        requests.set_label(RepeatedField::from_vec(vec![label("method", "post"),
+                                                        label("code", "200")]));
+        let mut requests = family("http_requests", MetricType::COUNTER, vec![requests]);
+        requests.set_help("The total number of \"HTTP\" requests.\nPer method".to_string());
+
+        let mut gauge = Gauge::new();
+        gauge.set_value(-3.5);
+        let mut temperature = Metric::new();
+        temperature.set_gauge(gauge);
+        temperature.set_timestamp_ms(1395066363500);
+        temperature.set_label(RepeatedField::from_vec(vec![label("room", "a \"b\" \\ c\n")]));
+        let mut temperature = family("room_temperature_celsius",
+                                     MetricType::GAUGE,
+                                     vec![temperature]);
+        temperature.set_unit("celsius".to_string());
+
+        let mut quantile = Quantile::new();
+        quantile.set_quantile(0.99);
+        quantile.set_value(0.003);
+        let mut summary = Summary::new();
+        summary.set_quantile(RepeatedField::from_vec(vec![quantile]));
+        summary.set_sample_sum(0.5);
+        summary.set_sample_count(100);
+        summary.set_created_timestamp(timestamp(1520430000, 0));
+        let mut rpc = Metric::new();
+        rpc.set_summary(summary);
+
+        let buckets = vec![(0.5, 129389, Some(trace)),
+                           (1.0, 133988, None),
+                           (f64::INFINITY, 144320, None)]
+            .into_iter()
+            .map(|(upper_bound, count, exemplar)| {
+                let mut bucket = Bucket::new();
+                bucket.set_upper_bound(upper_bound);
+                bucket.set_cumulative_count(count);
+                if let Some(exemplar) = exemplar {
+                    bucket.set_exemplar(exemplar);
+                }
+                bucket
+            })
+            .collect();
+        let mut histogram = Histogram::new();
+        histogram.set_bucket(RepeatedField::from_vec(buckets));
+        histogram.set_sample_sum(53423.0);
+        histogram.set_sample_count(144320);
+        histogram.set_created_timestamp(timestamp(1520430000, 500));
+        let mut latency = Metric::new();
+        latency.set_histogram(histogram);
+
+        let mut untyped = Untyped::new();
+        untyped.set_value(f64::NEG_INFINITY);
+        let mut other = Metric::new();
+        other.set_untyped(untyped);
+
+        vec![requests,
+             temperature,
+             family("rpc_duration_seconds", MetricType::SUMMARY, vec![rpc]),
+             family("request_duration_seconds", MetricType::HISTOGRAM, vec![latency]),
+             family("other", MetricType::UNTYPED, vec![other])]
+    }
+
+    #[test]
+    fn exposition() {
+        assert_eq!(families_to_openmetrics(&some_families()[..2]),
+                   "# TYPE http_requests counter\n\
+                    # HELP http_requests The total number of \\\"HTTP\\\" requests.\\nPer method\n\
+                    http_requests_total{method=\"post\",code=\"200\"} 1027 \
+                    # {trace_id=\"KOO5S4vxi0o\"} 0.67 1520879607.789\n\
+                    http_requests_created{method=\"post\",code=\"200\"} 1520430000.123\n\
+                    # TYPE room_temperature_celsius gauge\n\
+                    # UNIT room_temperature_celsius celsius\n\
+                    room_temperature_celsius{room=\"a \\\"b\\\" \\\\ c\\n\"} -3.5 1395066363.5\n\
+                    # EOF\n");
+    }
+
+    #[test]
+    fn counters_are_not_suffixed_twice() {
+        let mut metric = Metric::new();
+        metric.set_counter(Counter::new());
+        let mut unit_mismatch = family("requests_total", MetricType::COUNTER, vec![metric]);
+        unit_mismatch.set_unit("seconds".to_string());
+
+        assert_eq!(families_to_openmetrics(&[unit_mismatch]),
+                   "# TYPE requests counter\nrequests_total 0\n# EOF\n");
+    }
+
+    #[test]
+    fn round_trip_through_a_parser() {
+        let families = some_families();
+        let text = families_to_openmetrics(&families);
+        assert_eq!(parser::parse(&text), Ok(families));
+    }
+
+    // A strict enough OpenMetrics parser to read our own output back into metric families
+    mod parser {
+        use super::*;
+
+        pub fn parse(text: &str) -> Result<Vec<MetricFamily>, String> {
+            let body = match text.strip_suffix("# EOF\n") {
+                Some(body) => body,
+                None => return Err("missing # EOF".to_string()),
+            };
+            let mut families: Vec<MetricFamily> = Vec::new();
+            for line in body.lines() {
+                if let Some(metadata) = line.strip_prefix("# ") {
+                    let mut parts = metadata.splitn(3, ' ');
+                    let (keyword, name, value) = match (parts.next(), parts.next(), parts.next()) {
+                        (Some(k), Some(n), Some(v)) => (k, n, v),
+                        _ => return Err(format!("bad metadata {}", line)),
+                    };
+                    match keyword {
+                        "TYPE" => {
+                            if families.iter().any(|f| f.get_name() == name) {
+                                return Err(format!("{} is not contiguous", name));
+                            }
+                            let mut family = MetricFamily::new();
+                            family.set_name(name.to_string());
+                            family.set_field_type(parse_type(value)?);
+                            families.push(family);
+                        }
+                        "UNIT" | "HELP" => {
+                            let family = match families.last_mut() {
+                                Some(f) if f.get_name() == name && f.get_metric().is_empty() => f,
+                                _ => return Err(format!("misplaced {}", line)),
+                            };
+                            if keyword == "UNIT" {
+                                if !name.ends_with(&format!("_{}", value)) {
+                                    return Err(format!("unit does not match {}", name));
+                                }
+                                family.set_unit(value.to_string());
+                            } else {
+                                family.set_help(unescape(value));
+                            }
+                        }
+                        _ => return Err(format!("unknown metadata {}", line)),
+                    }
+                } else {
+                    let family = families.last_mut().ok_or("sample before # TYPE")?;
+                    add_sample(family, line)?;
+                }
+            }
+            Ok(families)
+        }
+
+        fn parse_type(value: &str) -> Result<MetricType, String> {
+            match value {
+                "counter" => Ok(MetricType::COUNTER),
+                "gauge" => Ok(MetricType::GAUGE),
+                "summary" => Ok(MetricType::SUMMARY),
+                "unknown" => Ok(MetricType::UNTYPED),
+                "histogram" => Ok(MetricType::HISTOGRAM),
+                _ => Err(format!("unknown type {}", value)),
+            }
+        }
+
+        fn add_sample(family: &mut MetricFamily, line: &str) -> Result<(), String> {
+            let (sample, exemplar) = match line.find(" # ") {
+                Some(i) => (&line[..i], Some(parse_exemplar(&line[i + 3..])?)),
+                None => (line, None),
+            };
+            let name_end = sample.find(['{', ' ']).ok_or("sample without value")?;
+            let (name, rest) = sample.split_at(name_end);
+            let (mut labels, rest) = parse_labels(rest)?;
+            let mut values = rest.split_whitespace();
+            let value = parse_float(values.next().ok_or("sample without value")?)?;
+            let timestamp = values.next().map(parse_float).transpose()?;
+
+            let suffix = name.strip_prefix(family.get_name()).ok_or("sample of another family")?;
+            let metric_type = family.get_field_type();
+            let extra = match (metric_type, suffix) {
+                (MetricType::SUMMARY, "") => Some("quantile"),
+                (MetricType::HISTOGRAM, "_bucket") => Some("le"),
+                _ => None,
+            };
+            let extra_value = match extra {
+                Some(extra) => {
+                    let position = labels.iter().position(|l| l.get_name() == extra);
+                    Some(parse_float(labels.remove(position.ok_or("missing label")?).get_value())?)
+                }
+                None => None,
+            };
+
+            let position = family.get_metric().iter().position(|m| m.get_label() == &labels[..]);
+            let metric = match position {
+                Some(i) => &mut family.mut_metric()[i],
+                None => {
+                    let mut metric = Metric::new();
+                    metric.set_label(RepeatedField::from_vec(labels));
+                    if let Some(timestamp) = timestamp {
+                        metric.set_timestamp_ms((timestamp * 1000.0).round() as i64);
+                    }
+                    family.mut_metric().push(metric);
+                    family.mut_metric().last_mut().unwrap()
+                }
+            };
+            match (metric_type, suffix) {
+                (MetricType::COUNTER, "_total") => {
+                    metric.mut_counter().set_value(value);
+                    if let Some(exemplar) = exemplar {
+                        metric.mut_counter().set_exemplar(exemplar);
+                    }
+                }
+                (MetricType::COUNTER, "_created") => {
+                    metric.mut_counter().set_created_timestamp(to_timestamp(value))
+                }
+                (MetricType::GAUGE, "") => metric.mut_gauge().set_value(value),
+                (MetricType::UNTYPED, "") => metric.mut_untyped().set_value(value),
+                (MetricType::SUMMARY, "") => {
+                    let mut quantile = Quantile::new();
+                    quantile.set_quantile(extra_value.unwrap());
+                    quantile.set_value(value);
+                    metric.mut_summary().mut_quantile().push(quantile);
+                }
+                (MetricType::SUMMARY, "_sum") => metric.mut_summary().set_sample_sum(value),
+                (MetricType::SUMMARY, "_count") => {
+                    metric.mut_summary().set_sample_count(value as u64)
+                }
+                (MetricType::SUMMARY, "_created") => {
+                    metric.mut_summary().set_created_timestamp(to_timestamp(value))
+                }
+                (MetricType::HISTOGRAM, "_bucket") => {
+                    let mut bucket = Bucket::new();
+                    bucket.set_upper_bound(extra_value.unwrap());
+                    bucket.set_cumulative_count(value as u64);
+                    if let Some(exemplar) = exemplar {
+                        bucket.set_exemplar(exemplar);
+                    }
+                    metric.mut_histogram().mut_bucket().push(bucket);
+                }
+                (MetricType::HISTOGRAM, "_sum") => metric.mut_histogram().set_sample_sum(value),
+                (MetricType::HISTOGRAM, "_count") => {
+                    metric.mut_histogram().set_sample_count(value as u64)
+                }
+                (MetricType::HISTOGRAM, "_created") => {
+                    metric.mut_histogram().set_created_timestamp(to_timestamp(value))
+                }
+                _ => return Err(format!("unexpected sample {}", line)),
+            }
+            Ok(())
+        }
+
+        fn parse_exemplar(text: &str) -> Result<Exemplar, String> {
+            let (labels, rest) = parse_labels(text)?;
+            if !text.starts_with('{') {
+                return Err("exemplar without labels".to_string());
+            }
+            let mut values = rest.split_whitespace();
+            let mut exemplar = Exemplar::new();
+            exemplar.set_label(RepeatedField::from_vec(labels));
+            exemplar.set_value(parse_float(values.next().ok_or("exemplar without value")?)?);
+            if let Some(timestamp) = values.next() {
+                exemplar.set_timestamp(to_timestamp(parse_float(timestamp)?));
+            }
+            Ok(exemplar)
+        }
+
+        // Parse an optional `{name="value",...}` label set, returning what follows it
+        fn parse_labels(text: &str) -> Result<(Vec<LabelPair>, &str), String> {
+            let mut labels = Vec::new();
+            let mut rest = match text.strip_prefix('{') {
+                Some(rest) => rest,
+                None => return Ok((labels, text)),
+            };
+            loop {
+                if let Some(after) = rest.strip_prefix('}') {
+                    return Ok((labels, after));
+                }
+                let eq = rest.find("=\"").ok_or("label without value")?;
+                let name = &rest[..eq];
+                rest = &rest[eq + 2..];
+                let mut value = String::new();
+                let mut chars = rest.char_indices();
+                let end = loop {
+                    match chars.next() {
+                        Some((i, '"')) => break i,
+                        Some((_, '\\')) => value.push('\\'),
+                        Some((_, c)) => {
+                            value.push(c);
+                            continue;
+                        }
+                        None => return Err("unterminated label value".to_string()),
+                    }
+                    // Keep escaped characters as they are, they are unescaped below
+                    match chars.next() {
+                        Some((_, c)) => value.push(c),
+                        None => return Err("unterminated label value".to_string()),
+                    }
+                };
+                labels.push(label(name, &unescape(&value)));
+                rest = &rest[end + 1..];
+                rest = rest.strip_prefix(',').unwrap_or(rest);
+            }
+        }
+
+        fn unescape(text: &str) -> String {
+            let mut unescaped = String::new();
+            let mut chars = text.chars();
+            while let Some(c) = chars.next() {
+                if c == '\\' {
+                    match chars.next() {
+                        Some('n') => unescaped.push('\n'),
+                        Some(c) => unescaped.push(c),
+                        None => unescaped.push('\\'),
+                    }
+                } else {
+                    unescaped.push(c);
+                }
+            }
+            unescaped
+        }
+
+        fn parse_float(text: &str) -> Result<f64, String> {
+            match text {
+                "+Inf" => Ok(f64::INFINITY),
+                "-Inf" => Ok(f64::NEG_INFINITY),
+                _ => text.parse().map_err(|_| format!("bad number {}", text)),
+            }
+        }
+
+        fn to_timestamp(seconds: f64) -> Timestamp {
+            let mut timestamp = Timestamp::new();
+            timestamp.set_seconds(seconds.floor() as i64);
+            // Our fixtures only have millisecond precision
+            timestamp.set_nanos(((seconds - seconds.floor()) * 1000.0).round() as i32 * 1_000_000);
+            timestamp
+        }
+    }
+}
@@ -3,15 +3,18 @@
 
 // https://github.com/Manishearth/rust-clippy/issues/702
 #![allow(unknown_lints)]
-#![allow(clippy)]
+#![allow(clippy::all)]
 
 #![cfg_attr(rustfmt, rustfmt_skip)]
 
-#![allow(box_pointers)]
+#![allow(bare_trait_objects)]
+#![allow(mismatched_lifetime_syntaxes)]
 #![allow(dead_code)]
+#![allow(missing_docs)]
 #![allow(non_camel_case_types)]
 #![allow(non_snake_case)]
 #![allow(non_upper_case_globals)]
+#![allow(static_mut_refs)]
 #![allow(trivial_casts)]
 #![allow(unsafe_code)]
 #![allow(unused_imports)]
@@ -20,14 +23,14 @@
 use protobuf::Message as Message_imported_for_functions;
 use protobuf::ProtobufEnum as ProtobufEnum_imported_for_functions;
 
-#[derive(Clone,Default)]
+#[derive(PartialEq,Clone,Default)]
 pub struct LabelPair {
     // message fields
     name: ::protobuf::SingularField<::std::string::String>,
     value: ::protobuf::SingularField<::std::string::String>,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
-    cached_size: ::std::cell::Cell<u32>,
+    cached_size: ::protobuf::CachedSize,
 }
 
 // see codegen.rs for the explanation why impl Sync explicitly
@@ -44,14 +47,7 @@ impl LabelPair {
             ptr: 0 as *const LabelPair,
         };
         unsafe {
-            instance.get(|| {
-                LabelPair {
-                    name: ::protobuf::SingularField::none(),
-                    value: ::protobuf::SingularField::none(),
-                    unknown_fields: ::protobuf::UnknownFields::new(),
-                    cached_size: ::std::cell::Cell::new(0),
-                }
-            })
+            instance.get(LabelPair::new)
         }
     }
 
@@ -75,7 +71,7 @@ impl LabelPair {
     pub fn mut_name(&mut self) -> &mut ::std::string::String {
         if self.name.is_none() {
             self.name.set_default();
-        };
+        }
         self.name.as_mut().unwrap()
     }
 
@@ -91,6 +87,14 @@ impl LabelPair {
         }
     }
 
+    fn get_name_for_reflect(&self) -> &::protobuf::SingularField<::std::string::String> {
+        &self.name
+    }
+
+    fn mut_name_for_reflect(&mut self) -> &mut ::protobuf::SingularField<::std::string::String> {
+        &mut self.name
+    }
+
     // optional string value = 2;
 
     pub fn clear_value(&mut self) {
@@ -111,7 +115,7 @@ impl LabelPair {
     pub fn mut_value(&mut self) -> &mut ::std::string::String {
         if self.value.is_none() {
             self.value.set_default();
-        };
+        }
         self.value.as_mut().unwrap()
     }
 
@@ -126,6 +130,14 @@ impl LabelPair {
             None => "",
         }
     }
+
+    fn get_value_for_reflect(&self) -> &::protobuf::SingularField<::std::string::String> {
+        &self.value
+    }
+
+    fn mut_value_for_reflect(&mut self) -> &mut ::protobuf::SingularField<::std::string::String> {
+        &mut self.value
+    }
 }
 
 impl ::protobuf::Message for LabelPair {
@@ -134,17 +146,17 @@ impl ::protobuf::Message for LabelPair {
     }
 
     fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
-        while !try!(is.eof()) {
-            let (field_number, wire_type) = try!(is.read_tag_unpack());
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
-                    try!(::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.name));
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.name)?;
                 },
                 2 => {
-                    try!(::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.value));
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.value)?;
                 },
                 _ => {
-                    try!(::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields()));
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -155,25 +167,25 @@ impl ::protobuf::Message for LabelPair {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        for value in &self.name {
-            my_size += ::protobuf::rt::string_size(1, &value);
-        };
-        for value in &self.value {
-            my_size += ::protobuf::rt::string_size(2, &value);
-        };
+        if let Some(ref v) = self.name.as_ref() {
+            my_size += ::protobuf::rt::string_size(1, &v);
+        }
+        if let Some(ref v) = self.value.as_ref() {
+            my_size += ::protobuf::rt::string_size(2, &v);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
-        if let Some(v) = self.name.as_ref() {
-            try!(os.write_string(1, &v));
-        };
-        if let Some(v) = self.value.as_ref() {
-            try!(os.write_string(2, &v));
-        };
-        try!(os.write_unknown_fields(self.get_unknown_fields()));
+        if let Some(ref v) = self.name.as_ref() {
+            os.write_string(1, &v)?;
+        }
+        if let Some(ref v) = self.value.as_ref() {
+            os.write_string(2, &v)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
 
@@ -189,13 +201,15 @@ impl ::protobuf::Message for LabelPair {
         &mut self.unknown_fields
     }
 
-    fn type_id(&self) -> ::std::any::TypeId {
-        ::std::any::TypeId::of::<LabelPair>()
-    }
-
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
 
     fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
         ::protobuf::MessageStatic::descriptor_static(None::<Self>)
@@ -215,15 +229,15 @@ impl ::protobuf::MessageStatic for LabelPair {
         unsafe {
             descriptor.get(|| {
                 let mut fields = ::std::vec::Vec::new();
-                fields.push(::protobuf::reflect::accessor::make_singular_string_accessor(
+                fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
                     "name",
-                    LabelPair::has_name,
-                    LabelPair::get_name,
+                    LabelPair::get_name_for_reflect,
+                    LabelPair::mut_name_for_reflect,
                 ));
-                fields.push(::protobuf::reflect::accessor::make_singular_string_accessor(
+                fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
                     "value",
-                    LabelPair::has_value,
-                    LabelPair::get_value,
+                    LabelPair::get_value_for_reflect,
+                    LabelPair::mut_value_for_reflect,
                 ));
                 ::protobuf::reflect::MessageDescriptor::new::<LabelPair>(
                     "LabelPair",
@@ -243,27 +257,25 @@ impl ::protobuf::Clear for LabelPair {
     }
 }
 
-impl ::std::cmp::PartialEq for LabelPair {
-    fn eq(&self, other: &LabelPair) -> bool {
-        self.name == other.name &&
-        self.value == other.value &&
-        self.unknown_fields == other.unknown_fields
-    }
-}
-
 impl ::std::fmt::Debug for LabelPair {
     fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-#[derive(Clone,Default)]
+impl ::protobuf::reflect::ProtobufValue for LabelPair {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
 pub struct Gauge {
     // message fields
     value: ::std::option::Option<f64>,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
-    cached_size: ::std::cell::Cell<u32>,
+    cached_size: ::protobuf::CachedSize,
 }
 
 // see codegen.rs for the explanation why impl Sync explicitly
@@ -280,13 +292,7 @@ impl Gauge {
             ptr: 0 as *const Gauge,
         };
         unsafe {
-            instance.get(|| {
-                Gauge {
-                    value: ::std::option::Option::None,
-                    unknown_fields: ::protobuf::UnknownFields::new(),
-                    cached_size: ::std::cell::Cell::new(0),
-                }
-            })
+            instance.get(Gauge::new)
         }
     }
 
@@ -308,6 +314,14 @@ impl Gauge {
     pub fn get_value(&self) -> f64 {
         self.value.unwrap_or(0.)
     }
+
+    fn get_value_for_reflect(&self) -> &::std::option::Option<f64> {
+        &self.value
+    }
+
+    fn mut_value_for_reflect(&mut self) -> &mut ::std::option::Option<f64> {
+        &mut self.value
+    }
 }
 
 impl ::protobuf::Message for Gauge {
@@ -316,18 +330,18 @@ impl ::protobuf::Message for Gauge {
     }
 
     fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
-        while !try!(is.eof()) {
-            let (field_number, wire_type) = try!(is.read_tag_unpack());
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
                     if wire_type != ::protobuf::wire_format::WireTypeFixed64 {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    };
-                    let tmp = try!(is.read_double());
+                    }
+                    let tmp = is.read_double()?;
                     self.value = ::std::option::Option::Some(tmp);
                 },
                 _ => {
-                    try!(::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields()));
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -338,9 +352,9 @@ impl ::protobuf::Message for Gauge {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        if self.value.is_some() {
+        if let Some(v) = self.value {
             my_size += 9;
-        };
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -348,9 +362,9 @@ impl ::protobuf::Message for Gauge {
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
         if let Some(v) = self.value {
-            try!(os.write_double(1, v));
-        };
-        try!(os.write_unknown_fields(self.get_unknown_fields()));
+            os.write_double(1, v)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
 
@@ -366,13 +380,15 @@ impl ::protobuf::Message for Gauge {
         &mut self.unknown_fields
     }
 
-    fn type_id(&self) -> ::std::any::TypeId {
-        ::std::any::TypeId::of::<Gauge>()
-    }
-
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
 
     fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
         ::protobuf::MessageStatic::descriptor_static(None::<Self>)
@@ -392,10 +408,10 @@ impl ::protobuf::MessageStatic for Gauge {
         unsafe {
             descriptor.get(|| {
                 let mut fields = ::std::vec::Vec::new();
-                fields.push(::protobuf::reflect::accessor::make_singular_f64_accessor(
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeDouble>(
                     "value",
-                    Gauge::has_value,
-                    Gauge::get_value,
+                    Gauge::get_value_for_reflect,
+                    Gauge::mut_value_for_reflect,
                 ));
                 ::protobuf::reflect::MessageDescriptor::new::<Gauge>(
                     "Gauge",
@@ -414,26 +430,27 @@ impl ::protobuf::Clear for Gauge {
     }
 }
 
-impl ::std::cmp::PartialEq for Gauge {
-    fn eq(&self, other: &Gauge) -> bool {
-        self.value == other.value &&
-        self.unknown_fields == other.unknown_fields
-    }
-}
-
 impl ::std::fmt::Debug for Gauge {
     fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-#[derive(Clone,Default)]
+impl ::protobuf::reflect::ProtobufValue for Gauge {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
 pub struct Counter {
     // message fields
     value: ::std::option::Option<f64>,
+    exemplar: ::protobuf::SingularPtrField<Exemplar>,
+    created_timestamp: ::protobuf::SingularPtrField<::protobuf::well_known_types::Timestamp>,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
-    cached_size: ::std::cell::Cell<u32>,
+    cached_size: ::protobuf::CachedSize,
 }
 
 // see codegen.rs for the explanation why impl Sync explicitly
@@ -450,13 +467,7 @@ impl Counter {
             ptr: 0 as *const Counter,
         };
         unsafe {
-            instance.get(|| {
-                Counter {
-                    value: ::std::option::Option::None,
-                    unknown_fields: ::protobuf::UnknownFields::new(),
-                    cached_size: ::std::cell::Cell::new(0),
-                }
-            })
+            instance.get(Counter::new)
         }
     }
 
@@ -478,26 +489,132 @@ impl Counter {
     pub fn get_value(&self) -> f64 {
         self.value.unwrap_or(0.)
     }
+
+    fn get_value_for_reflect(&self) -> &::std::option::Option<f64> {
+        &self.value
+    }
+
+    fn mut_value_for_reflect(&mut self) -> &mut ::std::option::Option<f64> {
+        &mut self.value
+    }
+
+    // optional .io.prometheus.client.Exemplar exemplar = 2;
+
+    pub fn clear_exemplar(&mut self) {
+        self.exemplar.clear();
+    }
+
+    pub fn has_exemplar(&self) -> bool {
+        self.exemplar.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_exemplar(&mut self, v: Exemplar) {
+        self.exemplar = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_exemplar(&mut self) -> &mut Exemplar {
+        if self.exemplar.is_none() {
+            self.exemplar.set_default();
+        }
+        self.exemplar.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_exemplar(&mut self) -> Exemplar {
+        self.exemplar.take().unwrap_or_else(|| Exemplar::new())
+    }
+
+    pub fn get_exemplar(&self) -> &Exemplar {
+        self.exemplar.as_ref().unwrap_or_else(|| Exemplar::default_instance())
+    }
+
+    fn get_exemplar_for_reflect(&self) -> &::protobuf::SingularPtrField<Exemplar> {
+        &self.exemplar
+    }
+
+    fn mut_exemplar_for_reflect(&mut self) -> &mut ::protobuf::SingularPtrField<Exemplar> {
+        &mut self.exemplar
+    }
+
+    // optional .google.protobuf.Timestamp created_timestamp = 3;
+
+    pub fn clear_created_timestamp(&mut self) {
+        self.created_timestamp.clear();
+    }
+
+    pub fn has_created_timestamp(&self) -> bool {
+        self.created_timestamp.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_created_timestamp(&mut self, v: ::protobuf::well_known_types::Timestamp) {
+        self.created_timestamp = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_created_timestamp(&mut self) -> &mut ::protobuf::well_known_types::Timestamp {
+        if self.created_timestamp.is_none() {
+            self.created_timestamp.set_default();
+        }
+        self.created_timestamp.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_created_timestamp(&mut self) -> ::protobuf::well_known_types::Timestamp {
+        self.created_timestamp.take().unwrap_or_else(|| ::protobuf::well_known_types::Timestamp::new())
+    }
+
+    pub fn get_created_timestamp(&self) -> &::protobuf::well_known_types::Timestamp {
+        self.created_timestamp.as_ref().unwrap_or_else(|| ::protobuf::well_known_types::Timestamp::default_instance())
+    }
+
+    fn get_created_timestamp_for_reflect(&self) -> &::protobuf::SingularPtrField<::protobuf::well_known_types::Timestamp> {
+        &self.created_timestamp
+    }
+
+    fn mut_created_timestamp_for_reflect(&mut self) -> &mut ::protobuf::SingularPtrField<::protobuf::well_known_types::Timestamp> {
+        &mut self.created_timestamp
+    }
 }
 
 impl ::protobuf::Message for Counter {
     fn is_initialized(&self) -> bool {
+        for v in &self.exemplar {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.created_timestamp {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
         true
     }
 
     fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
-        while !try!(is.eof()) {
-            let (field_number, wire_type) = try!(is.read_tag_unpack());
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
                     if wire_type != ::protobuf::wire_format::WireTypeFixed64 {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    };
-                    let tmp = try!(is.read_double());
+                    }
+                    let tmp = is.read_double()?;
                     self.value = ::std::option::Option::Some(tmp);
                 },
+                2 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.exemplar)?;
+                },
+                3 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.created_timestamp)?;
+                },
                 _ => {
-                    try!(::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields()));
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -508,9 +625,17 @@ impl ::protobuf::Message for Counter {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        if self.value.is_some() {
+        if let Some(v) = self.value {
             my_size += 9;
-        };
+        }
+        if let Some(ref v) = self.exemplar.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        if let Some(ref v) = self.created_timestamp.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -518,9 +643,19 @@ impl ::protobuf::Message for Counter {
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
         if let Some(v) = self.value {
-            try!(os.write_double(1, v));
-        };
-        try!(os.write_unknown_fields(self.get_unknown_fields()));
+            os.write_double(1, v)?;
+        }
+        if let Some(ref v) = self.exemplar.as_ref() {
+            os.write_tag(2, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        if let Some(ref v) = self.created_timestamp.as_ref() {
+            os.write_tag(3, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
 
@@ -536,13 +671,15 @@ impl ::protobuf::Message for Counter {
         &mut self.unknown_fields
     }
 
-    fn type_id(&self) -> ::std::any::TypeId {
-        ::std::any::TypeId::of::<Counter>()
-    }
-
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
 
     fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
         ::protobuf::MessageStatic::descriptor_static(None::<Self>)
@@ -562,10 +699,20 @@ impl ::protobuf::MessageStatic for Counter {
         unsafe {
             descriptor.get(|| {
                 let mut fields = ::std::vec::Vec::new();
-                fields.push(::protobuf::reflect::accessor::make_singular_f64_accessor(
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeDouble>(
                     "value",
-                    Counter::has_value,
-                    Counter::get_value,
+                    Counter::get_value_for_reflect,
+                    Counter::mut_value_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Exemplar>>(
+                    "exemplar",
+                    Counter::get_exemplar_for_reflect,
+                    Counter::mut_exemplar_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<::protobuf::well_known_types::Timestamp>>(
+                    "created_timestamp",
+                    Counter::get_created_timestamp_for_reflect,
+                    Counter::mut_created_timestamp_for_reflect,
                 ));
                 ::protobuf::reflect::MessageDescriptor::new::<Counter>(
                     "Counter",
@@ -580,31 +727,32 @@ impl ::protobuf::MessageStatic for Counter {
 impl ::protobuf::Clear for Counter {
     fn clear(&mut self) {
         self.clear_value();
+        self.clear_exemplar();
+        self.clear_created_timestamp();
         self.unknown_fields.clear();
     }
 }
 
-impl ::std::cmp::PartialEq for Counter {
-    fn eq(&self, other: &Counter) -> bool {
-        self.value == other.value &&
-        self.unknown_fields == other.unknown_fields
-    }
-}
-
 impl ::std::fmt::Debug for Counter {
     fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-#[derive(Clone,Default)]
+impl ::protobuf::reflect::ProtobufValue for Counter {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
 pub struct Quantile {
     // message fields
     quantile: ::std::option::Option<f64>,
     value: ::std::option::Option<f64>,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
-    cached_size: ::std::cell::Cell<u32>,
+    cached_size: ::protobuf::CachedSize,
 }
 
 // see codegen.rs for the explanation why impl Sync explicitly
@@ -621,14 +769,7 @@ impl Quantile {
             ptr: 0 as *const Quantile,
         };
         unsafe {
-            instance.get(|| {
-                Quantile {
-                    quantile: ::std::option::Option::None,
-                    value: ::std::option::Option::None,
-                    unknown_fields: ::protobuf::UnknownFields::new(),
-                    cached_size: ::std::cell::Cell::new(0),
-                }
-            })
+            instance.get(Quantile::new)
         }
     }
 
@@ -651,6 +792,14 @@ impl Quantile {
         self.quantile.unwrap_or(0.)
     }
 
+    fn get_quantile_for_reflect(&self) -> &::std::option::Option<f64> {
+        &self.quantile
+    }
+
+    fn mut_quantile_for_reflect(&mut self) -> &mut ::std::option::Option<f64> {
+        &mut self.quantile
+    }
+
     // optional double value = 2;
 
     pub fn clear_value(&mut self) {
@@ -669,6 +818,14 @@ impl Quantile {
     pub fn get_value(&self) -> f64 {
         self.value.unwrap_or(0.)
     }
+
+    fn get_value_for_reflect(&self) -> &::std::option::Option<f64> {
+        &self.value
+    }
+
+    fn mut_value_for_reflect(&mut self) -> &mut ::std::option::Option<f64> {
+        &mut self.value
+    }
 }
 
 impl ::protobuf::Message for Quantile {
@@ -677,25 +834,25 @@ impl ::protobuf::Message for Quantile {
     }
 
     fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
-        while !try!(is.eof()) {
-            let (field_number, wire_type) = try!(is.read_tag_unpack());
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
                     if wire_type != ::protobuf::wire_format::WireTypeFixed64 {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    };
-                    let tmp = try!(is.read_double());
+                    }
+                    let tmp = is.read_double()?;
                     self.quantile = ::std::option::Option::Some(tmp);
                 },
                 2 => {
                     if wire_type != ::protobuf::wire_format::WireTypeFixed64 {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    };
-                    let tmp = try!(is.read_double());
+                    }
+                    let tmp = is.read_double()?;
                     self.value = ::std::option::Option::Some(tmp);
                 },
                 _ => {
-                    try!(::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields()));
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -706,12 +863,12 @@ impl ::protobuf::Message for Quantile {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        if self.quantile.is_some() {
+        if let Some(v) = self.quantile {
             my_size += 9;
-        };
-        if self.value.is_some() {
+        }
+        if let Some(v) = self.value {
             my_size += 9;
-        };
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -719,12 +876,12 @@ impl ::protobuf::Message for Quantile {
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
         if let Some(v) = self.quantile {
-            try!(os.write_double(1, v));
-        };
+            os.write_double(1, v)?;
+        }
         if let Some(v) = self.value {
-            try!(os.write_double(2, v));
-        };
-        try!(os.write_unknown_fields(self.get_unknown_fields()));
+            os.write_double(2, v)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
 
@@ -740,13 +897,15 @@ impl ::protobuf::Message for Quantile {
         &mut self.unknown_fields
     }
 
-    fn type_id(&self) -> ::std::any::TypeId {
-        ::std::any::TypeId::of::<Quantile>()
-    }
-
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
 
     fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
         ::protobuf::MessageStatic::descriptor_static(None::<Self>)
@@ -766,15 +925,15 @@ impl ::protobuf::MessageStatic for Quantile {
         unsafe {
             descriptor.get(|| {
                 let mut fields = ::std::vec::Vec::new();
-                fields.push(::protobuf::reflect::accessor::make_singular_f64_accessor(
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeDouble>(
                     "quantile",
-                    Quantile::has_quantile,
-                    Quantile::get_quantile,
+                    Quantile::get_quantile_for_reflect,
+                    Quantile::mut_quantile_for_reflect,
                 ));
-                fields.push(::protobuf::reflect::accessor::make_singular_f64_accessor(
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeDouble>(
                     "value",
-                    Quantile::has_value,
-                    Quantile::get_value,
+                    Quantile::get_value_for_reflect,
+                    Quantile::mut_value_for_reflect,
                 ));
                 ::protobuf::reflect::MessageDescriptor::new::<Quantile>(
                     "Quantile",
@@ -794,29 +953,28 @@ impl ::protobuf::Clear for Quantile {
     }
 }
 
-impl ::std::cmp::PartialEq for Quantile {
-    fn eq(&self, other: &Quantile) -> bool {
-        self.quantile == other.quantile &&
-        self.value == other.value &&
-        self.unknown_fields == other.unknown_fields
-    }
-}
-
 impl ::std::fmt::Debug for Quantile {
     fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-#[derive(Clone,Default)]
+impl ::protobuf::reflect::ProtobufValue for Quantile {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
 pub struct Summary {
     // message fields
     sample_count: ::std::option::Option<u64>,
     sample_sum: ::std::option::Option<f64>,
     quantile: ::protobuf::RepeatedField<Quantile>,
+    created_timestamp: ::protobuf::SingularPtrField<::protobuf::well_known_types::Timestamp>,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
-    cached_size: ::std::cell::Cell<u32>,
+    cached_size: ::protobuf::CachedSize,
 }
 
 // see codegen.rs for the explanation why impl Sync explicitly
@@ -833,15 +991,7 @@ impl Summary {
             ptr: 0 as *const Summary,
         };
         unsafe {
-            instance.get(|| {
-                Summary {
-                    sample_count: ::std::option::Option::None,
-                    sample_sum: ::std::option::Option::None,
-                    quantile: ::protobuf::RepeatedField::new(),
-                    unknown_fields: ::protobuf::UnknownFields::new(),
-                    cached_size: ::std::cell::Cell::new(0),
-                }
-            })
+            instance.get(Summary::new)
         }
     }
 
@@ -864,6 +1014,14 @@ impl Summary {
         self.sample_count.unwrap_or(0)
     }
 
+    fn get_sample_count_for_reflect(&self) -> &::std::option::Option<u64> {
+        &self.sample_count
+    }
+
+    fn mut_sample_count_for_reflect(&mut self) -> &mut ::std::option::Option<u64> {
+        &mut self.sample_count
+    }
+
     // optional double sample_sum = 2;
 
     pub fn clear_sample_sum(&mut self) {
@@ -883,6 +1041,14 @@ impl Summary {
         self.sample_sum.unwrap_or(0.)
     }
 
+    fn get_sample_sum_for_reflect(&self) -> &::std::option::Option<f64> {
+        &self.sample_sum
+    }
+
+    fn mut_sample_sum_for_reflect(&mut self) -> &mut ::std::option::Option<f64> {
+        &mut self.sample_sum
+    }
+
     // repeated .io.prometheus.client.Quantile quantile = 3;
 
     pub fn clear_quantile(&mut self) {
@@ -907,36 +1073,98 @@ impl Summary {
     pub fn get_quantile(&self) -> &[Quantile] {
         &self.quantile
     }
+
+    fn get_quantile_for_reflect(&self) -> &::protobuf::RepeatedField<Quantile> {
+        &self.quantile
+    }
+
+    fn mut_quantile_for_reflect(&mut self) -> &mut ::protobuf::RepeatedField<Quantile> {
+        &mut self.quantile
+    }
+
+    // optional .google.protobuf.Timestamp created_timestamp = 4;
+
+    pub fn clear_created_timestamp(&mut self) {
+        self.created_timestamp.clear();
+    }
+
+    pub fn has_created_timestamp(&self) -> bool {
+        self.created_timestamp.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_created_timestamp(&mut self, v: ::protobuf::well_known_types::Timestamp) {
+        self.created_timestamp = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_created_timestamp(&mut self) -> &mut ::protobuf::well_known_types::Timestamp {
+        if self.created_timestamp.is_none() {
+            self.created_timestamp.set_default();
+        }
+        self.created_timestamp.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_created_timestamp(&mut self) -> ::protobuf::well_known_types::Timestamp {
+        self.created_timestamp.take().unwrap_or_else(|| ::protobuf::well_known_types::Timestamp::new())
+    }
+
+    pub fn get_created_timestamp(&self) -> &::protobuf::well_known_types::Timestamp {
+        self.created_timestamp.as_ref().unwrap_or_else(|| ::protobuf::well_known_types::Timestamp::default_instance())
+    }
+
+    fn get_created_timestamp_for_reflect(&self) -> &::protobuf::SingularPtrField<::protobuf::well_known_types::Timestamp> {
+        &self.created_timestamp
+    }
+
+    fn mut_created_timestamp_for_reflect(&mut self) -> &mut ::protobuf::SingularPtrField<::protobuf::well_known_types::Timestamp> {
+        &mut self.created_timestamp
+    }
 }
 
 impl ::protobuf::Message for Summary {
     fn is_initialized(&self) -> bool {
+        for v in &self.quantile {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.created_timestamp {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
         true
     }
 
     fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
-        while !try!(is.eof()) {
-            let (field_number, wire_type) = try!(is.read_tag_unpack());
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    };
-                    let tmp = try!(is.read_uint64());
+                    }
+                    let tmp = is.read_uint64()?;
                     self.sample_count = ::std::option::Option::Some(tmp);
                 },
                 2 => {
                     if wire_type != ::protobuf::wire_format::WireTypeFixed64 {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    };
-                    let tmp = try!(is.read_double());
+                    }
+                    let tmp = is.read_double()?;
                     self.sample_sum = ::std::option::Option::Some(tmp);
                 },
                 3 => {
-                    try!(::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.quantile));
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.quantile)?;
+                },
+                4 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.created_timestamp)?;
                 },
                 _ => {
-                    try!(::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields()));
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -947,16 +1175,20 @@ impl ::protobuf::Message for Summary {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        for value in &self.sample_count {
-            my_size += ::protobuf::rt::value_size(1, *value, ::protobuf::wire_format::WireTypeVarint);
-        };
-        if self.sample_sum.is_some() {
+        if let Some(v) = self.sample_count {
+            my_size += ::protobuf::rt::value_size(1, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(v) = self.sample_sum {
             my_size += 9;
-        };
+        }
         for value in &self.quantile {
             let len = value.compute_size();
             my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         };
+        if let Some(ref v) = self.created_timestamp.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -964,17 +1196,22 @@ impl ::protobuf::Message for Summary {
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
         if let Some(v) = self.sample_count {
-            try!(os.write_uint64(1, v));
-        };
+            os.write_uint64(1, v)?;
+        }
         if let Some(v) = self.sample_sum {
-            try!(os.write_double(2, v));
-        };
+            os.write_double(2, v)?;
+        }
         for v in &self.quantile {
-            try!(os.write_tag(3, ::protobuf::wire_format::WireTypeLengthDelimited));
-            try!(os.write_raw_varint32(v.get_cached_size()));
-            try!(v.write_to_with_cached_sizes(os));
-        };
-        try!(os.write_unknown_fields(self.get_unknown_fields()));
+            os.write_tag(3, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        if let Some(ref v) = self.created_timestamp.as_ref() {
+            os.write_tag(4, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
 
@@ -990,13 +1227,15 @@ impl ::protobuf::Message for Summary {
         &mut self.unknown_fields
     }
 
-    fn type_id(&self) -> ::std::any::TypeId {
-        ::std::any::TypeId::of::<Summary>()
-    }
-
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
 
     fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
         ::protobuf::MessageStatic::descriptor_static(None::<Self>)
@@ -1016,19 +1255,25 @@ impl ::protobuf::MessageStatic for Summary {
         unsafe {
             descriptor.get(|| {
                 let mut fields = ::std::vec::Vec::new();
-                fields.push(::protobuf::reflect::accessor::make_singular_u64_accessor(
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
                     "sample_count",
-                    Summary::has_sample_count,
-                    Summary::get_sample_count,
+                    Summary::get_sample_count_for_reflect,
+                    Summary::mut_sample_count_for_reflect,
                 ));
-                fields.push(::protobuf::reflect::accessor::make_singular_f64_accessor(
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeDouble>(
                     "sample_sum",
-                    Summary::has_sample_sum,
-                    Summary::get_sample_sum,
+                    Summary::get_sample_sum_for_reflect,
+                    Summary::mut_sample_sum_for_reflect,
                 ));
-                fields.push(::protobuf::reflect::accessor::make_repeated_message_accessor(
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Quantile>>(
                     "quantile",
-                    Summary::get_quantile,
+                    Summary::get_quantile_for_reflect,
+                    Summary::mut_quantile_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<::protobuf::well_known_types::Timestamp>>(
+                    "created_timestamp",
+                    Summary::get_created_timestamp_for_reflect,
+                    Summary::mut_created_timestamp_for_reflect,
                 ));
                 ::protobuf::reflect::MessageDescriptor::new::<Summary>(
                     "Summary",
@@ -1045,32 +1290,30 @@ impl ::protobuf::Clear for Summary {
         self.clear_sample_count();
         self.clear_sample_sum();
         self.clear_quantile();
+        self.clear_created_timestamp();
         self.unknown_fields.clear();
     }
 }
 
-impl ::std::cmp::PartialEq for Summary {
-    fn eq(&self, other: &Summary) -> bool {
-        self.sample_count == other.sample_count &&
-        self.sample_sum == other.sample_sum &&
-        self.quantile == other.quantile &&
-        self.unknown_fields == other.unknown_fields
-    }
-}
-
 impl ::std::fmt::Debug for Summary {
     fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-#[derive(Clone,Default)]
+impl ::protobuf::reflect::ProtobufValue for Summary {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
 pub struct Untyped {
     // message fields
     value: ::std::option::Option<f64>,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
-    cached_size: ::std::cell::Cell<u32>,
+    cached_size: ::protobuf::CachedSize,
 }
 
 // see codegen.rs for the explanation why impl Sync explicitly
@@ -1087,13 +1330,7 @@ impl Untyped {
             ptr: 0 as *const Untyped,
         };
         unsafe {
-            instance.get(|| {
-                Untyped {
-                    value: ::std::option::Option::None,
-                    unknown_fields: ::protobuf::UnknownFields::new(),
-                    cached_size: ::std::cell::Cell::new(0),
-                }
-            })
+            instance.get(Untyped::new)
         }
     }
 
@@ -1115,6 +1352,14 @@ impl Untyped {
     pub fn get_value(&self) -> f64 {
         self.value.unwrap_or(0.)
     }
+
+    fn get_value_for_reflect(&self) -> &::std::option::Option<f64> {
+        &self.value
+    }
+
+    fn mut_value_for_reflect(&mut self) -> &mut ::std::option::Option<f64> {
+        &mut self.value
+    }
 }
 
 impl ::protobuf::Message for Untyped {
@@ -1123,18 +1368,18 @@ impl ::protobuf::Message for Untyped {
     }
 
     fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
-        while !try!(is.eof()) {
-            let (field_number, wire_type) = try!(is.read_tag_unpack());
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
                     if wire_type != ::protobuf::wire_format::WireTypeFixed64 {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    };
-                    let tmp = try!(is.read_double());
+                    }
+                    let tmp = is.read_double()?;
                     self.value = ::std::option::Option::Some(tmp);
                 },
                 _ => {
-                    try!(::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields()));
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -1145,9 +1390,9 @@ impl ::protobuf::Message for Untyped {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        if self.value.is_some() {
+        if let Some(v) = self.value {
             my_size += 9;
-        };
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -1155,9 +1400,9 @@ impl ::protobuf::Message for Untyped {
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
         if let Some(v) = self.value {
-            try!(os.write_double(1, v));
-        };
-        try!(os.write_unknown_fields(self.get_unknown_fields()));
+            os.write_double(1, v)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
 
@@ -1173,13 +1418,15 @@ impl ::protobuf::Message for Untyped {
         &mut self.unknown_fields
     }
 
-    fn type_id(&self) -> ::std::any::TypeId {
-        ::std::any::TypeId::of::<Untyped>()
-    }
-
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
 
     fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
         ::protobuf::MessageStatic::descriptor_static(None::<Self>)
@@ -1199,10 +1446,10 @@ impl ::protobuf::MessageStatic for Untyped {
         unsafe {
             descriptor.get(|| {
                 let mut fields = ::std::vec::Vec::new();
-                fields.push(::protobuf::reflect::accessor::make_singular_f64_accessor(
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeDouble>(
                     "value",
-                    Untyped::has_value,
-                    Untyped::get_value,
+                    Untyped::get_value_for_reflect,
+                    Untyped::mut_value_for_reflect,
                 ));
                 ::protobuf::reflect::MessageDescriptor::new::<Untyped>(
                     "Untyped",
@@ -1221,28 +1468,28 @@ impl ::protobuf::Clear for Untyped {
     }
 }
 
-impl ::std::cmp::PartialEq for Untyped {
-    fn eq(&self, other: &Untyped) -> bool {
-        self.value == other.value &&
-        self.unknown_fields == other.unknown_fields
-    }
-}
-
 impl ::std::fmt::Debug for Untyped {
     fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-#[derive(Clone,Default)]
+impl ::protobuf::reflect::ProtobufValue for Untyped {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
 pub struct Histogram {
     // message fields
     sample_count: ::std::option::Option<u64>,
     sample_sum: ::std::option::Option<f64>,
     bucket: ::protobuf::RepeatedField<Bucket>,
+    created_timestamp: ::protobuf::SingularPtrField<::protobuf::well_known_types::Timestamp>,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
-    cached_size: ::std::cell::Cell<u32>,
+    cached_size: ::protobuf::CachedSize,
 }
 
 // see codegen.rs for the explanation why impl Sync explicitly
@@ -1259,15 +1506,7 @@ impl Histogram {
             ptr: 0 as *const Histogram,
         };
         unsafe {
-            instance.get(|| {
-                Histogram {
-                    sample_count: ::std::option::Option::None,
-                    sample_sum: ::std::option::Option::None,
-                    bucket: ::protobuf::RepeatedField::new(),
-                    unknown_fields: ::protobuf::UnknownFields::new(),
-                    cached_size: ::std::cell::Cell::new(0),
-                }
-            })
+            instance.get(Histogram::new)
         }
     }
 
@@ -1290,6 +1529,14 @@ impl Histogram {
         self.sample_count.unwrap_or(0)
     }
 
+    fn get_sample_count_for_reflect(&self) -> &::std::option::Option<u64> {
+        &self.sample_count
+    }
+
+    fn mut_sample_count_for_reflect(&mut self) -> &mut ::std::option::Option<u64> {
+        &mut self.sample_count
+    }
+
     // optional double sample_sum = 2;
 
     pub fn clear_sample_sum(&mut self) {
@@ -1309,6 +1556,14 @@ impl Histogram {
         self.sample_sum.unwrap_or(0.)
     }
 
+    fn get_sample_sum_for_reflect(&self) -> &::std::option::Option<f64> {
+        &self.sample_sum
+    }
+
+    fn mut_sample_sum_for_reflect(&mut self) -> &mut ::std::option::Option<f64> {
+        &mut self.sample_sum
+    }
+
     // repeated .io.prometheus.client.Bucket bucket = 3;
 
     pub fn clear_bucket(&mut self) {
@@ -1333,36 +1588,98 @@ impl Histogram {
     pub fn get_bucket(&self) -> &[Bucket] {
         &self.bucket
     }
+
+    fn get_bucket_for_reflect(&self) -> &::protobuf::RepeatedField<Bucket> {
+        &self.bucket
+    }
+
+    fn mut_bucket_for_reflect(&mut self) -> &mut ::protobuf::RepeatedField<Bucket> {
+        &mut self.bucket
+    }
+
+    // optional .google.protobuf.Timestamp created_timestamp = 15;
+
+    pub fn clear_created_timestamp(&mut self) {
+        self.created_timestamp.clear();
+    }
+
+    pub fn has_created_timestamp(&self) -> bool {
+        self.created_timestamp.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_created_timestamp(&mut self, v: ::protobuf::well_known_types::Timestamp) {
+        self.created_timestamp = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_created_timestamp(&mut self) -> &mut ::protobuf::well_known_types::Timestamp {
+        if self.created_timestamp.is_none() {
+            self.created_timestamp.set_default();
+        }
+        self.created_timestamp.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_created_timestamp(&mut self) -> ::protobuf::well_known_types::Timestamp {
+        self.created_timestamp.take().unwrap_or_else(|| ::protobuf::well_known_types::Timestamp::new())
+    }
+
+    pub fn get_created_timestamp(&self) -> &::protobuf::well_known_types::Timestamp {
+        self.created_timestamp.as_ref().unwrap_or_else(|| ::protobuf::well_known_types::Timestamp::default_instance())
+    }
+
+    fn get_created_timestamp_for_reflect(&self) -> &::protobuf::SingularPtrField<::protobuf::well_known_types::Timestamp> {
+        &self.created_timestamp
+    }
+
+    fn mut_created_timestamp_for_reflect(&mut self) -> &mut ::protobuf::SingularPtrField<::protobuf::well_known_types::Timestamp> {
+        &mut self.created_timestamp
+    }
 }
 
 impl ::protobuf::Message for Histogram {
     fn is_initialized(&self) -> bool {
+        for v in &self.bucket {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.created_timestamp {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
         true
     }
 
     fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
-        while !try!(is.eof()) {
-            let (field_number, wire_type) = try!(is.read_tag_unpack());
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    };
-                    let tmp = try!(is.read_uint64());
+                    }
+                    let tmp = is.read_uint64()?;
                     self.sample_count = ::std::option::Option::Some(tmp);
                 },
                 2 => {
                     if wire_type != ::protobuf::wire_format::WireTypeFixed64 {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    };
-                    let tmp = try!(is.read_double());
+                    }
+                    let tmp = is.read_double()?;
                     self.sample_sum = ::std::option::Option::Some(tmp);
                 },
                 3 => {
-                    try!(::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.bucket));
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.bucket)?;
+                },
+                15 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.created_timestamp)?;
                 },
                 _ => {
-                    try!(::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields()));
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -1373,16 +1690,20 @@ impl ::protobuf::Message for Histogram {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        for value in &self.sample_count {
-            my_size += ::protobuf::rt::value_size(1, *value, ::protobuf::wire_format::WireTypeVarint);
-        };
-        if self.sample_sum.is_some() {
+        if let Some(v) = self.sample_count {
+            my_size += ::protobuf::rt::value_size(1, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(v) = self.sample_sum {
             my_size += 9;
-        };
+        }
         for value in &self.bucket {
             let len = value.compute_size();
             my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         };
+        if let Some(ref v) = self.created_timestamp.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -1390,17 +1711,22 @@ impl ::protobuf::Message for Histogram {
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
         if let Some(v) = self.sample_count {
-            try!(os.write_uint64(1, v));
-        };
+            os.write_uint64(1, v)?;
+        }
         if let Some(v) = self.sample_sum {
-            try!(os.write_double(2, v));
-        };
+            os.write_double(2, v)?;
+        }
         for v in &self.bucket {
-            try!(os.write_tag(3, ::protobuf::wire_format::WireTypeLengthDelimited));
-            try!(os.write_raw_varint32(v.get_cached_size()));
-            try!(v.write_to_with_cached_sizes(os));
-        };
-        try!(os.write_unknown_fields(self.get_unknown_fields()));
+            os.write_tag(3, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        if let Some(ref v) = self.created_timestamp.as_ref() {
+            os.write_tag(15, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
 
@@ -1416,13 +1742,15 @@ impl ::protobuf::Message for Histogram {
         &mut self.unknown_fields
     }
 
-    fn type_id(&self) -> ::std::any::TypeId {
-        ::std::any::TypeId::of::<Histogram>()
-    }
-
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
 
     fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
         ::protobuf::MessageStatic::descriptor_static(None::<Self>)
@@ -1442,19 +1770,25 @@ impl ::protobuf::MessageStatic for Histogram {
         unsafe {
             descriptor.get(|| {
                 let mut fields = ::std::vec::Vec::new();
-                fields.push(::protobuf::reflect::accessor::make_singular_u64_accessor(
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
                     "sample_count",
-                    Histogram::has_sample_count,
-                    Histogram::get_sample_count,
+                    Histogram::get_sample_count_for_reflect,
+                    Histogram::mut_sample_count_for_reflect,
                 ));
-                fields.push(::protobuf::reflect::accessor::make_singular_f64_accessor(
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeDouble>(
                     "sample_sum",
-                    Histogram::has_sample_sum,
-                    Histogram::get_sample_sum,
+                    Histogram::get_sample_sum_for_reflect,
+                    Histogram::mut_sample_sum_for_reflect,
                 ));
-                fields.push(::protobuf::reflect::accessor::make_repeated_message_accessor(
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Bucket>>(
                     "bucket",
-                    Histogram::get_bucket,
+                    Histogram::get_bucket_for_reflect,
+                    Histogram::mut_bucket_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<::protobuf::well_known_types::Timestamp>>(
+                    "created_timestamp",
+                    Histogram::get_created_timestamp_for_reflect,
+                    Histogram::mut_created_timestamp_for_reflect,
                 ));
                 ::protobuf::reflect::MessageDescriptor::new::<Histogram>(
                     "Histogram",
@@ -1471,33 +1805,32 @@ impl ::protobuf::Clear for Histogram {
         self.clear_sample_count();
         self.clear_sample_sum();
         self.clear_bucket();
+        self.clear_created_timestamp();
         self.unknown_fields.clear();
     }
 }
 
-impl ::std::cmp::PartialEq for Histogram {
-    fn eq(&self, other: &Histogram) -> bool {
-        self.sample_count == other.sample_count &&
-        self.sample_sum == other.sample_sum &&
-        self.bucket == other.bucket &&
-        self.unknown_fields == other.unknown_fields
-    }
-}
-
 impl ::std::fmt::Debug for Histogram {
     fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-#[derive(Clone,Default)]
+impl ::protobuf::reflect::ProtobufValue for Histogram {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
 pub struct Bucket {
     // message fields
     cumulative_count: ::std::option::Option<u64>,
     upper_bound: ::std::option::Option<f64>,
+    exemplar: ::protobuf::SingularPtrField<Exemplar>,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
-    cached_size: ::std::cell::Cell<u32>,
+    cached_size: ::protobuf::CachedSize,
 }
 
 // see codegen.rs for the explanation why impl Sync explicitly
@@ -1514,14 +1847,7 @@ impl Bucket {
             ptr: 0 as *const Bucket,
         };
         unsafe {
-            instance.get(|| {
-                Bucket {
-                    cumulative_count: ::std::option::Option::None,
-                    upper_bound: ::std::option::Option::None,
-                    unknown_fields: ::protobuf::UnknownFields::new(),
-                    cached_size: ::std::cell::Cell::new(0),
-                }
-            })
+            instance.get(Bucket::new)
         }
     }
 
@@ -1544,6 +1870,14 @@ impl Bucket {
         self.cumulative_count.unwrap_or(0)
     }
 
+    fn get_cumulative_count_for_reflect(&self) -> &::std::option::Option<u64> {
+        &self.cumulative_count
+    }
+
+    fn mut_cumulative_count_for_reflect(&mut self) -> &mut ::std::option::Option<u64> {
+        &mut self.cumulative_count
+    }
+
     // optional double upper_bound = 2;
 
     pub fn clear_upper_bound(&mut self) {
@@ -1562,33 +1896,90 @@ impl Bucket {
     pub fn get_upper_bound(&self) -> f64 {
         self.upper_bound.unwrap_or(0.)
     }
+
+    fn get_upper_bound_for_reflect(&self) -> &::std::option::Option<f64> {
+        &self.upper_bound
+    }
+
+    fn mut_upper_bound_for_reflect(&mut self) -> &mut ::std::option::Option<f64> {
+        &mut self.upper_bound
+    }
+
+    // optional .io.prometheus.client.Exemplar exemplar = 3;
+
+    pub fn clear_exemplar(&mut self) {
+        self.exemplar.clear();
+    }
+
+    pub fn has_exemplar(&self) -> bool {
+        self.exemplar.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_exemplar(&mut self, v: Exemplar) {
+        self.exemplar = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_exemplar(&mut self) -> &mut Exemplar {
+        if self.exemplar.is_none() {
+            self.exemplar.set_default();
+        }
+        self.exemplar.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_exemplar(&mut self) -> Exemplar {
+        self.exemplar.take().unwrap_or_else(|| Exemplar::new())
+    }
+
+    pub fn get_exemplar(&self) -> &Exemplar {
+        self.exemplar.as_ref().unwrap_or_else(|| Exemplar::default_instance())
+    }
+
+    fn get_exemplar_for_reflect(&self) -> &::protobuf::SingularPtrField<Exemplar> {
+        &self.exemplar
+    }
+
+    fn mut_exemplar_for_reflect(&mut self) -> &mut ::protobuf::SingularPtrField<Exemplar> {
+        &mut self.exemplar
+    }
 }
 
 impl ::protobuf::Message for Bucket {
     fn is_initialized(&self) -> bool {
+        for v in &self.exemplar {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
         true
     }
 
     fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
-        while !try!(is.eof()) {
-            let (field_number, wire_type) = try!(is.read_tag_unpack());
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    };
-                    let tmp = try!(is.read_uint64());
+                    }
+                    let tmp = is.read_uint64()?;
                     self.cumulative_count = ::std::option::Option::Some(tmp);
                 },
                 2 => {
                     if wire_type != ::protobuf::wire_format::WireTypeFixed64 {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    };
-                    let tmp = try!(is.read_double());
+                    }
+                    let tmp = is.read_double()?;
                     self.upper_bound = ::std::option::Option::Some(tmp);
                 },
+                3 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.exemplar)?;
+                },
                 _ => {
-                    try!(::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields()));
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -1599,12 +1990,16 @@ impl ::protobuf::Message for Bucket {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        for value in &self.cumulative_count {
-            my_size += ::protobuf::rt::value_size(1, *value, ::protobuf::wire_format::WireTypeVarint);
-        };
-        if self.upper_bound.is_some() {
+        if let Some(v) = self.cumulative_count {
+            my_size += ::protobuf::rt::value_size(1, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(v) = self.upper_bound {
             my_size += 9;
-        };
+        }
+        if let Some(ref v) = self.exemplar.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -1612,12 +2007,17 @@ impl ::protobuf::Message for Bucket {
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
         if let Some(v) = self.cumulative_count {
-            try!(os.write_uint64(1, v));
-        };
+            os.write_uint64(1, v)?;
+        }
         if let Some(v) = self.upper_bound {
-            try!(os.write_double(2, v));
-        };
-        try!(os.write_unknown_fields(self.get_unknown_fields()));
+            os.write_double(2, v)?;
+        }
+        if let Some(ref v) = self.exemplar.as_ref() {
+            os.write_tag(3, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
 
@@ -1633,13 +2033,15 @@ impl ::protobuf::Message for Bucket {
         &mut self.unknown_fields
     }
 
-    fn type_id(&self) -> ::std::any::TypeId {
-        ::std::any::TypeId::of::<Bucket>()
-    }
-
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
 
     fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
         ::protobuf::MessageStatic::descriptor_static(None::<Self>)
@@ -1659,15 +2061,20 @@ impl ::protobuf::MessageStatic for Bucket {
         unsafe {
             descriptor.get(|| {
                 let mut fields = ::std::vec::Vec::new();
-                fields.push(::protobuf::reflect::accessor::make_singular_u64_accessor(
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
                     "cumulative_count",
-                    Bucket::has_cumulative_count,
-                    Bucket::get_cumulative_count,
+                    Bucket::get_cumulative_count_for_reflect,
+                    Bucket::mut_cumulative_count_for_reflect,
                 ));
-                fields.push(::protobuf::reflect::accessor::make_singular_f64_accessor(
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeDouble>(
                     "upper_bound",
-                    Bucket::has_upper_bound,
-                    Bucket::get_upper_bound,
+                    Bucket::get_upper_bound_for_reflect,
+                    Bucket::mut_upper_bound_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Exemplar>>(
+                    "exemplar",
+                    Bucket::get_exemplar_for_reflect,
+                    Bucket::mut_exemplar_for_reflect,
                 ));
                 ::protobuf::reflect::MessageDescriptor::new::<Bucket>(
                     "Bucket",
@@ -1683,25 +2090,319 @@ impl ::protobuf::Clear for Bucket {
     fn clear(&mut self) {
         self.clear_cumulative_count();
         self.clear_upper_bound();
+        self.clear_exemplar();
         self.unknown_fields.clear();
     }
 }
 
-impl ::std::cmp::PartialEq for Bucket {
-    fn eq(&self, other: &Bucket) -> bool {
-        self.cumulative_count == other.cumulative_count &&
-        self.upper_bound == other.upper_bound &&
-        self.unknown_fields == other.unknown_fields
+impl ::std::fmt::Debug for Bucket {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::std::fmt::Debug for Bucket {
+impl ::protobuf::reflect::ProtobufValue for Bucket {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct Exemplar {
+    // message fields
+    label: ::protobuf::RepeatedField<LabelPair>,
+    value: ::std::option::Option<f64>,
+    timestamp: ::protobuf::SingularPtrField<::protobuf::well_known_types::Timestamp>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for Exemplar {}
+
+impl Exemplar {
+    pub fn new() -> Exemplar {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static Exemplar {
+        static mut instance: ::protobuf::lazy::Lazy<Exemplar> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const Exemplar,
+        };
+        unsafe {
+            instance.get(Exemplar::new)
+        }
+    }
+
+    // repeated .io.prometheus.client.LabelPair label = 1;
+
+    pub fn clear_label(&mut self) {
+        self.label.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_label(&mut self, v: ::protobuf::RepeatedField<LabelPair>) {
+        self.label = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_label(&mut self) -> &mut ::protobuf::RepeatedField<LabelPair> {
+        &mut self.label
+    }
+
+    // Take field
+    pub fn take_label(&mut self) -> ::protobuf::RepeatedField<LabelPair> {
+        ::std::mem::replace(&mut self.label, ::protobuf::RepeatedField::new())
+    }
+
+    pub fn get_label(&self) -> &[LabelPair] {
+        &self.label
+    }
+
+    fn get_label_for_reflect(&self) -> &::protobuf::RepeatedField<LabelPair> {
+        &self.label
+    }
+
+    fn mut_label_for_reflect(&mut self) -> &mut ::protobuf::RepeatedField<LabelPair> {
+        &mut self.label
+    }
+
+    // optional double value = 2;
+
+    pub fn clear_value(&mut self) {
+        self.value = ::std::option::Option::None;
+    }
+
+    pub fn has_value(&self) -> bool {
+        self.value.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_value(&mut self, v: f64) {
+        self.value = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_value(&self) -> f64 {
+        self.value.unwrap_or(0.)
+    }
+
+    fn get_value_for_reflect(&self) -> &::std::option::Option<f64> {
+        &self.value
+    }
+
+    fn mut_value_for_reflect(&mut self) -> &mut ::std::option::Option<f64> {
+        &mut self.value
+    }
+
+    // optional .google.protobuf.Timestamp timestamp = 3;
+
+    pub fn clear_timestamp(&mut self) {
+        self.timestamp.clear();
+    }
+
+    pub fn has_timestamp(&self) -> bool {
+        self.timestamp.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_timestamp(&mut self, v: ::protobuf::well_known_types::Timestamp) {
+        self.timestamp = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_timestamp(&mut self) -> &mut ::protobuf::well_known_types::Timestamp {
+        if self.timestamp.is_none() {
+            self.timestamp.set_default();
+        }
+        self.timestamp.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_timestamp(&mut self) -> ::protobuf::well_known_types::Timestamp {
+        self.timestamp.take().unwrap_or_else(|| ::protobuf::well_known_types::Timestamp::new())
+    }
+
+    pub fn get_timestamp(&self) -> &::protobuf::well_known_types::Timestamp {
+        self.timestamp.as_ref().unwrap_or_else(|| ::protobuf::well_known_types::Timestamp::default_instance())
+    }
+
+    fn get_timestamp_for_reflect(&self) -> &::protobuf::SingularPtrField<::protobuf::well_known_types::Timestamp> {
+        &self.timestamp
+    }
+
+    fn mut_timestamp_for_reflect(&mut self) -> &mut ::protobuf::SingularPtrField<::protobuf::well_known_types::Timestamp> {
+        &mut self.timestamp
+    }
+}
+
+impl ::protobuf::Message for Exemplar {
+    fn is_initialized(&self) -> bool {
+        for v in &self.label {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.timestamp {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.label)?;
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeFixed64 {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_double()?;
+                    self.value = ::std::option::Option::Some(tmp);
+                },
+                3 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.timestamp)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        for value in &self.label {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        if let Some(v) = self.value {
+            my_size += 9;
+        }
+        if let Some(ref v) = self.timestamp.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        for v in &self.label {
+            os.write_tag(1, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        if let Some(v) = self.value {
+            os.write_double(2, v)?;
+        }
+        if let Some(ref v) = self.timestamp.as_ref() {
+            os.write_tag(3, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for Exemplar {
+    fn new() -> Exemplar {
+        Exemplar::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<Exemplar>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<LabelPair>>(
+                    "label",
+                    Exemplar::get_label_for_reflect,
+                    Exemplar::mut_label_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeDouble>(
+                    "value",
+                    Exemplar::get_value_for_reflect,
+                    Exemplar::mut_value_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<::protobuf::well_known_types::Timestamp>>(
+                    "timestamp",
+                    Exemplar::get_timestamp_for_reflect,
+                    Exemplar::mut_timestamp_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<Exemplar>(
+                    "Exemplar",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for Exemplar {
+    fn clear(&mut self) {
+        self.clear_label();
+        self.clear_value();
+        self.clear_timestamp();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Exemplar {
     fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-#[derive(Clone,Default)]
+impl ::protobuf::reflect::ProtobufValue for Exemplar {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
 pub struct Metric {
     // message fields
     label: ::protobuf::RepeatedField<LabelPair>,
@@ -1713,7 +2414,7 @@ pub struct Metric {
     timestamp_ms: ::std::option::Option<i64>,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
-    cached_size: ::std::cell::Cell<u32>,
+    cached_size: ::protobuf::CachedSize,
 }
 
 // see codegen.rs for the explanation why impl Sync explicitly
@@ -1730,19 +2431,7 @@ impl Metric {
             ptr: 0 as *const Metric,
         };
         unsafe {
-            instance.get(|| {
-                Metric {
-                    label: ::protobuf::RepeatedField::new(),
-                    gauge: ::protobuf::SingularPtrField::none(),
-                    counter: ::protobuf::SingularPtrField::none(),
-                    summary: ::protobuf::SingularPtrField::none(),
-                    untyped: ::protobuf::SingularPtrField::none(),
-                    histogram: ::protobuf::SingularPtrField::none(),
-                    timestamp_ms: ::std::option::Option::None,
-                    unknown_fields: ::protobuf::UnknownFields::new(),
-                    cached_size: ::std::cell::Cell::new(0),
-                }
-            })
+            instance.get(Metric::new)
         }
     }
 
@@ -1771,6 +2460,14 @@ impl Metric {
         &self.label
     }
 
+    fn get_label_for_reflect(&self) -> &::protobuf::RepeatedField<LabelPair> {
+        &self.label
+    }
+
+    fn mut_label_for_reflect(&mut self) -> &mut ::protobuf::RepeatedField<LabelPair> {
+        &mut self.label
+    }
+
     // optional .io.prometheus.client.Gauge gauge = 2;
 
     pub fn clear_gauge(&mut self) {
@@ -1791,7 +2488,7 @@ impl Metric {
     pub fn mut_gauge(&mut self) -> &mut Gauge {
         if self.gauge.is_none() {
             self.gauge.set_default();
-        };
+        }
         self.gauge.as_mut().unwrap()
     }
 
@@ -1804,6 +2501,14 @@ impl Metric {
         self.gauge.as_ref().unwrap_or_else(|| Gauge::default_instance())
     }
 
+    fn get_gauge_for_reflect(&self) -> &::protobuf::SingularPtrField<Gauge> {
+        &self.gauge
+    }
+
+    fn mut_gauge_for_reflect(&mut self) -> &mut ::protobuf::SingularPtrField<Gauge> {
+        &mut self.gauge
+    }
+
     // optional .io.prometheus.client.Counter counter = 3;
 
     pub fn clear_counter(&mut self) {
@@ -1824,7 +2529,7 @@ impl Metric {
     pub fn mut_counter(&mut self) -> &mut Counter {
         if self.counter.is_none() {
             self.counter.set_default();
-        };
+        }
         self.counter.as_mut().unwrap()
     }
 
@@ -1837,6 +2542,14 @@ impl Metric {
         self.counter.as_ref().unwrap_or_else(|| Counter::default_instance())
     }
 
+    fn get_counter_for_reflect(&self) -> &::protobuf::SingularPtrField<Counter> {
+        &self.counter
+    }
+
+    fn mut_counter_for_reflect(&mut self) -> &mut ::protobuf::SingularPtrField<Counter> {
+        &mut self.counter
+    }
+
     // optional .io.prometheus.client.Summary summary = 4;
 
     pub fn clear_summary(&mut self) {
@@ -1857,7 +2570,7 @@ impl Metric {
     pub fn mut_summary(&mut self) -> &mut Summary {
         if self.summary.is_none() {
             self.summary.set_default();
-        };
+        }
         self.summary.as_mut().unwrap()
     }
 
@@ -1870,6 +2583,14 @@ impl Metric {
         self.summary.as_ref().unwrap_or_else(|| Summary::default_instance())
     }
 
+    fn get_summary_for_reflect(&self) -> &::protobuf::SingularPtrField<Summary> {
+        &self.summary
+    }
+
+    fn mut_summary_for_reflect(&mut self) -> &mut ::protobuf::SingularPtrField<Summary> {
+        &mut self.summary
+    }
+
     // optional .io.prometheus.client.Untyped untyped = 5;
 
     pub fn clear_untyped(&mut self) {
@@ -1890,7 +2611,7 @@ impl Metric {
     pub fn mut_untyped(&mut self) -> &mut Untyped {
         if self.untyped.is_none() {
             self.untyped.set_default();
-        };
+        }
         self.untyped.as_mut().unwrap()
     }
 
@@ -1903,6 +2624,14 @@ impl Metric {
         self.untyped.as_ref().unwrap_or_else(|| Untyped::default_instance())
     }
 
+    fn get_untyped_for_reflect(&self) -> &::protobuf::SingularPtrField<Untyped> {
+        &self.untyped
+    }
+
+    fn mut_untyped_for_reflect(&mut self) -> &mut ::protobuf::SingularPtrField<Untyped> {
+        &mut self.untyped
+    }
+
     // optional .io.prometheus.client.Histogram histogram = 7;
 
     pub fn clear_histogram(&mut self) {
@@ -1923,7 +2652,7 @@ impl Metric {
     pub fn mut_histogram(&mut self) -> &mut Histogram {
         if self.histogram.is_none() {
             self.histogram.set_default();
-        };
+        }
         self.histogram.as_mut().unwrap()
     }
 
@@ -1936,6 +2665,14 @@ impl Metric {
         self.histogram.as_ref().unwrap_or_else(|| Histogram::default_instance())
     }
 
+    fn get_histogram_for_reflect(&self) -> &::protobuf::SingularPtrField<Histogram> {
+        &self.histogram
+    }
+
+    fn mut_histogram_for_reflect(&mut self) -> &mut ::protobuf::SingularPtrField<Histogram> {
+        &mut self.histogram
+    }
+
     // optional int64 timestamp_ms = 6;
 
     pub fn clear_timestamp_ms(&mut self) {
@@ -1954,44 +2691,82 @@ impl Metric {
     pub fn get_timestamp_ms(&self) -> i64 {
         self.timestamp_ms.unwrap_or(0)
     }
+
+    fn get_timestamp_ms_for_reflect(&self) -> &::std::option::Option<i64> {
+        &self.timestamp_ms
+    }
+
+    fn mut_timestamp_ms_for_reflect(&mut self) -> &mut ::std::option::Option<i64> {
+        &mut self.timestamp_ms
+    }
 }
 
 impl ::protobuf::Message for Metric {
     fn is_initialized(&self) -> bool {
+        for v in &self.label {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.gauge {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.counter {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.summary {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.untyped {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.histogram {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
         true
     }
 
     fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
-        while !try!(is.eof()) {
-            let (field_number, wire_type) = try!(is.read_tag_unpack());
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
-                    try!(::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.label));
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.label)?;
                 },
                 2 => {
-                    try!(::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.gauge));
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.gauge)?;
                 },
                 3 => {
-                    try!(::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.counter));
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.counter)?;
                 },
                 4 => {
-                    try!(::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.summary));
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.summary)?;
                 },
                 5 => {
-                    try!(::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.untyped));
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.untyped)?;
                 },
                 7 => {
-                    try!(::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.histogram));
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.histogram)?;
                 },
                 6 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    };
-                    let tmp = try!(is.read_int64());
+                    }
+                    let tmp = is.read_int64()?;
                     self.timestamp_ms = ::std::option::Option::Some(tmp);
                 },
                 _ => {
-                    try!(::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields()));
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -2006,29 +2781,29 @@ impl ::protobuf::Message for Metric {
             let len = value.compute_size();
             my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         };
-        for value in &self.gauge {
-            let len = value.compute_size();
+        if let Some(ref v) = self.gauge.as_ref() {
+            let len = v.compute_size();
             my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
-        };
-        for value in &self.counter {
-            let len = value.compute_size();
+        }
+        if let Some(ref v) = self.counter.as_ref() {
+            let len = v.compute_size();
             my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
-        };
-        for value in &self.summary {
-            let len = value.compute_size();
+        }
+        if let Some(ref v) = self.summary.as_ref() {
+            let len = v.compute_size();
             my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
-        };
-        for value in &self.untyped {
-            let len = value.compute_size();
+        }
+        if let Some(ref v) = self.untyped.as_ref() {
+            let len = v.compute_size();
             my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
-        };
-        for value in &self.histogram {
-            let len = value.compute_size();
+        }
+        if let Some(ref v) = self.histogram.as_ref() {
+            let len = v.compute_size();
             my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
-        };
-        for value in &self.timestamp_ms {
-            my_size += ::protobuf::rt::value_size(6, *value, ::protobuf::wire_format::WireTypeVarint);
-        };
+        }
+        if let Some(v) = self.timestamp_ms {
+            my_size += ::protobuf::rt::value_size(6, v, ::protobuf::wire_format::WireTypeVarint);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -2036,39 +2811,39 @@ impl ::protobuf::Message for Metric {
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
         for v in &self.label {
-            try!(os.write_tag(1, ::protobuf::wire_format::WireTypeLengthDelimited));
-            try!(os.write_raw_varint32(v.get_cached_size()));
-            try!(v.write_to_with_cached_sizes(os));
-        };
-        if let Some(v) = self.gauge.as_ref() {
-            try!(os.write_tag(2, ::protobuf::wire_format::WireTypeLengthDelimited));
-            try!(os.write_raw_varint32(v.get_cached_size()));
-            try!(v.write_to_with_cached_sizes(os));
-        };
-        if let Some(v) = self.counter.as_ref() {
-            try!(os.write_tag(3, ::protobuf::wire_format::WireTypeLengthDelimited));
-            try!(os.write_raw_varint32(v.get_cached_size()));
-            try!(v.write_to_with_cached_sizes(os));
-        };
-        if let Some(v) = self.summary.as_ref() {
-            try!(os.write_tag(4, ::protobuf::wire_format::WireTypeLengthDelimited));
-            try!(os.write_raw_varint32(v.get_cached_size()));
-            try!(v.write_to_with_cached_sizes(os));
-        };
-        if let Some(v) = self.untyped.as_ref() {
-            try!(os.write_tag(5, ::protobuf::wire_format::WireTypeLengthDelimited));
-            try!(os.write_raw_varint32(v.get_cached_size()));
-            try!(v.write_to_with_cached_sizes(os));
-        };
-        if let Some(v) = self.histogram.as_ref() {
-            try!(os.write_tag(7, ::protobuf::wire_format::WireTypeLengthDelimited));
-            try!(os.write_raw_varint32(v.get_cached_size()));
-            try!(v.write_to_with_cached_sizes(os));
-        };
+            os.write_tag(1, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        if let Some(ref v) = self.gauge.as_ref() {
+            os.write_tag(2, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        if let Some(ref v) = self.counter.as_ref() {
+            os.write_tag(3, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        if let Some(ref v) = self.summary.as_ref() {
+            os.write_tag(4, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        if let Some(ref v) = self.untyped.as_ref() {
+            os.write_tag(5, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        if let Some(ref v) = self.histogram.as_ref() {
+            os.write_tag(7, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
         if let Some(v) = self.timestamp_ms {
-            try!(os.write_int64(6, v));
-        };
-        try!(os.write_unknown_fields(self.get_unknown_fields()));
+            os.write_int64(6, v)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
 
@@ -2084,13 +2859,15 @@ impl ::protobuf::Message for Metric {
         &mut self.unknown_fields
     }
 
-    fn type_id(&self) -> ::std::any::TypeId {
-        ::std::any::TypeId::of::<Metric>()
-    }
-
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
 
     fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
         ::protobuf::MessageStatic::descriptor_static(None::<Self>)
@@ -2110,39 +2887,40 @@ impl ::protobuf::MessageStatic for Metric {
         unsafe {
             descriptor.get(|| {
                 let mut fields = ::std::vec::Vec::new();
-                fields.push(::protobuf::reflect::accessor::make_repeated_message_accessor(
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<LabelPair>>(
                     "label",
-                    Metric::get_label,
+                    Metric::get_label_for_reflect,
+                    Metric::mut_label_for_reflect,
                 ));
-                fields.push(::protobuf::reflect::accessor::make_singular_message_accessor(
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Gauge>>(
                     "gauge",
-                    Metric::has_gauge,
-                    Metric::get_gauge,
+                    Metric::get_gauge_for_reflect,
+                    Metric::mut_gauge_for_reflect,
                 ));
-                fields.push(::protobuf::reflect::accessor::make_singular_message_accessor(
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Counter>>(
                     "counter",
-                    Metric::has_counter,
-                    Metric::get_counter,
+                    Metric::get_counter_for_reflect,
+                    Metric::mut_counter_for_reflect,
                 ));
-                fields.push(::protobuf::reflect::accessor::make_singular_message_accessor(
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Summary>>(
                     "summary",
-                    Metric::has_summary,
-                    Metric::get_summary,
+                    Metric::get_summary_for_reflect,
+                    Metric::mut_summary_for_reflect,
                 ));
-                fields.push(::protobuf::reflect::accessor::make_singular_message_accessor(
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Untyped>>(
                     "untyped",
-                    Metric::has_untyped,
-                    Metric::get_untyped,
+                    Metric::get_untyped_for_reflect,
+                    Metric::mut_untyped_for_reflect,
                 ));
-                fields.push(::protobuf::reflect::accessor::make_singular_message_accessor(
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Histogram>>(
                     "histogram",
-                    Metric::has_histogram,
-                    Metric::get_histogram,
+                    Metric::get_histogram_for_reflect,
+                    Metric::mut_histogram_for_reflect,
                 ));
-                fields.push(::protobuf::reflect::accessor::make_singular_i64_accessor(
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeInt64>(
                     "timestamp_ms",
-                    Metric::has_timestamp_ms,
-                    Metric::get_timestamp_ms,
+                    Metric::get_timestamp_ms_for_reflect,
+                    Metric::mut_timestamp_ms_for_reflect,
                 ));
                 ::protobuf::reflect::MessageDescriptor::new::<Metric>(
                     "Metric",
@@ -2167,35 +2945,29 @@ impl ::protobuf::Clear for Metric {
     }
 }
 
-impl ::std::cmp::PartialEq for Metric {
-    fn eq(&self, other: &Metric) -> bool {
-        self.label == other.label &&
-        self.gauge == other.gauge &&
-        self.counter == other.counter &&
-        self.summary == other.summary &&
-        self.untyped == other.untyped &&
-        self.histogram == other.histogram &&
-        self.timestamp_ms == other.timestamp_ms &&
-        self.unknown_fields == other.unknown_fields
-    }
-}
-
 impl ::std::fmt::Debug for Metric {
     fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-#[derive(Clone,Default)]
+impl ::protobuf::reflect::ProtobufValue for Metric {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
 pub struct MetricFamily {
     // message fields
     name: ::protobuf::SingularField<::std::string::String>,
     help: ::protobuf::SingularField<::std::string::String>,
     field_type: ::std::option::Option<MetricType>,
     metric: ::protobuf::RepeatedField<Metric>,
+    unit: ::protobuf::SingularField<::std::string::String>,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
-    cached_size: ::std::cell::Cell<u32>,
+    cached_size: ::protobuf::CachedSize,
 }
 
 // see codegen.rs for the explanation why impl Sync explicitly
@@ -2212,16 +2984,7 @@ impl MetricFamily {
             ptr: 0 as *const MetricFamily,
         };
         unsafe {
-            instance.get(|| {
-                MetricFamily {
-                    name: ::protobuf::SingularField::none(),
-                    help: ::protobuf::SingularField::none(),
-                    field_type: ::std::option::Option::None,
-                    metric: ::protobuf::RepeatedField::new(),
-                    unknown_fields: ::protobuf::UnknownFields::new(),
-                    cached_size: ::std::cell::Cell::new(0),
-                }
-            })
+            instance.get(MetricFamily::new)
         }
     }
 
@@ -2245,7 +3008,7 @@ impl MetricFamily {
     pub fn mut_name(&mut self) -> &mut ::std::string::String {
         if self.name.is_none() {
             self.name.set_default();
-        };
+        }
         self.name.as_mut().unwrap()
     }
 
@@ -2261,6 +3024,14 @@ impl MetricFamily {
         }
     }
 
+    fn get_name_for_reflect(&self) -> &::protobuf::SingularField<::std::string::String> {
+        &self.name
+    }
+
+    fn mut_name_for_reflect(&mut self) -> &mut ::protobuf::SingularField<::std::string::String> {
+        &mut self.name
+    }
+
     // optional string help = 2;
 
     pub fn clear_help(&mut self) {
@@ -2281,7 +3052,7 @@ impl MetricFamily {
     pub fn mut_help(&mut self) -> &mut ::std::string::String {
         if self.help.is_none() {
             self.help.set_default();
-        };
+        }
         self.help.as_mut().unwrap()
     }
 
@@ -2297,6 +3068,14 @@ impl MetricFamily {
         }
     }
 
+    fn get_help_for_reflect(&self) -> &::protobuf::SingularField<::std::string::String> {
+        &self.help
+    }
+
+    fn mut_help_for_reflect(&mut self) -> &mut ::protobuf::SingularField<::std::string::String> {
+        &mut self.help
+    }
+
     // optional .io.prometheus.client.MetricType type = 3;
 
     pub fn clear_field_type(&mut self) {
@@ -2316,6 +3095,14 @@ impl MetricFamily {
         self.field_type.unwrap_or(MetricType::COUNTER)
     }
 
+    fn get_field_type_for_reflect(&self) -> &::std::option::Option<MetricType> {
+        &self.field_type
+    }
+
+    fn mut_field_type_for_reflect(&mut self) -> &mut ::std::option::Option<MetricType> {
+        &mut self.field_type
+    }
+
     // repeated .io.prometheus.client.Metric metric = 4;
 
     pub fn clear_metric(&mut self) {
@@ -2340,35 +3127,91 @@ impl MetricFamily {
     pub fn get_metric(&self) -> &[Metric] {
         &self.metric
     }
+
+    fn get_metric_for_reflect(&self) -> &::protobuf::RepeatedField<Metric> {
+        &self.metric
+    }
+
+    fn mut_metric_for_reflect(&mut self) -> &mut ::protobuf::RepeatedField<Metric> {
+        &mut self.metric
+    }
+
+    // optional string unit = 5;
+
+    pub fn clear_unit(&mut self) {
+        self.unit.clear();
+    }
+
+    pub fn has_unit(&self) -> bool {
+        self.unit.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_unit(&mut self, v: ::std::string::String) {
+        self.unit = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_unit(&mut self) -> &mut ::std::string::String {
+        if self.unit.is_none() {
+            self.unit.set_default();
+        }
+        self.unit.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_unit(&mut self) -> ::std::string::String {
+        self.unit.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    pub fn get_unit(&self) -> &str {
+        match self.unit.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+
+    fn get_unit_for_reflect(&self) -> &::protobuf::SingularField<::std::string::String> {
+        &self.unit
+    }
+
+    fn mut_unit_for_reflect(&mut self) -> &mut ::protobuf::SingularField<::std::string::String> {
+        &mut self.unit
+    }
 }
 
 impl ::protobuf::Message for MetricFamily {
     fn is_initialized(&self) -> bool {
+        for v in &self.metric {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
         true
     }
 
     fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
-        while !try!(is.eof()) {
-            let (field_number, wire_type) = try!(is.read_tag_unpack());
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
-                    try!(::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.name));
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.name)?;
                 },
                 2 => {
-                    try!(::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.help));
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.help)?;
                 },
                 3 => {
-                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
-                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    };
-                    let tmp = try!(is.read_enum());
-                    self.field_type = ::std::option::Option::Some(tmp);
+                    ::protobuf::rt::read_proto2_enum_with_unknown_fields_into(wire_type, is, &mut self.field_type, 3, &mut self.unknown_fields)?
                 },
                 4 => {
-                    try!(::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.metric));
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.metric)?;
+                },
+                5 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.unit)?;
                 },
                 _ => {
-                    try!(::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields()));
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
             };
         }
@@ -2379,40 +3222,46 @@ impl ::protobuf::Message for MetricFamily {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        for value in &self.name {
-            my_size += ::protobuf::rt::string_size(1, &value);
-        };
-        for value in &self.help {
-            my_size += ::protobuf::rt::string_size(2, &value);
-        };
-        for value in &self.field_type {
-            my_size += ::protobuf::rt::enum_size(3, *value);
-        };
+        if let Some(ref v) = self.name.as_ref() {
+            my_size += ::protobuf::rt::string_size(1, &v);
+        }
+        if let Some(ref v) = self.help.as_ref() {
+            my_size += ::protobuf::rt::string_size(2, &v);
+        }
+        if let Some(v) = self.field_type {
+            my_size += ::protobuf::rt::enum_size(3, v);
+        }
         for value in &self.metric {
             let len = value.compute_size();
             my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         };
+        if let Some(ref v) = self.unit.as_ref() {
+            my_size += ::protobuf::rt::string_size(5, &v);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
-        if let Some(v) = self.name.as_ref() {
-            try!(os.write_string(1, &v));
-        };
-        if let Some(v) = self.help.as_ref() {
-            try!(os.write_string(2, &v));
-        };
+        if let Some(ref v) = self.name.as_ref() {
+            os.write_string(1, &v)?;
+        }
+        if let Some(ref v) = self.help.as_ref() {
+            os.write_string(2, &v)?;
+        }
         if let Some(v) = self.field_type {
-            try!(os.write_enum(3, v.value()));
-        };
+            os.write_enum(3, v.value())?;
+        }
         for v in &self.metric {
-            try!(os.write_tag(4, ::protobuf::wire_format::WireTypeLengthDelimited));
-            try!(os.write_raw_varint32(v.get_cached_size()));
-            try!(v.write_to_with_cached_sizes(os));
+            os.write_tag(4, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
         };
-        try!(os.write_unknown_fields(self.get_unknown_fields()));
+        if let Some(ref v) = self.unit.as_ref() {
+            os.write_string(5, &v)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
 
@@ -2428,13 +3277,15 @@ impl ::protobuf::Message for MetricFamily {
         &mut self.unknown_fields
     }
 
-    fn type_id(&self) -> ::std::any::TypeId {
-        ::std::any::TypeId::of::<MetricFamily>()
-    }
-
     fn as_any(&self) -> &::std::any::Any {
         self as &::std::any::Any
     }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
 
     fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
         ::protobuf::MessageStatic::descriptor_static(None::<Self>)
@@ -2454,24 +3305,30 @@ impl ::protobuf::MessageStatic for MetricFamily {
         unsafe {
             descriptor.get(|| {
                 let mut fields = ::std::vec::Vec::new();
-                fields.push(::protobuf::reflect::accessor::make_singular_string_accessor(
+                fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
                     "name",
-                    MetricFamily::has_name,
-                    MetricFamily::get_name,
+                    MetricFamily::get_name_for_reflect,
+                    MetricFamily::mut_name_for_reflect,
                 ));
-                fields.push(::protobuf::reflect::accessor::make_singular_string_accessor(
+                fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
                     "help",
-                    MetricFamily::has_help,
-                    MetricFamily::get_help,
+                    MetricFamily::get_help_for_reflect,
+                    MetricFamily::mut_help_for_reflect,
                 ));
-                fields.push(::protobuf::reflect::accessor::make_singular_enum_accessor(
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeEnum<MetricType>>(
                     "type",
-                    MetricFamily::has_field_type,
-                    MetricFamily::get_field_type,
+                    MetricFamily::get_field_type_for_reflect,
+                    MetricFamily::mut_field_type_for_reflect,
                 ));
-                fields.push(::protobuf::reflect::accessor::make_repeated_message_accessor(
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Metric>>(
                     "metric",
-                    MetricFamily::get_metric,
+                    MetricFamily::get_metric_for_reflect,
+                    MetricFamily::mut_metric_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "unit",
+                    MetricFamily::get_unit_for_reflect,
+                    MetricFamily::mut_unit_for_reflect,
                 ));
                 ::protobuf::reflect::MessageDescriptor::new::<MetricFamily>(
                     "MetricFamily",
@@ -2489,26 +3346,23 @@ impl ::protobuf::Clear for MetricFamily {
         self.clear_help();
         self.clear_field_type();
         self.clear_metric();
+        self.clear_unit();
         self.unknown_fields.clear();
     }
 }
 
-impl ::std::cmp::PartialEq for MetricFamily {
-    fn eq(&self, other: &MetricFamily) -> bool {
-        self.name == other.name &&
-        self.help == other.help &&
-        self.field_type == other.field_type &&
-        self.metric == other.metric &&
-        self.unknown_fields == other.unknown_fields
-    }
-}
-
 impl ::std::fmt::Debug for MetricFamily {
     fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
+impl ::protobuf::reflect::ProtobufValue for MetricFamily {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
 #[derive(Clone,PartialEq,Eq,Debug,Hash)]
 pub enum MetricType {
     COUNTER = 0,
@@ -2545,7 +3399,7 @@ impl ::protobuf::ProtobufEnum for MetricType {
         values
     }
 
-    fn enum_descriptor_static(_: Option<MetricType>) -> &'static ::protobuf::reflect::EnumDescriptor {
+    fn enum_descriptor_static(_: ::std::option::Option<MetricType>) -> &'static ::protobuf::reflect::EnumDescriptor {
         static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::EnumDescriptor> = ::protobuf::lazy::Lazy {
             lock: ::protobuf::lazy::ONCE_INIT,
             ptr: 0 as *const ::protobuf::reflect::EnumDescriptor,
@@ -2561,228 +3415,208 @@ impl ::protobuf::ProtobufEnum for MetricType {
 impl ::std::marker::Copy for MetricType {
 }
 
-static file_descriptor_proto_data: &'static [u8] = &[
-    0x0a, 0x0d, 0x6d, 0x65, 0x74, 0x72, 0x69, 0x63, 0x73, 0x2e, 0x70, 0x72, 0x6f, 0x74, 0x6f, 0x12,
-    0x14, 0x69, 0x6f, 0x2e, 0x70, 0x72, 0x6f, 0x6d, 0x65, 0x74, 0x68, 0x65, 0x75, 0x73, 0x2e, 0x63,
-    0x6c, 0x69, 0x65, 0x6e, 0x74, 0x22, 0x28, 0x0a, 0x09, 0x4c, 0x61, 0x62, 0x65, 0x6c, 0x50, 0x61,
-    0x69, 0x72, 0x12, 0x0c, 0x0a, 0x04, 0x6e, 0x61, 0x6d, 0x65, 0x18, 0x01, 0x20, 0x01, 0x28, 0x09,
-    0x12, 0x0d, 0x0a, 0x05, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x18, 0x02, 0x20, 0x01, 0x28, 0x09, 0x22,
-    0x16, 0x0a, 0x05, 0x47, 0x61, 0x75, 0x67, 0x65, 0x12, 0x0d, 0x0a, 0x05, 0x76, 0x61, 0x6c, 0x75,
-    0x65, 0x18, 0x01, 0x20, 0x01, 0x28, 0x01, 0x22, 0x18, 0x0a, 0x07, 0x43, 0x6f, 0x75, 0x6e, 0x74,
-    0x65, 0x72, 0x12, 0x0d, 0x0a, 0x05, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x18, 0x01, 0x20, 0x01, 0x28,
-    0x01, 0x22, 0x2b, 0x0a, 0x08, 0x51, 0x75, 0x61, 0x6e, 0x74, 0x69, 0x6c, 0x65, 0x12, 0x10, 0x0a,
-    0x08, 0x71, 0x75, 0x61, 0x6e, 0x74, 0x69, 0x6c, 0x65, 0x18, 0x01, 0x20, 0x01, 0x28, 0x01, 0x12,
-    0x0d, 0x0a, 0x05, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x18, 0x02, 0x20, 0x01, 0x28, 0x01, 0x22, 0x65,
-    0x0a, 0x07, 0x53, 0x75, 0x6d, 0x6d, 0x61, 0x72, 0x79, 0x12, 0x14, 0x0a, 0x0c, 0x73, 0x61, 0x6d,
-    0x70, 0x6c, 0x65, 0x5f, 0x63, 0x6f, 0x75, 0x6e, 0x74, 0x18, 0x01, 0x20, 0x01, 0x28, 0x04, 0x12,
-    0x12, 0x0a, 0x0a, 0x73, 0x61, 0x6d, 0x70, 0x6c, 0x65, 0x5f, 0x73, 0x75, 0x6d, 0x18, 0x02, 0x20,
-    0x01, 0x28, 0x01, 0x12, 0x30, 0x0a, 0x08, 0x71, 0x75, 0x61, 0x6e, 0x74, 0x69, 0x6c, 0x65, 0x18,
-    0x03, 0x20, 0x03, 0x28, 0x0b, 0x32, 0x1e, 0x2e, 0x69, 0x6f, 0x2e, 0x70, 0x72, 0x6f, 0x6d, 0x65,
-    0x74, 0x68, 0x65, 0x75, 0x73, 0x2e, 0x63, 0x6c, 0x69, 0x65, 0x6e, 0x74, 0x2e, 0x51, 0x75, 0x61,
-    0x6e, 0x74, 0x69, 0x6c, 0x65, 0x22, 0x18, 0x0a, 0x07, 0x55, 0x6e, 0x74, 0x79, 0x70, 0x65, 0x64,
-    0x12, 0x0d, 0x0a, 0x05, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x18, 0x01, 0x20, 0x01, 0x28, 0x01, 0x22,
-    0x63, 0x0a, 0x09, 0x48, 0x69, 0x73, 0x74, 0x6f, 0x67, 0x72, 0x61, 0x6d, 0x12, 0x14, 0x0a, 0x0c,
-    0x73, 0x61, 0x6d, 0x70, 0x6c, 0x65, 0x5f, 0x63, 0x6f, 0x75, 0x6e, 0x74, 0x18, 0x01, 0x20, 0x01,
-    0x28, 0x04, 0x12, 0x12, 0x0a, 0x0a, 0x73, 0x61, 0x6d, 0x70, 0x6c, 0x65, 0x5f, 0x73, 0x75, 0x6d,
-    0x18, 0x02, 0x20, 0x01, 0x28, 0x01, 0x12, 0x2c, 0x0a, 0x06, 0x62, 0x75, 0x63, 0x6b, 0x65, 0x74,
-    0x18, 0x03, 0x20, 0x03, 0x28, 0x0b, 0x32, 0x1c, 0x2e, 0x69, 0x6f, 0x2e, 0x70, 0x72, 0x6f, 0x6d,
-    0x65, 0x74, 0x68, 0x65, 0x75, 0x73, 0x2e, 0x63, 0x6c, 0x69, 0x65, 0x6e, 0x74, 0x2e, 0x42, 0x75,
-    0x63, 0x6b, 0x65, 0x74, 0x22, 0x37, 0x0a, 0x06, 0x42, 0x75, 0x63, 0x6b, 0x65, 0x74, 0x12, 0x18,
-    0x0a, 0x10, 0x63, 0x75, 0x6d, 0x75, 0x6c, 0x61, 0x74, 0x69, 0x76, 0x65, 0x5f, 0x63, 0x6f, 0x75,
-    0x6e, 0x74, 0x18, 0x01, 0x20, 0x01, 0x28, 0x04, 0x12, 0x13, 0x0a, 0x0b, 0x75, 0x70, 0x70, 0x65,
-    0x72, 0x5f, 0x62, 0x6f, 0x75, 0x6e, 0x64, 0x18, 0x02, 0x20, 0x01, 0x28, 0x01, 0x22, 0xbe, 0x02,
-    0x0a, 0x06, 0x4d, 0x65, 0x74, 0x72, 0x69, 0x63, 0x12, 0x2e, 0x0a, 0x05, 0x6c, 0x61, 0x62, 0x65,
-    0x6c, 0x18, 0x01, 0x20, 0x03, 0x28, 0x0b, 0x32, 0x1f, 0x2e, 0x69, 0x6f, 0x2e, 0x70, 0x72, 0x6f,
-    0x6d, 0x65, 0x74, 0x68, 0x65, 0x75, 0x73, 0x2e, 0x63, 0x6c, 0x69, 0x65, 0x6e, 0x74, 0x2e, 0x4c,
-    0x61, 0x62, 0x65, 0x6c, 0x50, 0x61, 0x69, 0x72, 0x12, 0x2a, 0x0a, 0x05, 0x67, 0x61, 0x75, 0x67,
-    0x65, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x1b, 0x2e, 0x69, 0x6f, 0x2e, 0x70, 0x72, 0x6f,
-    0x6d, 0x65, 0x74, 0x68, 0x65, 0x75, 0x73, 0x2e, 0x63, 0x6c, 0x69, 0x65, 0x6e, 0x74, 0x2e, 0x47,
-    0x61, 0x75, 0x67, 0x65, 0x12, 0x2e, 0x0a, 0x07, 0x63, 0x6f, 0x75, 0x6e, 0x74, 0x65, 0x72, 0x18,
-    0x03, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x1d, 0x2e, 0x69, 0x6f, 0x2e, 0x70, 0x72, 0x6f, 0x6d, 0x65,
-    0x74, 0x68, 0x65, 0x75, 0x73, 0x2e, 0x63, 0x6c, 0x69, 0x65, 0x6e, 0x74, 0x2e, 0x43, 0x6f, 0x75,
-    0x6e, 0x74, 0x65, 0x72, 0x12, 0x2e, 0x0a, 0x07, 0x73, 0x75, 0x6d, 0x6d, 0x61, 0x72, 0x79, 0x18,
-    0x04, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x1d, 0x2e, 0x69, 0x6f, 0x2e, 0x70, 0x72, 0x6f, 0x6d, 0x65,
-    0x74, 0x68, 0x65, 0x75, 0x73, 0x2e, 0x63, 0x6c, 0x69, 0x65, 0x6e, 0x74, 0x2e, 0x53, 0x75, 0x6d,
-    0x6d, 0x61, 0x72, 0x79, 0x12, 0x2e, 0x0a, 0x07, 0x75, 0x6e, 0x74, 0x79, 0x70, 0x65, 0x64, 0x18,
-    0x05, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x1d, 0x2e, 0x69, 0x6f, 0x2e, 0x70, 0x72, 0x6f, 0x6d, 0x65,
-    0x74, 0x68, 0x65, 0x75, 0x73, 0x2e, 0x63, 0x6c, 0x69, 0x65, 0x6e, 0x74, 0x2e, 0x55, 0x6e, 0x74,
-    0x79, 0x70, 0x65, 0x64, 0x12, 0x32, 0x0a, 0x09, 0x68, 0x69, 0x73, 0x74, 0x6f, 0x67, 0x72, 0x61,
-    0x6d, 0x18, 0x07, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x1f, 0x2e, 0x69, 0x6f, 0x2e, 0x70, 0x72, 0x6f,
-    0x6d, 0x65, 0x74, 0x68, 0x65, 0x75, 0x73, 0x2e, 0x63, 0x6c, 0x69, 0x65, 0x6e, 0x74, 0x2e, 0x48,
-    0x69, 0x73, 0x74, 0x6f, 0x67, 0x72, 0x61, 0x6d, 0x12, 0x14, 0x0a, 0x0c, 0x74, 0x69, 0x6d, 0x65,
-    0x73, 0x74, 0x61, 0x6d, 0x70, 0x5f, 0x6d, 0x73, 0x18, 0x06, 0x20, 0x01, 0x28, 0x03, 0x22, 0x88,
-    0x01, 0x0a, 0x0c, 0x4d, 0x65, 0x74, 0x72, 0x69, 0x63, 0x46, 0x61, 0x6d, 0x69, 0x6c, 0x79, 0x12,
-    0x0c, 0x0a, 0x04, 0x6e, 0x61, 0x6d, 0x65, 0x18, 0x01, 0x20, 0x01, 0x28, 0x09, 0x12, 0x0c, 0x0a,
-    0x04, 0x68, 0x65, 0x6c, 0x70, 0x18, 0x02, 0x20, 0x01, 0x28, 0x09, 0x12, 0x2e, 0x0a, 0x04, 0x74,
-    0x79, 0x70, 0x65, 0x18, 0x03, 0x20, 0x01, 0x28, 0x0e, 0x32, 0x20, 0x2e, 0x69, 0x6f, 0x2e, 0x70,
-    0x72, 0x6f, 0x6d, 0x65, 0x74, 0x68, 0x65, 0x75, 0x73, 0x2e, 0x63, 0x6c, 0x69, 0x65, 0x6e, 0x74,
-    0x2e, 0x4d, 0x65, 0x74, 0x72, 0x69, 0x63, 0x54, 0x79, 0x70, 0x65, 0x12, 0x2c, 0x0a, 0x06, 0x6d,
-    0x65, 0x74, 0x72, 0x69, 0x63, 0x18, 0x04, 0x20, 0x03, 0x28, 0x0b, 0x32, 0x1c, 0x2e, 0x69, 0x6f,
-    0x2e, 0x70, 0x72, 0x6f, 0x6d, 0x65, 0x74, 0x68, 0x65, 0x75, 0x73, 0x2e, 0x63, 0x6c, 0x69, 0x65,
-    0x6e, 0x74, 0x2e, 0x4d, 0x65, 0x74, 0x72, 0x69, 0x63, 0x2a, 0x4d, 0x0a, 0x0a, 0x4d, 0x65, 0x74,
-    0x72, 0x69, 0x63, 0x54, 0x79, 0x70, 0x65, 0x12, 0x0b, 0x0a, 0x07, 0x43, 0x4f, 0x55, 0x4e, 0x54,
-    0x45, 0x52, 0x10, 0x00, 0x12, 0x09, 0x0a, 0x05, 0x47, 0x41, 0x55, 0x47, 0x45, 0x10, 0x01, 0x12,
-    0x0b, 0x0a, 0x07, 0x53, 0x55, 0x4d, 0x4d, 0x41, 0x52, 0x59, 0x10, 0x02, 0x12, 0x0b, 0x0a, 0x07,
-    0x55, 0x4e, 0x54, 0x59, 0x50, 0x45, 0x44, 0x10, 0x03, 0x12, 0x0d, 0x0a, 0x09, 0x48, 0x49, 0x53,
-    0x54, 0x4f, 0x47, 0x52, 0x41, 0x4d, 0x10, 0x04, 0x42, 0x16, 0x0a, 0x14, 0x69, 0x6f, 0x2e, 0x70,
-    0x72, 0x6f, 0x6d, 0x65, 0x74, 0x68, 0x65, 0x75, 0x73, 0x2e, 0x63, 0x6c, 0x69, 0x65, 0x6e, 0x74,
-    0x4a, 0xb4, 0x13, 0x0a, 0x06, 0x12, 0x04, 0x0d, 0x00, 0x50, 0x01, 0x0a, 0x08, 0x0a, 0x01, 0x02,
-    0x12, 0x03, 0x0f, 0x08, 0x1c, 0x0a, 0x08, 0x0a, 0x01, 0x08, 0x12, 0x03, 0x10, 0x00, 0x2d, 0x0a,
-    0x0b, 0x0a, 0x04, 0x08, 0xe7, 0x07, 0x00, 0x12, 0x03, 0x10, 0x00, 0x2d, 0x0a, 0x0c, 0x0a, 0x05,
-    0x08, 0xe7, 0x07, 0x00, 0x02, 0x12, 0x03, 0x10, 0x07, 0x13, 0x0a, 0x0d, 0x0a, 0x06, 0x08, 0xe7,
-    0x07, 0x00, 0x02, 0x00, 0x12, 0x03, 0x10, 0x07, 0x13, 0x0a, 0x0e, 0x0a, 0x07, 0x08, 0xe7, 0x07,
-    0x00, 0x02, 0x00, 0x01, 0x12, 0x03, 0x10, 0x07, 0x13, 0x0a, 0x0c, 0x0a, 0x05, 0x08, 0xe7, 0x07,
-    0x00, 0x07, 0x12, 0x03, 0x10, 0x16, 0x2c, 0x0a, 0x0a, 0x0a, 0x02, 0x04, 0x00, 0x12, 0x04, 0x12,
-    0x00, 0x15, 0x01, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x00, 0x01, 0x12, 0x03, 0x12, 0x08, 0x11, 0x0a,
-    0x0b, 0x0a, 0x04, 0x04, 0x00, 0x02, 0x00, 0x12, 0x03, 0x13, 0x02, 0x1c, 0x0a, 0x0c, 0x0a, 0x05,
-    0x04, 0x00, 0x02, 0x00, 0x04, 0x12, 0x03, 0x13, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00,
-    0x02, 0x00, 0x05, 0x12, 0x03, 0x13, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x00,
-    0x01, 0x12, 0x03, 0x13, 0x12, 0x16, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x00, 0x03, 0x12,
-    0x03, 0x13, 0x1a, 0x1b, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x00, 0x02, 0x01, 0x12, 0x03, 0x14, 0x02,
-    0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x01, 0x04, 0x12, 0x03, 0x14, 0x02, 0x0a, 0x0a,
-    0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x01, 0x05, 0x12, 0x03, 0x14, 0x0b, 0x11, 0x0a, 0x0c, 0x0a,
-    0x05, 0x04, 0x00, 0x02, 0x01, 0x01, 0x12, 0x03, 0x14, 0x12, 0x17, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
-    0x00, 0x02, 0x01, 0x03, 0x12, 0x03, 0x14, 0x1a, 0x1b, 0x0a, 0x0a, 0x0a, 0x02, 0x05, 0x00, 0x12,
-    0x04, 0x17, 0x00, 0x1d, 0x01, 0x0a, 0x0a, 0x0a, 0x03, 0x05, 0x00, 0x01, 0x12, 0x03, 0x17, 0x05,
-    0x0f, 0x0a, 0x0b, 0x0a, 0x04, 0x05, 0x00, 0x02, 0x00, 0x12, 0x03, 0x18, 0x02, 0x11, 0x0a, 0x0c,
-    0x0a, 0x05, 0x05, 0x00, 0x02, 0x00, 0x01, 0x12, 0x03, 0x18, 0x02, 0x09, 0x0a, 0x0c, 0x0a, 0x05,
-    0x05, 0x00, 0x02, 0x00, 0x02, 0x12, 0x03, 0x18, 0x0f, 0x10, 0x0a, 0x0b, 0x0a, 0x04, 0x05, 0x00,
-    0x02, 0x01, 0x12, 0x03, 0x19, 0x02, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x01, 0x01,
-    0x12, 0x03, 0x19, 0x02, 0x07, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x01, 0x02, 0x12, 0x03,
-    0x19, 0x0f, 0x10, 0x0a, 0x0b, 0x0a, 0x04, 0x05, 0x00, 0x02, 0x02, 0x12, 0x03, 0x1a, 0x02, 0x11,
-    0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x02, 0x01, 0x12, 0x03, 0x1a, 0x02, 0x09, 0x0a, 0x0c,
-    0x0a, 0x05, 0x05, 0x00, 0x02, 0x02, 0x02, 0x12, 0x03, 0x1a, 0x0f, 0x10, 0x0a, 0x0b, 0x0a, 0x04,
-    0x05, 0x00, 0x02, 0x03, 0x12, 0x03, 0x1b, 0x02, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02,
-    0x03, 0x01, 0x12, 0x03, 0x1b, 0x02, 0x09, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x03, 0x02,
-    0x12, 0x03, 0x1b, 0x0f, 0x10, 0x0a, 0x0b, 0x0a, 0x04, 0x05, 0x00, 0x02, 0x04, 0x12, 0x03, 0x1c,
-    0x02, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x04, 0x01, 0x12, 0x03, 0x1c, 0x02, 0x0b,
-    0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x04, 0x02, 0x12, 0x03, 0x1c, 0x0f, 0x10, 0x0a, 0x0a,
-    0x0a, 0x02, 0x04, 0x01, 0x12, 0x04, 0x1f, 0x00, 0x21, 0x01, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x01,
-    0x01, 0x12, 0x03, 0x1f, 0x08, 0x0d, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x01, 0x02, 0x00, 0x12, 0x03,
-    0x20, 0x02, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x00, 0x04, 0x12, 0x03, 0x20, 0x02,
-    0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x00, 0x05, 0x12, 0x03, 0x20, 0x0b, 0x11, 0x0a,
-    0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x00, 0x01, 0x12, 0x03, 0x20, 0x12, 0x17, 0x0a, 0x0c, 0x0a,
-    0x05, 0x04, 0x01, 0x02, 0x00, 0x03, 0x12, 0x03, 0x20, 0x1a, 0x1b, 0x0a, 0x0a, 0x0a, 0x02, 0x04,
-    0x02, 0x12, 0x04, 0x23, 0x00, 0x25, 0x01, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x02, 0x01, 0x12, 0x03,
-    0x23, 0x08, 0x0f, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x02, 0x02, 0x00, 0x12, 0x03, 0x24, 0x02, 0x1c,
-    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x00, 0x04, 0x12, 0x03, 0x24, 0x02, 0x0a, 0x0a, 0x0c,
-    0x0a, 0x05, 0x04, 0x02, 0x02, 0x00, 0x05, 0x12, 0x03, 0x24, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05,
-    0x04, 0x02, 0x02, 0x00, 0x01, 0x12, 0x03, 0x24, 0x12, 0x17, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02,
-    0x02, 0x00, 0x03, 0x12, 0x03, 0x24, 0x1a, 0x1b, 0x0a, 0x0a, 0x0a, 0x02, 0x04, 0x03, 0x12, 0x04,
-    0x27, 0x00, 0x2a, 0x01, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x03, 0x01, 0x12, 0x03, 0x27, 0x08, 0x10,
-    0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x03, 0x02, 0x00, 0x12, 0x03, 0x28, 0x02, 0x1f, 0x0a, 0x0c, 0x0a,
-    0x05, 0x04, 0x03, 0x02, 0x00, 0x04, 0x12, 0x03, 0x28, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
-    0x03, 0x02, 0x00, 0x05, 0x12, 0x03, 0x28, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02,
-    0x00, 0x01, 0x12, 0x03, 0x28, 0x12, 0x1a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x00, 0x03,
-    0x12, 0x03, 0x28, 0x1d, 0x1e, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x03, 0x02, 0x01, 0x12, 0x03, 0x29,
-    0x02, 0x1f, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x01, 0x04, 0x12, 0x03, 0x29, 0x02, 0x0a,
-    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x01, 0x05, 0x12, 0x03, 0x29, 0x0b, 0x11, 0x0a, 0x0c,
-    0x0a, 0x05, 0x04, 0x03, 0x02, 0x01, 0x01, 0x12, 0x03, 0x29, 0x12, 0x17, 0x0a, 0x0c, 0x0a, 0x05,
-    0x04, 0x03, 0x02, 0x01, 0x03, 0x12, 0x03, 0x29, 0x1d, 0x1e, 0x0a, 0x0a, 0x0a, 0x02, 0x04, 0x04,
-    0x12, 0x04, 0x2c, 0x00, 0x30, 0x01, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x04, 0x01, 0x12, 0x03, 0x2c,
-    0x08, 0x0f, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x04, 0x02, 0x00, 0x12, 0x03, 0x2d, 0x02, 0x25, 0x0a,
-    0x0c, 0x0a, 0x05, 0x04, 0x04, 0x02, 0x00, 0x04, 0x12, 0x03, 0x2d, 0x02, 0x0a, 0x0a, 0x0c, 0x0a,
-    0x05, 0x04, 0x04, 0x02, 0x00, 0x05, 0x12, 0x03, 0x2d, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
-    0x04, 0x02, 0x00, 0x01, 0x12, 0x03, 0x2d, 0x14, 0x20, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x04, 0x02,
-    0x00, 0x03, 0x12, 0x03, 0x2d, 0x23, 0x24, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x04, 0x02, 0x01, 0x12,
-    0x03, 0x2e, 0x02, 0x25, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x04, 0x02, 0x01, 0x04, 0x12, 0x03, 0x2e,
-    0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x04, 0x02, 0x01, 0x05, 0x12, 0x03, 0x2e, 0x0b, 0x11,
-    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x04, 0x02, 0x01, 0x01, 0x12, 0x03, 0x2e, 0x14, 0x1e, 0x0a, 0x0c,
-    0x0a, 0x05, 0x04, 0x04, 0x02, 0x01, 0x03, 0x12, 0x03, 0x2e, 0x23, 0x24, 0x0a, 0x0b, 0x0a, 0x04,
-    0x04, 0x04, 0x02, 0x02, 0x12, 0x03, 0x2f, 0x02, 0x25, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x04, 0x02,
-    0x02, 0x04, 0x12, 0x03, 0x2f, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x04, 0x02, 0x02, 0x06,
-    0x12, 0x03, 0x2f, 0x0b, 0x13, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x04, 0x02, 0x02, 0x01, 0x12, 0x03,
-    0x2f, 0x14, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x04, 0x02, 0x02, 0x03, 0x12, 0x03, 0x2f, 0x23,
-    0x24, 0x0a, 0x0a, 0x0a, 0x02, 0x04, 0x05, 0x12, 0x04, 0x32, 0x00, 0x34, 0x01, 0x0a, 0x0a, 0x0a,
-    0x03, 0x04, 0x05, 0x01, 0x12, 0x03, 0x32, 0x08, 0x0f, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x05, 0x02,
-    0x00, 0x12, 0x03, 0x33, 0x02, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x00, 0x04, 0x12,
-    0x03, 0x33, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x00, 0x05, 0x12, 0x03, 0x33,
-    0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x00, 0x01, 0x12, 0x03, 0x33, 0x12, 0x17,
-    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x00, 0x03, 0x12, 0x03, 0x33, 0x1a, 0x1b, 0x0a, 0x0a,
-    0x0a, 0x02, 0x04, 0x06, 0x12, 0x04, 0x36, 0x00, 0x3a, 0x01, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x06,
-    0x01, 0x12, 0x03, 0x36, 0x08, 0x11, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x06, 0x02, 0x00, 0x12, 0x03,
-    0x37, 0x02, 0x23, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x00, 0x04, 0x12, 0x03, 0x37, 0x02,
-    0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x00, 0x05, 0x12, 0x03, 0x37, 0x0b, 0x11, 0x0a,
-    0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x00, 0x01, 0x12, 0x03, 0x37, 0x12, 0x1e, 0x0a, 0x0c, 0x0a,
-    0x05, 0x04, 0x06, 0x02, 0x00, 0x03, 0x12, 0x03, 0x37, 0x21, 0x22, 0x0a, 0x0b, 0x0a, 0x04, 0x04,
-    0x06, 0x02, 0x01, 0x12, 0x03, 0x38, 0x02, 0x23, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x01,
-    0x04, 0x12, 0x03, 0x38, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x01, 0x05, 0x12,
-    0x03, 0x38, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x01, 0x01, 0x12, 0x03, 0x38,
-    0x12, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x01, 0x03, 0x12, 0x03, 0x38, 0x21, 0x22,
-    0x0a, 0x53, 0x0a, 0x04, 0x04, 0x06, 0x02, 0x02, 0x12, 0x03, 0x39, 0x02, 0x23, 0x22, 0x46, 0x20,
-    0x4f, 0x72, 0x64, 0x65, 0x72, 0x65, 0x64, 0x20, 0x69, 0x6e, 0x20, 0x69, 0x6e, 0x63, 0x72, 0x65,
-    0x61, 0x73, 0x69, 0x6e, 0x67, 0x20, 0x6f, 0x72, 0x64, 0x65, 0x72, 0x20, 0x6f, 0x66, 0x20, 0x75,
-    0x70, 0x70, 0x65, 0x72, 0x5f, 0x62, 0x6f, 0x75, 0x6e, 0x64, 0x2c, 0x20, 0x2b, 0x49, 0x6e, 0x66,
-    0x20, 0x62, 0x75, 0x63, 0x6b, 0x65, 0x74, 0x20, 0x69, 0x73, 0x20, 0x6f, 0x70, 0x74, 0x69, 0x6f,
-    0x6e, 0x61, 0x6c, 0x2e, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x02, 0x04, 0x12, 0x03,
-    0x39, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x02, 0x06, 0x12, 0x03, 0x39, 0x0b,
-    0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x02, 0x01, 0x12, 0x03, 0x39, 0x12, 0x18, 0x0a,
-    0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x02, 0x03, 0x12, 0x03, 0x39, 0x21, 0x22, 0x0a, 0x0a, 0x0a,
-    0x02, 0x04, 0x07, 0x12, 0x04, 0x3c, 0x00, 0x3f, 0x01, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x07, 0x01,
-    0x12, 0x03, 0x3c, 0x08, 0x0e, 0x0a, 0x2e, 0x0a, 0x04, 0x04, 0x07, 0x02, 0x00, 0x12, 0x03, 0x3d,
-    0x02, 0x27, 0x22, 0x21, 0x20, 0x43, 0x75, 0x6d, 0x75, 0x6c, 0x61, 0x74, 0x69, 0x76, 0x65, 0x20,
-    0x69, 0x6e, 0x20, 0x69, 0x6e, 0x63, 0x72, 0x65, 0x61, 0x73, 0x69, 0x6e, 0x67, 0x20, 0x6f, 0x72,
-    0x64, 0x65, 0x72, 0x2e, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x00, 0x04, 0x12, 0x03,
-    0x3d, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x00, 0x05, 0x12, 0x03, 0x3d, 0x0b,
-    0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x00, 0x01, 0x12, 0x03, 0x3d, 0x12, 0x22, 0x0a,
-    0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x00, 0x03, 0x12, 0x03, 0x3d, 0x25, 0x26, 0x0a, 0x19, 0x0a,
-    0x04, 0x04, 0x07, 0x02, 0x01, 0x12, 0x03, 0x3e, 0x02, 0x22, 0x22, 0x0c, 0x20, 0x49, 0x6e, 0x63,
-    0x6c, 0x75, 0x73, 0x69, 0x76, 0x65, 0x2e, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x01,
-    0x04, 0x12, 0x03, 0x3e, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x01, 0x05, 0x12,
-    0x03, 0x3e, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x01, 0x01, 0x12, 0x03, 0x3e,
-    0x12, 0x1d, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x01, 0x03, 0x12, 0x03, 0x3e, 0x20, 0x21,
-    0x0a, 0x0a, 0x0a, 0x02, 0x04, 0x08, 0x12, 0x04, 0x41, 0x00, 0x49, 0x01, 0x0a, 0x0a, 0x0a, 0x03,
-    0x04, 0x08, 0x01, 0x12, 0x03, 0x41, 0x08, 0x0e, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x08, 0x02, 0x00,
-    0x12, 0x03, 0x42, 0x02, 0x26, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x08, 0x02, 0x00, 0x04, 0x12, 0x03,
-    0x42, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x08, 0x02, 0x00, 0x06, 0x12, 0x03, 0x42, 0x0b,
-    0x14, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x08, 0x02, 0x00, 0x01, 0x12, 0x03, 0x42, 0x15, 0x1a, 0x0a,
-    0x0c, 0x0a, 0x05, 0x04, 0x08, 0x02, 0x00, 0x03, 0x12, 0x03, 0x42, 0x24, 0x25, 0x0a, 0x0b, 0x0a,
-    0x04, 0x04, 0x08, 0x02, 0x01, 0x12, 0x03, 0x43, 0x02, 0x26, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x08,
-    0x02, 0x01, 0x04, 0x12, 0x03, 0x43, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x08, 0x02, 0x01,
-    0x06, 0x12, 0x03, 0x43, 0x0b, 0x10, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x08, 0x02, 0x01, 0x01, 0x12,
-    0x03, 0x43, 0x15, 0x1a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x08, 0x02, 0x01, 0x03, 0x12, 0x03, 0x43,
-    0x24, 0x25, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x08, 0x02, 0x02, 0x12, 0x03, 0x44, 0x02, 0x26, 0x0a,
-    0x0c, 0x0a, 0x05, 0x04, 0x08, 0x02, 0x02, 0x04, 0x12, 0x03, 0x44, 0x02, 0x0a, 0x0a, 0x0c, 0x0a,
-    0x05, 0x04, 0x08, 0x02, 0x02, 0x06, 0x12, 0x03, 0x44, 0x0b, 0x12, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
-    0x08, 0x02, 0x02, 0x01, 0x12, 0x03, 0x44, 0x15, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x08, 0x02,
-    0x02, 0x03, 0x12, 0x03, 0x44, 0x24, 0x25, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x08, 0x02, 0x03, 0x12,
-    0x03, 0x45, 0x02, 0x26, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x08, 0x02, 0x03, 0x04, 0x12, 0x03, 0x45,
-    0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x08, 0x02, 0x03, 0x06, 0x12, 0x03, 0x45, 0x0b, 0x12,
-    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x08, 0x02, 0x03, 0x01, 0x12, 0x03, 0x45, 0x15, 0x1c, 0x0a, 0x0c,
-    0x0a, 0x05, 0x04, 0x08, 0x02, 0x03, 0x03, 0x12, 0x03, 0x45, 0x24, 0x25, 0x0a, 0x0b, 0x0a, 0x04,
-    0x04, 0x08, 0x02, 0x04, 0x12, 0x03, 0x46, 0x02, 0x26, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x08, 0x02,
-    0x04, 0x04, 0x12, 0x03, 0x46, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x08, 0x02, 0x04, 0x06,
-    0x12, 0x03, 0x46, 0x0b, 0x12, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x08, 0x02, 0x04, 0x01, 0x12, 0x03,
-    0x46, 0x15, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x08, 0x02, 0x04, 0x03, 0x12, 0x03, 0x46, 0x24,
-    0x25, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x08, 0x02, 0x05, 0x12, 0x03, 0x47, 0x02, 0x26, 0x0a, 0x0c,
-    0x0a, 0x05, 0x04, 0x08, 0x02, 0x05, 0x04, 0x12, 0x03, 0x47, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05,
-    0x04, 0x08, 0x02, 0x05, 0x06, 0x12, 0x03, 0x47, 0x0b, 0x14, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x08,
-    0x02, 0x05, 0x01, 0x12, 0x03, 0x47, 0x15, 0x1e, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x08, 0x02, 0x05,
-    0x03, 0x12, 0x03, 0x47, 0x24, 0x25, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x08, 0x02, 0x06, 0x12, 0x03,
-    0x48, 0x02, 0x26, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x08, 0x02, 0x06, 0x04, 0x12, 0x03, 0x48, 0x02,
-    0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x08, 0x02, 0x06, 0x05, 0x12, 0x03, 0x48, 0x0b, 0x10, 0x0a,
-    0x0c, 0x0a, 0x05, 0x04, 0x08, 0x02, 0x06, 0x01, 0x12, 0x03, 0x48, 0x15, 0x21, 0x0a, 0x0c, 0x0a,
-    0x05, 0x04, 0x08, 0x02, 0x06, 0x03, 0x12, 0x03, 0x48, 0x24, 0x25, 0x0a, 0x0a, 0x0a, 0x02, 0x04,
-    0x09, 0x12, 0x04, 0x4b, 0x00, 0x50, 0x01, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x09, 0x01, 0x12, 0x03,
-    0x4b, 0x08, 0x14, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x09, 0x02, 0x00, 0x12, 0x03, 0x4c, 0x02, 0x21,
-    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x00, 0x04, 0x12, 0x03, 0x4c, 0x02, 0x0a, 0x0a, 0x0c,
-    0x0a, 0x05, 0x04, 0x09, 0x02, 0x00, 0x05, 0x12, 0x03, 0x4c, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05,
-    0x04, 0x09, 0x02, 0x00, 0x01, 0x12, 0x03, 0x4c, 0x16, 0x1a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09,
-    0x02, 0x00, 0x03, 0x12, 0x03, 0x4c, 0x1f, 0x20, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x09, 0x02, 0x01,
-    0x12, 0x03, 0x4d, 0x02, 0x21, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x01, 0x04, 0x12, 0x03,
-    0x4d, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x01, 0x05, 0x12, 0x03, 0x4d, 0x0b,
-    0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x01, 0x01, 0x12, 0x03, 0x4d, 0x16, 0x1a, 0x0a,
-    0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x01, 0x03, 0x12, 0x03, 0x4d, 0x1f, 0x20, 0x0a, 0x0b, 0x0a,
-    0x04, 0x04, 0x09, 0x02, 0x02, 0x12, 0x03, 0x4e, 0x02, 0x21, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09,
-    0x02, 0x02, 0x04, 0x12, 0x03, 0x4e, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x02,
-    0x06, 0x12, 0x03, 0x4e, 0x0b, 0x15, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x02, 0x01, 0x12,
-    0x03, 0x4e, 0x16, 0x1a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x02, 0x03, 0x12, 0x03, 0x4e,
-    0x1f, 0x20, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x09, 0x02, 0x03, 0x12, 0x03, 0x4f, 0x02, 0x21, 0x0a,
-    0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x03, 0x04, 0x12, 0x03, 0x4f, 0x02, 0x0a, 0x0a, 0x0c, 0x0a,
-    0x05, 0x04, 0x09, 0x02, 0x03, 0x06, 0x12, 0x03, 0x4f, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
-    0x09, 0x02, 0x03, 0x01, 0x12, 0x03, 0x4f, 0x16, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02,
-    0x03, 0x03, 0x12, 0x03, 0x4f, 0x1f, 0x20,
-];
+impl ::protobuf::reflect::ProtobufValue for MetricType {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Enum(self.descriptor())
+    }
+}
+
+static file_descriptor_proto_data: &'static [u8] = b"\
+    \n\rmetrics.proto\x12\x14io.prometheus.client\x1a\x1fgoogle/protobuf/tim\
+    estamp.proto\"5\n\tLabelPair\x12\x12\n\x04name\x18\x01\x20\x01(\tR\x04na\
+    me\x12\x14\n\x05value\x18\x02\x20\x01(\tR\x05value\"\x1d\n\x05Gauge\x12\
+    \x14\n\x05value\x18\x01\x20\x01(\x01R\x05value\"\xa4\x01\n\x07Counter\
+    \x12\x14\n\x05value\x18\x01\x20\x01(\x01R\x05value\x12:\n\x08exemplar\
+    \x18\x02\x20\x01(\x0b2\x1e.io.prometheus.client.ExemplarR\x08exemplar\
+    \x12G\n\x11created_timestamp\x18\x03\x20\x01(\x0b2\x1a.google.protobuf.T\
+    imestampR\x10createdTimestamp\"<\n\x08Quantile\x12\x1a\n\x08quantile\x18\
+    \x01\x20\x01(\x01R\x08quantile\x12\x14\n\x05value\x18\x02\x20\x01(\x01R\
+    \x05value\"\xd0\x01\n\x07Summary\x12!\n\x0csample_count\x18\x01\x20\x01(\
+    \x04R\x0bsampleCount\x12\x1d\n\nsample_sum\x18\x02\x20\x01(\x01R\tsample\
+    Sum\x12:\n\x08quantile\x18\x03\x20\x03(\x0b2\x1e.io.prometheus.client.Qu\
+    antileR\x08quantile\x12G\n\x11created_timestamp\x18\x04\x20\x01(\x0b2\
+    \x1a.google.protobuf.TimestampR\x10createdTimestamp\"\x1f\n\x07Untyped\
+    \x12\x14\n\x05value\x18\x01\x20\x01(\x01R\x05value\"\xcc\x01\n\tHistogra\
+    m\x12!\n\x0csample_count\x18\x01\x20\x01(\x04R\x0bsampleCount\x12\x1d\n\
+    \nsample_sum\x18\x02\x20\x01(\x01R\tsampleSum\x124\n\x06bucket\x18\x03\
+    \x20\x03(\x0b2\x1c.io.prometheus.client.BucketR\x06bucket\x12G\n\x11crea\
+    ted_timestamp\x18\x0f\x20\x01(\x0b2\x1a.google.protobuf.TimestampR\x10cr\
+    eatedTimestamp\"\x90\x01\n\x06Bucket\x12)\n\x10cumulative_count\x18\x01\
+    \x20\x01(\x04R\x0fcumulativeCount\x12\x1f\n\x0bupper_bound\x18\x02\x20\
+    \x01(\x01R\nupperBound\x12:\n\x08exemplar\x18\x03\x20\x01(\x0b2\x1e.io.p\
+    rometheus.client.ExemplarR\x08exemplar\"\x91\x01\n\x08Exemplar\x125\n\
+    \x05label\x18\x01\x20\x03(\x0b2\x1f.io.prometheus.client.LabelPairR\x05l\
+    abel\x12\x14\n\x05value\x18\x02\x20\x01(\x01R\x05value\x128\n\ttimestamp\
+    \x18\x03\x20\x01(\x0b2\x1a.google.protobuf.TimestampR\ttimestamp\"\xff\
+    \x02\n\x06Metric\x125\n\x05label\x18\x01\x20\x03(\x0b2\x1f.io.prometheus\
+    .client.LabelPairR\x05label\x121\n\x05gauge\x18\x02\x20\x01(\x0b2\x1b.io\
+    .prometheus.client.GaugeR\x05gauge\x127\n\x07counter\x18\x03\x20\x01(\
+    \x0b2\x1d.io.prometheus.client.CounterR\x07counter\x127\n\x07summary\x18\
+    \x04\x20\x01(\x0b2\x1d.io.prometheus.client.SummaryR\x07summary\x127\n\
+    \x07untyped\x18\x05\x20\x01(\x0b2\x1d.io.prometheus.client.UntypedR\x07u\
+    ntyped\x12=\n\thistogram\x18\x07\x20\x01(\x0b2\x1f.io.prometheus.client.\
+    HistogramR\thistogram\x12!\n\x0ctimestamp_ms\x18\x06\x20\x01(\x03R\x0bti\
+    mestampMs\"\xb6\x01\n\x0cMetricFamily\x12\x12\n\x04name\x18\x01\x20\x01(\
+    \tR\x04name\x12\x12\n\x04help\x18\x02\x20\x01(\tR\x04help\x124\n\x04type\
+    \x18\x03\x20\x01(\x0e2\x20.io.prometheus.client.MetricTypeR\x04type\x124\
+    \n\x06metric\x18\x04\x20\x03(\x0b2\x1c.io.prometheus.client.MetricR\x06m\
+    etric\x12\x12\n\x04unit\x18\x05\x20\x01(\tR\x04unit*M\n\nMetricType\x12\
+    \x0b\n\x07COUNTER\x10\0\x12\t\n\x05GAUGE\x10\x01\x12\x0b\n\x07SUMMARY\
+    \x10\x02\x12\x0b\n\x07UNTYPED\x10\x03\x12\r\n\tHISTOGRAM\x10\x04B\x16\n\
+    \x14io.prometheus.clientJ\xdc\x1c\n\x06\x12\x04\r\0a\x01\n\xbc\x04\n\x01\
+    \x0c\x12\x03\r\0\x122\xb1\x04\x20Copyright\x202013\x20Prometheus\x20Team\
+    \n\x20Licensed\x20under\x20the\x20Apache\x20License,\x20Version\x202.0\
+    \x20(the\x20\"License\");\n\x20you\x20may\x20not\x20use\x20this\x20file\
+    \x20except\x20in\x20compliance\x20with\x20the\x20License.\n\x20You\x20ma\
+    y\x20obtain\x20a\x20copy\x20of\x20the\x20License\x20at\n\n\x20http://www\
+    .apache.org/licenses/LICENSE-2.0\n\n\x20Unless\x20required\x20by\x20appl\
+    icable\x20law\x20or\x20agreed\x20to\x20in\x20writing,\x20software\n\x20d\
+    istributed\x20under\x20the\x20License\x20is\x20distributed\x20on\x20an\
+    \x20\"AS\x20IS\"\x20BASIS,\n\x20WITHOUT\x20WARRANTIES\x20OR\x20CONDITION\
+    S\x20OF\x20ANY\x20KIND,\x20either\x20express\x20or\x20implied.\n\x20See\
+    \x20the\x20License\x20for\x20the\x20specific\x20language\x20governing\
+    \x20permissions\x20and\n\x20limitations\x20under\x20the\x20License.\n\n\
+    \x08\n\x01\x02\x12\x03\x0f\0\x1d\n\x08\n\x01\x08\x12\x03\x10\0-\n\t\n\
+    \x02\x08\x01\x12\x03\x10\0-\n\t\n\x02\x03\0\x12\x03\x12\0)\n\n\n\x02\x04\
+    \0\x12\x04\x14\0\x17\x01\n\n\n\x03\x04\0\x01\x12\x03\x14\x08\x11\n\x0b\n\
+    \x04\x04\0\x02\0\x12\x03\x15\x02\x1c\n\x0c\n\x05\x04\0\x02\0\x04\x12\x03\
+    \x15\x02\n\n\x0c\n\x05\x04\0\x02\0\x05\x12\x03\x15\x0b\x11\n\x0c\n\x05\
+    \x04\0\x02\0\x01\x12\x03\x15\x12\x16\n\x0c\n\x05\x04\0\x02\0\x03\x12\x03\
+    \x15\x1a\x1b\n\x0b\n\x04\x04\0\x02\x01\x12\x03\x16\x02\x1c\n\x0c\n\x05\
+    \x04\0\x02\x01\x04\x12\x03\x16\x02\n\n\x0c\n\x05\x04\0\x02\x01\x05\x12\
+    \x03\x16\x0b\x11\n\x0c\n\x05\x04\0\x02\x01\x01\x12\x03\x16\x12\x17\n\x0c\
+    \n\x05\x04\0\x02\x01\x03\x12\x03\x16\x1a\x1b\n\n\n\x02\x05\0\x12\x04\x19\
+    \0\x1f\x01\n\n\n\x03\x05\0\x01\x12\x03\x19\x05\x0f\n\x0b\n\x04\x05\0\x02\
+    \0\x12\x03\x1a\x02\x11\n\x0c\n\x05\x05\0\x02\0\x01\x12\x03\x1a\x02\t\n\
+    \x0c\n\x05\x05\0\x02\0\x02\x12\x03\x1a\x0f\x10\n\x0b\n\x04\x05\0\x02\x01\
+    \x12\x03\x1b\x02\x11\n\x0c\n\x05\x05\0\x02\x01\x01\x12\x03\x1b\x02\x07\n\
+    \x0c\n\x05\x05\0\x02\x01\x02\x12\x03\x1b\x0f\x10\n\x0b\n\x04\x05\0\x02\
+    \x02\x12\x03\x1c\x02\x11\n\x0c\n\x05\x05\0\x02\x02\x01\x12\x03\x1c\x02\t\
+    \n\x0c\n\x05\x05\0\x02\x02\x02\x12\x03\x1c\x0f\x10\n\x0b\n\x04\x05\0\x02\
+    \x03\x12\x03\x1d\x02\x11\n\x0c\n\x05\x05\0\x02\x03\x01\x12\x03\x1d\x02\t\
+    \n\x0c\n\x05\x05\0\x02\x03\x02\x12\x03\x1d\x0f\x10\n\x0b\n\x04\x05\0\x02\
+    \x04\x12\x03\x1e\x02\x11\n\x0c\n\x05\x05\0\x02\x04\x01\x12\x03\x1e\x02\
+    \x0b\n\x0c\n\x05\x05\0\x02\x04\x02\x12\x03\x1e\x0f\x10\n\n\n\x02\x04\x01\
+    \x12\x04!\0#\x01\n\n\n\x03\x04\x01\x01\x12\x03!\x08\r\n\x0b\n\x04\x04\
+    \x01\x02\0\x12\x03\"\x02\x1c\n\x0c\n\x05\x04\x01\x02\0\x04\x12\x03\"\x02\
+    \n\n\x0c\n\x05\x04\x01\x02\0\x05\x12\x03\"\x0b\x11\n\x0c\n\x05\x04\x01\
+    \x02\0\x01\x12\x03\"\x12\x17\n\x0c\n\x05\x04\x01\x02\0\x03\x12\x03\"\x1a\
+    \x1b\n\n\n\x02\x04\x02\x12\x04%\0*\x01\n\n\n\x03\x04\x02\x01\x12\x03%\
+    \x08\x0f\n\x0b\n\x04\x04\x02\x02\0\x12\x03&\x02!\n\x0c\n\x05\x04\x02\x02\
+    \0\x04\x12\x03&\x02\n\n\x0c\n\x05\x04\x02\x02\0\x05\x12\x03&\x0b\x11\n\
+    \x0c\n\x05\x04\x02\x02\0\x01\x12\x03&\x14\x19\n\x0c\n\x05\x04\x02\x02\0\
+    \x03\x12\x03&\x1f\x20\n\x0b\n\x04\x04\x02\x02\x01\x12\x03'\x02!\n\x0c\n\
+    \x05\x04\x02\x02\x01\x04\x12\x03'\x02\n\n\x0c\n\x05\x04\x02\x02\x01\x06\
+    \x12\x03'\x0b\x13\n\x0c\n\x05\x04\x02\x02\x01\x01\x12\x03'\x14\x1c\n\x0c\
+    \n\x05\x04\x02\x02\x01\x03\x12\x03'\x1f\x20\n\x0b\n\x04\x04\x02\x02\x02\
+    \x12\x03)\x02;\n\x0c\n\x05\x04\x02\x02\x02\x04\x12\x03)\x02\n\n\x0c\n\
+    \x05\x04\x02\x02\x02\x06\x12\x03)\x0b$\n\x0c\n\x05\x04\x02\x02\x02\x01\
+    \x12\x03)%6\n\x0c\n\x05\x04\x02\x02\x02\x03\x12\x03)9:\n\n\n\x02\x04\x03\
+    \x12\x04,\0/\x01\n\n\n\x03\x04\x03\x01\x12\x03,\x08\x10\n\x0b\n\x04\x04\
+    \x03\x02\0\x12\x03-\x02\x1f\n\x0c\n\x05\x04\x03\x02\0\x04\x12\x03-\x02\n\
+    \n\x0c\n\x05\x04\x03\x02\0\x05\x12\x03-\x0b\x11\n\x0c\n\x05\x04\x03\x02\
+    \0\x01\x12\x03-\x12\x1a\n\x0c\n\x05\x04\x03\x02\0\x03\x12\x03-\x1d\x1e\n\
+    \x0b\n\x04\x04\x03\x02\x01\x12\x03.\x02\x1f\n\x0c\n\x05\x04\x03\x02\x01\
+    \x04\x12\x03.\x02\n\n\x0c\n\x05\x04\x03\x02\x01\x05\x12\x03.\x0b\x11\n\
+    \x0c\n\x05\x04\x03\x02\x01\x01\x12\x03.\x12\x17\n\x0c\n\x05\x04\x03\x02\
+    \x01\x03\x12\x03.\x1d\x1e\n\n\n\x02\x04\x04\x12\x041\07\x01\n\n\n\x03\
+    \x04\x04\x01\x12\x031\x08\x0f\n\x0b\n\x04\x04\x04\x02\0\x12\x032\x02%\n\
+    \x0c\n\x05\x04\x04\x02\0\x04\x12\x032\x02\n\n\x0c\n\x05\x04\x04\x02\0\
+    \x05\x12\x032\x0b\x11\n\x0c\n\x05\x04\x04\x02\0\x01\x12\x032\x14\x20\n\
+    \x0c\n\x05\x04\x04\x02\0\x03\x12\x032#$\n\x0b\n\x04\x04\x04\x02\x01\x12\
+    \x033\x02%\n\x0c\n\x05\x04\x04\x02\x01\x04\x12\x033\x02\n\n\x0c\n\x05\
+    \x04\x04\x02\x01\x05\x12\x033\x0b\x11\n\x0c\n\x05\x04\x04\x02\x01\x01\
+    \x12\x033\x14\x1e\n\x0c\n\x05\x04\x04\x02\x01\x03\x12\x033#$\n\x0b\n\x04\
+    \x04\x04\x02\x02\x12\x034\x02%\n\x0c\n\x05\x04\x04\x02\x02\x04\x12\x034\
+    \x02\n\n\x0c\n\x05\x04\x04\x02\x02\x06\x12\x034\x0b\x13\n\x0c\n\x05\x04\
+    \x04\x02\x02\x01\x12\x034\x14\x1c\n\x0c\n\x05\x04\x04\x02\x02\x03\x12\
+    \x034#$\n\x0b\n\x04\x04\x04\x02\x03\x12\x036\x02;\n\x0c\n\x05\x04\x04\
+    \x02\x03\x04\x12\x036\x02\n\n\x0c\n\x05\x04\x04\x02\x03\x06\x12\x036\x0b\
+    $\n\x0c\n\x05\x04\x04\x02\x03\x01\x12\x036%6\n\x0c\n\x05\x04\x04\x02\x03\
+    \x03\x12\x0369:\n\n\n\x02\x04\x05\x12\x049\0;\x01\n\n\n\x03\x04\x05\x01\
+    \x12\x039\x08\x0f\n\x0b\n\x04\x04\x05\x02\0\x12\x03:\x02\x1c\n\x0c\n\x05\
+    \x04\x05\x02\0\x04\x12\x03:\x02\n\n\x0c\n\x05\x04\x05\x02\0\x05\x12\x03:\
+    \x0b\x11\n\x0c\n\x05\x04\x05\x02\0\x01\x12\x03:\x12\x17\n\x0c\n\x05\x04\
+    \x05\x02\0\x03\x12\x03:\x1a\x1b\n\n\n\x02\x04\x06\x12\x04=\0C\x01\n\n\n\
+    \x03\x04\x06\x01\x12\x03=\x08\x11\n\x0b\n\x04\x04\x06\x02\0\x12\x03>\x02\
+    #\n\x0c\n\x05\x04\x06\x02\0\x04\x12\x03>\x02\n\n\x0c\n\x05\x04\x06\x02\0\
+    \x05\x12\x03>\x0b\x11\n\x0c\n\x05\x04\x06\x02\0\x01\x12\x03>\x12\x1e\n\
+    \x0c\n\x05\x04\x06\x02\0\x03\x12\x03>!\"\n\x0b\n\x04\x04\x06\x02\x01\x12\
+    \x03?\x02#\n\x0c\n\x05\x04\x06\x02\x01\x04\x12\x03?\x02\n\n\x0c\n\x05\
+    \x04\x06\x02\x01\x05\x12\x03?\x0b\x11\n\x0c\n\x05\x04\x06\x02\x01\x01\
+    \x12\x03?\x12\x1c\n\x0c\n\x05\x04\x06\x02\x01\x03\x12\x03?!\"\nS\n\x04\
+    \x04\x06\x02\x02\x12\x03@\x02#\"F\x20Ordered\x20in\x20increasing\x20orde\
+    r\x20of\x20upper_bound,\x20+Inf\x20bucket\x20is\x20optional.\n\n\x0c\n\
+    \x05\x04\x06\x02\x02\x04\x12\x03@\x02\n\n\x0c\n\x05\x04\x06\x02\x02\x06\
+    \x12\x03@\x0b\x11\n\x0c\n\x05\x04\x06\x02\x02\x01\x12\x03@\x12\x18\n\x0c\
+    \n\x05\x04\x06\x02\x02\x03\x12\x03@!\"\n\x0b\n\x04\x04\x06\x02\x03\x12\
+    \x03B\x02<\n\x0c\n\x05\x04\x06\x02\x03\x04\x12\x03B\x02\n\n\x0c\n\x05\
+    \x04\x06\x02\x03\x06\x12\x03B\x0b$\n\x0c\n\x05\x04\x06\x02\x03\x01\x12\
+    \x03B%6\n\x0c\n\x05\x04\x06\x02\x03\x03\x12\x03B9;\n\n\n\x02\x04\x07\x12\
+    \x04E\0I\x01\n\n\n\x03\x04\x07\x01\x12\x03E\x08\x0e\n.\n\x04\x04\x07\x02\
+    \0\x12\x03F\x02'\"!\x20Cumulative\x20in\x20increasing\x20order.\n\n\x0c\
+    \n\x05\x04\x07\x02\0\x04\x12\x03F\x02\n\n\x0c\n\x05\x04\x07\x02\0\x05\
+    \x12\x03F\x0b\x11\n\x0c\n\x05\x04\x07\x02\0\x01\x12\x03F\x12\"\n\x0c\n\
+    \x05\x04\x07\x02\0\x03\x12\x03F%&\n\x19\n\x04\x04\x07\x02\x01\x12\x03G\
+    \x02\"\"\x0c\x20Inclusive.\n\n\x0c\n\x05\x04\x07\x02\x01\x04\x12\x03G\
+    \x02\n\n\x0c\n\x05\x04\x07\x02\x01\x05\x12\x03G\x0b\x11\n\x0c\n\x05\x04\
+    \x07\x02\x01\x01\x12\x03G\x12\x1d\n\x0c\n\x05\x04\x07\x02\x01\x03\x12\
+    \x03G\x20!\n\x0b\n\x04\x04\x07\x02\x02\x12\x03H\x02!\n\x0c\n\x05\x04\x07\
+    \x02\x02\x04\x12\x03H\x02\n\n\x0c\n\x05\x04\x07\x02\x02\x06\x12\x03H\x0b\
+    \x13\n\x0c\n\x05\x04\x07\x02\x02\x01\x12\x03H\x14\x1c\n\x0c\n\x05\x04\
+    \x07\x02\x02\x03\x12\x03H\x1f\x20\n\n\n\x02\x04\x08\x12\x04K\0O\x01\n\n\
+    \n\x03\x04\x08\x01\x12\x03K\x08\x10\n\x0b\n\x04\x04\x08\x02\0\x12\x03L\
+    \x02\x1f\n\x0c\n\x05\x04\x08\x02\0\x04\x12\x03L\x02\n\n\x0c\n\x05\x04\
+    \x08\x02\0\x06\x12\x03L\x0b\x14\n\x0c\n\x05\x04\x08\x02\0\x01\x12\x03L\
+    \x15\x1a\n\x0c\n\x05\x04\x08\x02\0\x03\x12\x03L\x1d\x1e\n\x0b\n\x04\x04\
+    \x08\x02\x01\x12\x03M\x02\x1c\n\x0c\n\x05\x04\x08\x02\x01\x04\x12\x03M\
+    \x02\n\n\x0c\n\x05\x04\x08\x02\x01\x05\x12\x03M\x0b\x11\n\x0c\n\x05\x04\
+    \x08\x02\x01\x01\x12\x03M\x12\x17\n\x0c\n\x05\x04\x08\x02\x01\x03\x12\
+    \x03M\x1a\x1b\n!\n\x04\x04\x08\x02\x02\x12\x03N\x023\"\x14\x20OpenMetric\
+    s-style.\n\n\x0c\n\x05\x04\x08\x02\x02\x04\x12\x03N\x02\n\n\x0c\n\x05\
+    \x04\x08\x02\x02\x06\x12\x03N\x0b$\n\x0c\n\x05\x04\x08\x02\x02\x01\x12\
+    \x03N%.\n\x0c\n\x05\x04\x08\x02\x02\x03\x12\x03N12\n\n\n\x02\x04\t\x12\
+    \x04Q\0Y\x01\n\n\n\x03\x04\t\x01\x12\x03Q\x08\x0e\n\x0b\n\x04\x04\t\x02\
+    \0\x12\x03R\x02&\n\x0c\n\x05\x04\t\x02\0\x04\x12\x03R\x02\n\n\x0c\n\x05\
+    \x04\t\x02\0\x06\x12\x03R\x0b\x14\n\x0c\n\x05\x04\t\x02\0\x01\x12\x03R\
+    \x15\x1a\n\x0c\n\x05\x04\t\x02\0\x03\x12\x03R$%\n\x0b\n\x04\x04\t\x02\
+    \x01\x12\x03S\x02&\n\x0c\n\x05\x04\t\x02\x01\x04\x12\x03S\x02\n\n\x0c\n\
+    \x05\x04\t\x02\x01\x06\x12\x03S\x0b\x10\n\x0c\n\x05\x04\t\x02\x01\x01\
+    \x12\x03S\x15\x1a\n\x0c\n\x05\x04\t\x02\x01\x03\x12\x03S$%\n\x0b\n\x04\
+    \x04\t\x02\x02\x12\x03T\x02&\n\x0c\n\x05\x04\t\x02\x02\x04\x12\x03T\x02\
+    \n\n\x0c\n\x05\x04\t\x02\x02\x06\x12\x03T\x0b\x12\n\x0c\n\x05\x04\t\x02\
+    \x02\x01\x12\x03T\x15\x1c\n\x0c\n\x05\x04\t\x02\x02\x03\x12\x03T$%\n\x0b\
+    \n\x04\x04\t\x02\x03\x12\x03U\x02&\n\x0c\n\x05\x04\t\x02\x03\x04\x12\x03\
+    U\x02\n\n\x0c\n\x05\x04\t\x02\x03\x06\x12\x03U\x0b\x12\n\x0c\n\x05\x04\t\
+    \x02\x03\x01\x12\x03U\x15\x1c\n\x0c\n\x05\x04\t\x02\x03\x03\x12\x03U$%\n\
+    \x0b\n\x04\x04\t\x02\x04\x12\x03V\x02&\n\x0c\n\x05\x04\t\x02\x04\x04\x12\
+    \x03V\x02\n\n\x0c\n\x05\x04\t\x02\x04\x06\x12\x03V\x0b\x12\n\x0c\n\x05\
+    \x04\t\x02\x04\x01\x12\x03V\x15\x1c\n\x0c\n\x05\x04\t\x02\x04\x03\x12\
+    \x03V$%\n\x0b\n\x04\x04\t\x02\x05\x12\x03W\x02&\n\x0c\n\x05\x04\t\x02\
+    \x05\x04\x12\x03W\x02\n\n\x0c\n\x05\x04\t\x02\x05\x06\x12\x03W\x0b\x14\n\
+    \x0c\n\x05\x04\t\x02\x05\x01\x12\x03W\x15\x1e\n\x0c\n\x05\x04\t\x02\x05\
+    \x03\x12\x03W$%\n\x0b\n\x04\x04\t\x02\x06\x12\x03X\x02&\n\x0c\n\x05\x04\
+    \t\x02\x06\x04\x12\x03X\x02\n\n\x0c\n\x05\x04\t\x02\x06\x05\x12\x03X\x0b\
+    \x10\n\x0c\n\x05\x04\t\x02\x06\x01\x12\x03X\x15!\n\x0c\n\x05\x04\t\x02\
+    \x06\x03\x12\x03X$%\n\n\n\x02\x04\n\x12\x04[\0a\x01\n\n\n\x03\x04\n\x01\
+    \x12\x03[\x08\x14\n\x0b\n\x04\x04\n\x02\0\x12\x03\\\x02!\n\x0c\n\x05\x04\
+    \n\x02\0\x04\x12\x03\\\x02\n\n\x0c\n\x05\x04\n\x02\0\x05\x12\x03\\\x0b\
+    \x11\n\x0c\n\x05\x04\n\x02\0\x01\x12\x03\\\x16\x1a\n\x0c\n\x05\x04\n\x02\
+    \0\x03\x12\x03\\\x1f\x20\n\x0b\n\x04\x04\n\x02\x01\x12\x03]\x02!\n\x0c\n\
+    \x05\x04\n\x02\x01\x04\x12\x03]\x02\n\n\x0c\n\x05\x04\n\x02\x01\x05\x12\
+    \x03]\x0b\x11\n\x0c\n\x05\x04\n\x02\x01\x01\x12\x03]\x16\x1a\n\x0c\n\x05\
+    \x04\n\x02\x01\x03\x12\x03]\x1f\x20\n\x0b\n\x04\x04\n\x02\x02\x12\x03^\
+    \x02!\n\x0c\n\x05\x04\n\x02\x02\x04\x12\x03^\x02\n\n\x0c\n\x05\x04\n\x02\
+    \x02\x06\x12\x03^\x0b\x15\n\x0c\n\x05\x04\n\x02\x02\x01\x12\x03^\x16\x1a\
+    \n\x0c\n\x05\x04\n\x02\x02\x03\x12\x03^\x1f\x20\n\x0b\n\x04\x04\n\x02\
+    \x03\x12\x03_\x02!\n\x0c\n\x05\x04\n\x02\x03\x04\x12\x03_\x02\n\n\x0c\n\
+    \x05\x04\n\x02\x03\x06\x12\x03_\x0b\x11\n\x0c\n\x05\x04\n\x02\x03\x01\
+    \x12\x03_\x16\x1c\n\x0c\n\x05\x04\n\x02\x03\x03\x12\x03_\x1f\x20\n\x0b\n\
+    \x04\x04\n\x02\x04\x12\x03`\x02!\n\x0c\n\x05\x04\n\x02\x04\x04\x12\x03`\
+    \x02\n\n\x0c\n\x05\x04\n\x02\x04\x05\x12\x03`\x0b\x11\n\x0c\n\x05\x04\n\
+    \x02\x04\x01\x12\x03`\x16\x1a\n\x0c\n\x05\x04\n\x02\x04\x03\x12\x03`\x1f\
+    \x20\
+";
 
 static mut file_descriptor_proto_lazy: ::protobuf::lazy::Lazy<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::lazy::Lazy {
     lock: ::protobuf::lazy::ONCE_INIT,
@@ -0,0 +1,300 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+//
+// Pushing metric families to a Pushgateway, for jobs that are gone before Prometheus scrapes them.
+// refer to https://github.com/prometheus/pushgateway#api
+
+use hyper::client::Client;
+use hyper::header::Headers;
+use hyper::status::StatusClass;
+use promo_proto::MetricFamily;
+use text_format;
+
+/// How the families are serialized when pushed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PushFormat {
+    Text,
+    Protobuf,
+}
+
+/// How a push treats the metrics already in the group.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PushMethod {
+    /// `PUT`, replacing every metric of the group.
+    Replace,
+    /// `POST`, only replacing the metrics with the same names.
+    Add,
+}
+
+/// A group of metrics on a Pushgateway, identified by a job name and
+/// grouping labels.
+#[derive(Clone, Debug)]
+pub struct Pushgateway {
+    url: String,
+    job: String,
+    grouping: Vec<(String, String)>,
+    format: PushFormat,
+    method: PushMethod,
+    delete_on_stop: bool,
+}
+
+impl Pushgateway {
+    /// Push to the Pushgateway at `url`, e.g. `http://localhost:9091`, as the
+    /// given job. Families are pushed in the text format, replacing the group.
+    pub fn new(url: &str, job: &str) -> Self {
+        Pushgateway {
+            url: url.trim_end_matches('/').to_string(),
+            job: job.to_string(),
+            grouping: Vec::new(),
+            format: PushFormat::Text,
+            method: PushMethod::Replace,
+            delete_on_stop: false,
+        }
+    }
+
+    /// Add a grouping label, e.g. `instance`.
+    pub fn grouping(mut self, name: &str, value: &str) -> Self {
+        self.grouping.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn format(mut self, format: PushFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    pub fn method(mut self, method: PushMethod) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Delete the group from the Pushgateway on a clean shutdown, rather
+    /// than pushing one last time.
+    pub fn delete_on_stop(mut self, delete_on_stop: bool) -> Self {
+        self.delete_on_stop = delete_on_stop;
+        self
+    }
+
+    pub fn deletes_on_stop(&self) -> bool {
+        self.delete_on_stop
+    }
+
+    /// The URL of the group, `/metrics/job/<job>{/<label>/<value>}`. Values
+    /// that cannot go in a path segment as they are are base64 encoded.
+    pub fn group_url(&self) -> String {
+        let mut url = format!("{}/metrics", self.url);
+        let job = ("job".to_string(), self.job.clone());
+        for (name, value) in Some(&job).into_iter().chain(&self.grouping) {
+            if value.is_empty() || value.contains('/') || value.contains('%') {
+                url.push_str(&format!("/{}@base64/{}", name, base64url(value.as_bytes())));
+            } else {
+                url.push_str(&format!("/{}/{}", name, percent_encode(value)));
+            }
+        }
+        url
+    }
+
+    /// Push the families to the group.
+    pub fn push(&self, metric_families: &[MetricFamily]) -> Result<(), String> {
+        let (content_type, body) = match self.format {
+            PushFormat::Text => {
+                let text = text_format::families_to_text(metric_families);
+                (text_format::CONTENT_TYPE, text.into_bytes())
+            }
+            PushFormat::Protobuf => (super::CONTENT_TYPE, super::families_to_u8(metric_families)),
+        };
+        let mut headers = Headers::new();
+        headers.set_raw("Content-Type", vec![content_type.as_bytes().to_vec()]);
+        let client = Client::new();
+        let url = self.group_url();
+        let request = match self.method {
+            PushMethod::Replace => client.put(&url),
+            PushMethod::Add => client.post(&url),
+        };
+        check(&url, request.headers(headers).body(&body[..]).send())
+    }
+
+    /// Delete the group and all of its metrics.
+    pub fn delete(&self) -> Result<(), String> {
+        let url = self.group_url();
+        check(&url, Client::new().delete(&url).send())
+    }
+}
+
+fn check(url: &str, response: ::hyper::Result<::hyper::client::Response>) -> Result<(), String> {
+    match response {
+        Ok(ref response) if response.status.class() == StatusClass::Success => Ok(()),
+        Ok(response) => Err(format!("Pushgateway at {} answered {}", url, response.status)),
+        Err(e) => Err(format!("Unable to reach the Pushgateway at {}: {}", url, e)),
+    }
+}
+
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::new();
+    for byte in value.bytes() {
+        match byte {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+// The URL and filename safe alphabet of RFC 4648, with padding
+fn base64url(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ\
+                                  abcdefghijklmnopqrstuvwxyz\
+                                  0123456789-_";
+    if bytes.is_empty() {
+        return "=".to_string();
+    }
+    let mut encoded = String::new();
+    for chunk in bytes.chunks(3) {
+        let n = chunk.iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+
+    /// A request received by `stand_in`.
+    #[derive(Debug)]
+    pub struct Received {
+        pub method: String,
+        pub path: String,
+        pub content_type: Option<String>,
+        pub body: Vec<u8>,
+    }
+
+    /// Start a local HTTP server answering every request with the status,
+    /// returning its URL and the requests it receives.
+    pub fn stand_in(status: &'static str) -> (String, mpsc::Receiver<Received>) {
+        answering(vec![status])
+    }
+
+    /// Like `stand_in`, answering the requests with the statuses in turn.
+    /// The last status answers every request after that.
+    pub fn answering(statuses: Vec<&'static str>) -> (String, mpsc::Receiver<Received>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || for (i, stream) in listener.incoming().enumerate() {
+            let status = statuses[i.min(statuses.len() - 1)];
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut parts = request_line.split_whitespace();
+            let method = parts.next().unwrap_or("").to_string();
+            let path = parts.next().unwrap_or("").to_string();
+            let mut content_length = 0;
+            let mut content_type = None;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                let mut header = line.splitn(2, ':');
+                let name = header.next().unwrap().trim().to_lowercase();
+                let value = header.next().unwrap_or("").trim().to_string();
+                if name == "content-length" {
+                    content_length = value.parse().unwrap();
+                } else if name == "content-type" {
+                    content_type = Some(value);
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            write!(stream,
+                   "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                   status)
+                .unwrap();
+            let received = Received {
+                method,
+                path,
+                content_type,
+                body,
+            };
+            if tx.send(received).is_err() {
+                break;
+            }
+        });
+        (url, rx)
+    }
+
+    #[test]
+    fn group_urls() {
+        let gateway = Pushgateway::new("http://localhost:9091/", "batch job")
+            .grouping("instance", "host:1")
+            .grouping("path", "/var/tmp")
+            .grouping("empty", "");
+        assert_eq!(gateway.group_url(),
+                   "http://localhost:9091/metrics/job/batch%20job/instance/host%3A1\
+                    /path@base64/L3Zhci90bXA=/empty@base64/=");
+    }
+
+    #[test]
+    fn base64() {
+        assert_eq!(base64url(b"f"), "Zg==");
+        assert_eq!(base64url(b"fo"), "Zm8=");
+        assert_eq!(base64url(b"foo"), "Zm9v");
+        assert_eq!(base64url(b"\xfb\xff"), "-_8=");
+    }
+
+    #[test]
+    fn push_and_delete() {
+        let (url, requests) = stand_in("202 Accepted");
+        let gateway = Pushgateway::new(&url, "backup").grouping("instance", "db1");
+        let mut family = MetricFamily::new();
+        family.set_name("last_success".to_string());
+        family.set_field_type(::promo_proto::MetricType::GAUGE);
+        family.mut_metric().push(::promo_proto::Metric::new());
+
+        gateway.push(&[family.clone()]).unwrap();
+        let received = requests.recv().unwrap();
+        assert_eq!(received.method, "PUT");
+        assert_eq!(received.path, "/metrics/job/backup/instance/db1");
+        assert_eq!(received.content_type.unwrap(), text_format::CONTENT_TYPE);
+        assert_eq!(String::from_utf8(received.body).unwrap(),
+                   "# TYPE last_success gauge\nlast_success 0\n");
+
+        gateway.clone()
+            .method(PushMethod::Add)
+            .format(PushFormat::Protobuf)
+            .push(&[family])
+            .unwrap();
+        let received = requests.recv().unwrap();
+        assert_eq!(received.method, "POST");
+        assert!(received.content_type.unwrap().starts_with("application/vnd.google.protobuf"));
+
+        gateway.delete().unwrap();
+        assert_eq!(requests.recv().unwrap().method, "DELETE");
+    }
+
+    #[test]
+    fn failed_pushes_are_errors() {
+        let (url, _requests) = stand_in("400 Bad Request");
+        assert!(Pushgateway::new(&url, "backup").push(&[]).is_err());
+    }
+}
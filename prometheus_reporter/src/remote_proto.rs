@@ -0,0 +1,1451 @@
+// This file is generated. Do not edit
+// @generated
+
+// https://github.com/Manishearth/rust-clippy/issues/702
+#![allow(unknown_lints)]
+#![allow(clippy::all)]
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+
+#![allow(bare_trait_objects)]
+#![allow(mismatched_lifetime_syntaxes)]
+#![allow(dead_code)]
+#![allow(missing_docs)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+#![allow(static_mut_refs)]
+#![allow(trivial_casts)]
+#![allow(unsafe_code)]
+#![allow(unused_imports)]
+#![allow(unused_results)]
+
+use protobuf::Message as Message_imported_for_functions;
+use protobuf::ProtobufEnum as ProtobufEnum_imported_for_functions;
+
+#[derive(PartialEq,Clone,Default)]
+pub struct WriteRequest {
+    // message fields
+    pub timeseries: ::protobuf::RepeatedField<TimeSeries>,
+    pub metadata: ::protobuf::RepeatedField<MetricMetadata>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for WriteRequest {}
+
+impl WriteRequest {
+    pub fn new() -> WriteRequest {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static WriteRequest {
+        static mut instance: ::protobuf::lazy::Lazy<WriteRequest> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const WriteRequest,
+        };
+        unsafe {
+            instance.get(WriteRequest::new)
+        }
+    }
+
+    // repeated .prometheus.TimeSeries timeseries = 1;
+
+    pub fn clear_timeseries(&mut self) {
+        self.timeseries.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_timeseries(&mut self, v: ::protobuf::RepeatedField<TimeSeries>) {
+        self.timeseries = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_timeseries(&mut self) -> &mut ::protobuf::RepeatedField<TimeSeries> {
+        &mut self.timeseries
+    }
+
+    // Take field
+    pub fn take_timeseries(&mut self) -> ::protobuf::RepeatedField<TimeSeries> {
+        ::std::mem::replace(&mut self.timeseries, ::protobuf::RepeatedField::new())
+    }
+
+    pub fn get_timeseries(&self) -> &[TimeSeries] {
+        &self.timeseries
+    }
+
+    fn get_timeseries_for_reflect(&self) -> &::protobuf::RepeatedField<TimeSeries> {
+        &self.timeseries
+    }
+
+    fn mut_timeseries_for_reflect(&mut self) -> &mut ::protobuf::RepeatedField<TimeSeries> {
+        &mut self.timeseries
+    }
+
+    // repeated .prometheus.MetricMetadata metadata = 3;
+
+    pub fn clear_metadata(&mut self) {
+        self.metadata.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_metadata(&mut self, v: ::protobuf::RepeatedField<MetricMetadata>) {
+        self.metadata = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_metadata(&mut self) -> &mut ::protobuf::RepeatedField<MetricMetadata> {
+        &mut self.metadata
+    }
+
+    // Take field
+    pub fn take_metadata(&mut self) -> ::protobuf::RepeatedField<MetricMetadata> {
+        ::std::mem::replace(&mut self.metadata, ::protobuf::RepeatedField::new())
+    }
+
+    pub fn get_metadata(&self) -> &[MetricMetadata] {
+        &self.metadata
+    }
+
+    fn get_metadata_for_reflect(&self) -> &::protobuf::RepeatedField<MetricMetadata> {
+        &self.metadata
+    }
+
+    fn mut_metadata_for_reflect(&mut self) -> &mut ::protobuf::RepeatedField<MetricMetadata> {
+        &mut self.metadata
+    }
+}
+
+impl ::protobuf::Message for WriteRequest {
+    fn is_initialized(&self) -> bool {
+        for v in &self.timeseries {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.metadata {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.timeseries)?;
+                },
+                3 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.metadata)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        for value in &self.timeseries {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        for value in &self.metadata {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        for v in &self.timeseries {
+            os.write_tag(1, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        for v in &self.metadata {
+            os.write_tag(3, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for WriteRequest {
+    fn new() -> WriteRequest {
+        WriteRequest::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<WriteRequest>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<TimeSeries>>(
+                    "timeseries",
+                    WriteRequest::get_timeseries_for_reflect,
+                    WriteRequest::mut_timeseries_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<MetricMetadata>>(
+                    "metadata",
+                    WriteRequest::get_metadata_for_reflect,
+                    WriteRequest::mut_metadata_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<WriteRequest>(
+                    "WriteRequest",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for WriteRequest {
+    fn clear(&mut self) {
+        self.clear_timeseries();
+        self.clear_metadata();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for WriteRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for WriteRequest {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct MetricMetadata {
+    // message fields
+    pub field_type: MetricMetadata_MetricType,
+    pub metric_family_name: ::std::string::String,
+    pub help: ::std::string::String,
+    pub unit: ::std::string::String,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for MetricMetadata {}
+
+impl MetricMetadata {
+    pub fn new() -> MetricMetadata {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static MetricMetadata {
+        static mut instance: ::protobuf::lazy::Lazy<MetricMetadata> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const MetricMetadata,
+        };
+        unsafe {
+            instance.get(MetricMetadata::new)
+        }
+    }
+
+    // .prometheus.MetricMetadata.MetricType type = 1;
+
+    pub fn clear_field_type(&mut self) {
+        self.field_type = MetricMetadata_MetricType::UNKNOWN;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_field_type(&mut self, v: MetricMetadata_MetricType) {
+        self.field_type = v;
+    }
+
+    pub fn get_field_type(&self) -> MetricMetadata_MetricType {
+        self.field_type
+    }
+
+    fn get_field_type_for_reflect(&self) -> &MetricMetadata_MetricType {
+        &self.field_type
+    }
+
+    fn mut_field_type_for_reflect(&mut self) -> &mut MetricMetadata_MetricType {
+        &mut self.field_type
+    }
+
+    // string metric_family_name = 2;
+
+    pub fn clear_metric_family_name(&mut self) {
+        self.metric_family_name.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_metric_family_name(&mut self, v: ::std::string::String) {
+        self.metric_family_name = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_metric_family_name(&mut self) -> &mut ::std::string::String {
+        &mut self.metric_family_name
+    }
+
+    // Take field
+    pub fn take_metric_family_name(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.metric_family_name, ::std::string::String::new())
+    }
+
+    pub fn get_metric_family_name(&self) -> &str {
+        &self.metric_family_name
+    }
+
+    fn get_metric_family_name_for_reflect(&self) -> &::std::string::String {
+        &self.metric_family_name
+    }
+
+    fn mut_metric_family_name_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.metric_family_name
+    }
+
+    // string help = 4;
+
+    pub fn clear_help(&mut self) {
+        self.help.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_help(&mut self, v: ::std::string::String) {
+        self.help = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_help(&mut self) -> &mut ::std::string::String {
+        &mut self.help
+    }
+
+    // Take field
+    pub fn take_help(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.help, ::std::string::String::new())
+    }
+
+    pub fn get_help(&self) -> &str {
+        &self.help
+    }
+
+    fn get_help_for_reflect(&self) -> &::std::string::String {
+        &self.help
+    }
+
+    fn mut_help_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.help
+    }
+
+    // string unit = 5;
+
+    pub fn clear_unit(&mut self) {
+        self.unit.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_unit(&mut self, v: ::std::string::String) {
+        self.unit = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_unit(&mut self) -> &mut ::std::string::String {
+        &mut self.unit
+    }
+
+    // Take field
+    pub fn take_unit(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.unit, ::std::string::String::new())
+    }
+
+    pub fn get_unit(&self) -> &str {
+        &self.unit
+    }
+
+    fn get_unit_for_reflect(&self) -> &::std::string::String {
+        &self.unit
+    }
+
+    fn mut_unit_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.unit
+    }
+}
+
+impl ::protobuf::Message for MetricMetadata {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_proto3_enum_with_unknown_fields_into(wire_type, is, &mut self.field_type, 1, &mut self.unknown_fields)?
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.metric_family_name)?;
+                },
+                4 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.help)?;
+                },
+                5 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.unit)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.field_type != MetricMetadata_MetricType::UNKNOWN {
+            my_size += ::protobuf::rt::enum_size(1, self.field_type);
+        }
+        if !self.metric_family_name.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.metric_family_name);
+        }
+        if !self.help.is_empty() {
+            my_size += ::protobuf::rt::string_size(4, &self.help);
+        }
+        if !self.unit.is_empty() {
+            my_size += ::protobuf::rt::string_size(5, &self.unit);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if self.field_type != MetricMetadata_MetricType::UNKNOWN {
+            os.write_enum(1, self.field_type.value())?;
+        }
+        if !self.metric_family_name.is_empty() {
+            os.write_string(2, &self.metric_family_name)?;
+        }
+        if !self.help.is_empty() {
+            os.write_string(4, &self.help)?;
+        }
+        if !self.unit.is_empty() {
+            os.write_string(5, &self.unit)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for MetricMetadata {
+    fn new() -> MetricMetadata {
+        MetricMetadata::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<MetricMetadata>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<MetricMetadata_MetricType>>(
+                    "type",
+                    MetricMetadata::get_field_type_for_reflect,
+                    MetricMetadata::mut_field_type_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "metric_family_name",
+                    MetricMetadata::get_metric_family_name_for_reflect,
+                    MetricMetadata::mut_metric_family_name_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "help",
+                    MetricMetadata::get_help_for_reflect,
+                    MetricMetadata::mut_help_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "unit",
+                    MetricMetadata::get_unit_for_reflect,
+                    MetricMetadata::mut_unit_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<MetricMetadata>(
+                    "MetricMetadata",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for MetricMetadata {
+    fn clear(&mut self) {
+        self.clear_field_type();
+        self.clear_metric_family_name();
+        self.clear_help();
+        self.clear_unit();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for MetricMetadata {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for MetricMetadata {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+pub enum MetricMetadata_MetricType {
+    UNKNOWN = 0,
+    COUNTER = 1,
+    GAUGE = 2,
+    HISTOGRAM = 3,
+    GAUGEHISTOGRAM = 4,
+    SUMMARY = 5,
+    INFO = 6,
+    STATESET = 7,
+}
+
+impl ::protobuf::ProtobufEnum for MetricMetadata_MetricType {
+    fn value(&self) -> i32 {
+        *self as i32
+    }
+
+    fn from_i32(value: i32) -> ::std::option::Option<MetricMetadata_MetricType> {
+        match value {
+            0 => ::std::option::Option::Some(MetricMetadata_MetricType::UNKNOWN),
+            1 => ::std::option::Option::Some(MetricMetadata_MetricType::COUNTER),
+            2 => ::std::option::Option::Some(MetricMetadata_MetricType::GAUGE),
+            3 => ::std::option::Option::Some(MetricMetadata_MetricType::HISTOGRAM),
+            4 => ::std::option::Option::Some(MetricMetadata_MetricType::GAUGEHISTOGRAM),
+            5 => ::std::option::Option::Some(MetricMetadata_MetricType::SUMMARY),
+            6 => ::std::option::Option::Some(MetricMetadata_MetricType::INFO),
+            7 => ::std::option::Option::Some(MetricMetadata_MetricType::STATESET),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    fn values() -> &'static [Self] {
+        static values: &'static [MetricMetadata_MetricType] = &[
+            MetricMetadata_MetricType::UNKNOWN,
+            MetricMetadata_MetricType::COUNTER,
+            MetricMetadata_MetricType::GAUGE,
+            MetricMetadata_MetricType::HISTOGRAM,
+            MetricMetadata_MetricType::GAUGEHISTOGRAM,
+            MetricMetadata_MetricType::SUMMARY,
+            MetricMetadata_MetricType::INFO,
+            MetricMetadata_MetricType::STATESET,
+        ];
+        values
+    }
+
+    fn enum_descriptor_static(_: ::std::option::Option<MetricMetadata_MetricType>) -> &'static ::protobuf::reflect::EnumDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::EnumDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::EnumDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                ::protobuf::reflect::EnumDescriptor::new("MetricMetadata_MetricType", file_descriptor_proto())
+            })
+        }
+    }
+}
+
+impl ::std::marker::Copy for MetricMetadata_MetricType {
+}
+
+impl ::std::default::Default for MetricMetadata_MetricType {
+    fn default() -> Self {
+        MetricMetadata_MetricType::UNKNOWN
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for MetricMetadata_MetricType {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Enum(self.descriptor())
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct Sample {
+    // message fields
+    pub value: f64,
+    pub timestamp: i64,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for Sample {}
+
+impl Sample {
+    pub fn new() -> Sample {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static Sample {
+        static mut instance: ::protobuf::lazy::Lazy<Sample> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const Sample,
+        };
+        unsafe {
+            instance.get(Sample::new)
+        }
+    }
+
+    // double value = 1;
+
+    pub fn clear_value(&mut self) {
+        self.value = 0.;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_value(&mut self, v: f64) {
+        self.value = v;
+    }
+
+    pub fn get_value(&self) -> f64 {
+        self.value
+    }
+
+    fn get_value_for_reflect(&self) -> &f64 {
+        &self.value
+    }
+
+    fn mut_value_for_reflect(&mut self) -> &mut f64 {
+        &mut self.value
+    }
+
+    // int64 timestamp = 2;
+
+    pub fn clear_timestamp(&mut self) {
+        self.timestamp = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_timestamp(&mut self, v: i64) {
+        self.timestamp = v;
+    }
+
+    pub fn get_timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    fn get_timestamp_for_reflect(&self) -> &i64 {
+        &self.timestamp
+    }
+
+    fn mut_timestamp_for_reflect(&mut self) -> &mut i64 {
+        &mut self.timestamp
+    }
+}
+
+impl ::protobuf::Message for Sample {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeFixed64 {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_double()?;
+                    self.value = tmp;
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int64()?;
+                    self.timestamp = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.value != 0. {
+            my_size += 9;
+        }
+        if self.timestamp != 0 {
+            my_size += ::protobuf::rt::value_size(2, self.timestamp, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if self.value != 0. {
+            os.write_double(1, self.value)?;
+        }
+        if self.timestamp != 0 {
+            os.write_int64(2, self.timestamp)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for Sample {
+    fn new() -> Sample {
+        Sample::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<Sample>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeDouble>(
+                    "value",
+                    Sample::get_value_for_reflect,
+                    Sample::mut_value_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt64>(
+                    "timestamp",
+                    Sample::get_timestamp_for_reflect,
+                    Sample::mut_timestamp_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<Sample>(
+                    "Sample",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for Sample {
+    fn clear(&mut self) {
+        self.clear_value();
+        self.clear_timestamp();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Sample {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Sample {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct TimeSeries {
+    // message fields
+    pub labels: ::protobuf::RepeatedField<Label>,
+    pub samples: ::protobuf::RepeatedField<Sample>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for TimeSeries {}
+
+impl TimeSeries {
+    pub fn new() -> TimeSeries {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static TimeSeries {
+        static mut instance: ::protobuf::lazy::Lazy<TimeSeries> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const TimeSeries,
+        };
+        unsafe {
+            instance.get(TimeSeries::new)
+        }
+    }
+
+    // repeated .prometheus.Label labels = 1;
+
+    pub fn clear_labels(&mut self) {
+        self.labels.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_labels(&mut self, v: ::protobuf::RepeatedField<Label>) {
+        self.labels = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_labels(&mut self) -> &mut ::protobuf::RepeatedField<Label> {
+        &mut self.labels
+    }
+
+    // Take field
+    pub fn take_labels(&mut self) -> ::protobuf::RepeatedField<Label> {
+        ::std::mem::replace(&mut self.labels, ::protobuf::RepeatedField::new())
+    }
+
+    pub fn get_labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    fn get_labels_for_reflect(&self) -> &::protobuf::RepeatedField<Label> {
+        &self.labels
+    }
+
+    fn mut_labels_for_reflect(&mut self) -> &mut ::protobuf::RepeatedField<Label> {
+        &mut self.labels
+    }
+
+    // repeated .prometheus.Sample samples = 2;
+
+    pub fn clear_samples(&mut self) {
+        self.samples.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_samples(&mut self, v: ::protobuf::RepeatedField<Sample>) {
+        self.samples = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_samples(&mut self) -> &mut ::protobuf::RepeatedField<Sample> {
+        &mut self.samples
+    }
+
+    // Take field
+    pub fn take_samples(&mut self) -> ::protobuf::RepeatedField<Sample> {
+        ::std::mem::replace(&mut self.samples, ::protobuf::RepeatedField::new())
+    }
+
+    pub fn get_samples(&self) -> &[Sample] {
+        &self.samples
+    }
+
+    fn get_samples_for_reflect(&self) -> &::protobuf::RepeatedField<Sample> {
+        &self.samples
+    }
+
+    fn mut_samples_for_reflect(&mut self) -> &mut ::protobuf::RepeatedField<Sample> {
+        &mut self.samples
+    }
+}
+
+impl ::protobuf::Message for TimeSeries {
+    fn is_initialized(&self) -> bool {
+        for v in &self.labels {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.samples {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.labels)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.samples)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        for value in &self.labels {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        for value in &self.samples {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        for v in &self.labels {
+            os.write_tag(1, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        for v in &self.samples {
+            os.write_tag(2, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for TimeSeries {
+    fn new() -> TimeSeries {
+        TimeSeries::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<TimeSeries>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Label>>(
+                    "labels",
+                    TimeSeries::get_labels_for_reflect,
+                    TimeSeries::mut_labels_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Sample>>(
+                    "samples",
+                    TimeSeries::get_samples_for_reflect,
+                    TimeSeries::mut_samples_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<TimeSeries>(
+                    "TimeSeries",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for TimeSeries {
+    fn clear(&mut self) {
+        self.clear_labels();
+        self.clear_samples();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for TimeSeries {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for TimeSeries {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct Label {
+    // message fields
+    pub name: ::std::string::String,
+    pub value: ::std::string::String,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for Label {}
+
+impl Label {
+    pub fn new() -> Label {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static Label {
+        static mut instance: ::protobuf::lazy::Lazy<Label> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const Label,
+        };
+        unsafe {
+            instance.get(Label::new)
+        }
+    }
+
+    // string name = 1;
+
+    pub fn clear_name(&mut self) {
+        self.name.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_name(&mut self, v: ::std::string::String) {
+        self.name = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_name(&mut self) -> &mut ::std::string::String {
+        &mut self.name
+    }
+
+    // Take field
+    pub fn take_name(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.name, ::std::string::String::new())
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    fn get_name_for_reflect(&self) -> &::std::string::String {
+        &self.name
+    }
+
+    fn mut_name_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.name
+    }
+
+    // string value = 2;
+
+    pub fn clear_value(&mut self) {
+        self.value.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_value(&mut self, v: ::std::string::String) {
+        self.value = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_value(&mut self) -> &mut ::std::string::String {
+        &mut self.value
+    }
+
+    // Take field
+    pub fn take_value(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.value, ::std::string::String::new())
+    }
+
+    pub fn get_value(&self) -> &str {
+        &self.value
+    }
+
+    fn get_value_for_reflect(&self) -> &::std::string::String {
+        &self.value
+    }
+
+    fn mut_value_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.value
+    }
+}
+
+impl ::protobuf::Message for Label {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.name)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.value)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.name.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.name);
+        }
+        if !self.value.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.value);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if !self.name.is_empty() {
+            os.write_string(1, &self.name)?;
+        }
+        if !self.value.is_empty() {
+            os.write_string(2, &self.value)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for Label {
+    fn new() -> Label {
+        Label::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<Label>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "name",
+                    Label::get_name_for_reflect,
+                    Label::mut_name_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "value",
+                    Label::get_value_for_reflect,
+                    Label::mut_value_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<Label>(
+                    "Label",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for Label {
+    fn clear(&mut self) {
+        self.clear_name();
+        self.clear_value();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Label {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Label {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+static file_descriptor_proto_data: &'static [u8] = b"\
+    \n\x0cremote.proto\x12\nprometheus\"\x84\x01\n\x0cWriteRequest\x126\n\nt\
+    imeseries\x18\x01\x20\x03(\x0b2\x16.prometheus.TimeSeriesR\ntimeseries\
+    \x126\n\x08metadata\x18\x03\x20\x03(\x0b2\x1a.prometheus.MetricMetadataR\
+    \x08metadataJ\x04\x08\x02\x10\x03\"\x9c\x02\n\x0eMetricMetadata\x129\n\
+    \x04type\x18\x01\x20\x01(\x0e2%.prometheus.MetricMetadata.MetricTypeR\
+    \x04type\x12,\n\x12metric_family_name\x18\x02\x20\x01(\tR\x10metricFamil\
+    yName\x12\x12\n\x04help\x18\x04\x20\x01(\tR\x04help\x12\x12\n\x04unit\
+    \x18\x05\x20\x01(\tR\x04unit\"y\n\nMetricType\x12\x0b\n\x07UNKNOWN\x10\0\
+    \x12\x0b\n\x07COUNTER\x10\x01\x12\t\n\x05GAUGE\x10\x02\x12\r\n\tHISTOGRA\
+    M\x10\x03\x12\x12\n\x0eGAUGEHISTOGRAM\x10\x04\x12\x0b\n\x07SUMMARY\x10\
+    \x05\x12\x08\n\x04INFO\x10\x06\x12\x0c\n\x08STATESET\x10\x07\"<\n\x06Sam\
+    ple\x12\x14\n\x05value\x18\x01\x20\x01(\x01R\x05value\x12\x1c\n\ttimesta\
+    mp\x18\x02\x20\x01(\x03R\ttimestamp\"e\n\nTimeSeries\x12)\n\x06labels\
+    \x18\x01\x20\x03(\x0b2\x11.prometheus.LabelR\x06labels\x12,\n\x07samples\
+    \x18\x02\x20\x03(\x0b2\x12.prometheus.SampleR\x07samples\"1\n\x05Label\
+    \x12\x12\n\x04name\x18\x01\x20\x01(\tR\x04name\x12\x14\n\x05value\x18\
+    \x02\x20\x01(\tR\x05valueJ\x8d\x12\n\x06\x12\x04\x10\0>\x01\n\xbf\x05\n\
+    \x01\x0c\x12\x03\x10\0\x122\xb1\x04\x20Copyright\x202016\x20Prometheus\
+    \x20Team\n\x20Licensed\x20under\x20the\x20Apache\x20License,\x20Version\
+    \x202.0\x20(the\x20\"License\");\n\x20you\x20may\x20not\x20use\x20this\
+    \x20file\x20except\x20in\x20compliance\x20with\x20the\x20License.\n\x20Y\
+    ou\x20may\x20obtain\x20a\x20copy\x20of\x20the\x20License\x20at\n\n\x20ht\
+    tp://www.apache.org/licenses/LICENSE-2.0\n\n\x20Unless\x20required\x20by\
+    \x20applicable\x20law\x20or\x20agreed\x20to\x20in\x20writing,\x20softwar\
+    e\n\x20distributed\x20under\x20the\x20License\x20is\x20distributed\x20on\
+    \x20an\x20\"AS\x20IS\"\x20BASIS,\n\x20WITHOUT\x20WARRANTIES\x20OR\x20CON\
+    DITIONS\x20OF\x20ANY\x20KIND,\x20either\x20express\x20or\x20implied.\n\
+    \x20See\x20the\x20License\x20for\x20the\x20specific\x20language\x20gover\
+    ning\x20permissions\x20and\n\x20limitations\x20under\x20the\x20License.\
+    \n2\x80\x01\x20The\x20messages\x20of\x20the\x20remote_write\x20protocol,\
+    \x20from\x20prometheus/prompb\x20remote.proto\x20and\n\x20types.proto\
+    \x20without\x20the\x20gogoproto\x20options.\n\n\x08\n\x01\x02\x12\x03\
+    \x12\0\x13\n\n\n\x02\x04\0\x12\x04\x14\0\x19\x01\n\n\n\x03\x04\0\x01\x12\
+    \x03\x14\x08\x14\n\x0b\n\x04\x04\0\x02\0\x12\x03\x15\x02%\n\x0c\n\x05\
+    \x04\0\x02\0\x04\x12\x03\x15\x02\n\n\x0c\n\x05\x04\0\x02\0\x06\x12\x03\
+    \x15\x0b\x15\n\x0c\n\x05\x04\0\x02\0\x01\x12\x03\x15\x16\x20\n\x0c\n\x05\
+    \x04\0\x02\0\x03\x12\x03\x15#$\nR\n\x03\x04\0\t\x12\x03\x17\x02\r\x1aF\
+    \x20Cortex\x20uses\x20this\x20field\x20to\x20determine\x20the\x20source\
+    \x20of\x20the\x20write\x20request.\n\n\x0b\n\x04\x04\0\t\0\x12\x03\x17\
+    \x0b\x0c\n\x0c\n\x05\x04\0\t\0\x01\x12\x03\x17\x0b\x0c\n\x0c\n\x05\x04\0\
+    \t\0\x02\x12\x03\x17\x0b\x0c\n\x0b\n\x04\x04\0\x02\x01\x12\x03\x18\x02'\
+    \n\x0c\n\x05\x04\0\x02\x01\x04\x12\x03\x18\x02\n\n\x0c\n\x05\x04\0\x02\
+    \x01\x06\x12\x03\x18\x0b\x19\n\x0c\n\x05\x04\0\x02\x01\x01\x12\x03\x18\
+    \x1a\"\n\x0c\n\x05\x04\0\x02\x01\x03\x12\x03\x18%&\n\n\n\x02\x04\x01\x12\
+    \x04\x1b\0,\x01\n\n\n\x03\x04\x01\x01\x12\x03\x1b\x08\x16\n\x0c\n\x04\
+    \x04\x01\x04\0\x12\x04\x1c\x02%\x03\n\x0c\n\x05\x04\x01\x04\0\x01\x12\
+    \x03\x1c\x07\x11\n\r\n\x06\x04\x01\x04\0\x02\0\x12\x03\x1d\x04\x17\n\x0e\
+    \n\x07\x04\x01\x04\0\x02\0\x01\x12\x03\x1d\x04\x0b\n\x0e\n\x07\x04\x01\
+    \x04\0\x02\0\x02\x12\x03\x1d\x15\x16\n\r\n\x06\x04\x01\x04\0\x02\x01\x12\
+    \x03\x1e\x04\x17\n\x0e\n\x07\x04\x01\x04\0\x02\x01\x01\x12\x03\x1e\x04\
+    \x0b\n\x0e\n\x07\x04\x01\x04\0\x02\x01\x02\x12\x03\x1e\x15\x16\n\r\n\x06\
+    \x04\x01\x04\0\x02\x02\x12\x03\x1f\x04\x17\n\x0e\n\x07\x04\x01\x04\0\x02\
+    \x02\x01\x12\x03\x1f\x04\t\n\x0e\n\x07\x04\x01\x04\0\x02\x02\x02\x12\x03\
+    \x1f\x15\x16\n\r\n\x06\x04\x01\x04\0\x02\x03\x12\x03\x20\x04\x17\n\x0e\n\
+    \x07\x04\x01\x04\0\x02\x03\x01\x12\x03\x20\x04\r\n\x0e\n\x07\x04\x01\x04\
+    \0\x02\x03\x02\x12\x03\x20\x15\x16\n\r\n\x06\x04\x01\x04\0\x02\x04\x12\
+    \x03!\x04\x17\n\x0e\n\x07\x04\x01\x04\0\x02\x04\x01\x12\x03!\x04\x12\n\
+    \x0e\n\x07\x04\x01\x04\0\x02\x04\x02\x12\x03!\x15\x16\n\r\n\x06\x04\x01\
+    \x04\0\x02\x05\x12\x03\"\x04\x17\n\x0e\n\x07\x04\x01\x04\0\x02\x05\x01\
+    \x12\x03\"\x04\x0b\n\x0e\n\x07\x04\x01\x04\0\x02\x05\x02\x12\x03\"\x15\
+    \x16\n\r\n\x06\x04\x01\x04\0\x02\x06\x12\x03#\x04\x17\n\x0e\n\x07\x04\
+    \x01\x04\0\x02\x06\x01\x12\x03#\x04\x08\n\x0e\n\x07\x04\x01\x04\0\x02\
+    \x06\x02\x12\x03#\x15\x16\n\r\n\x06\x04\x01\x04\0\x02\x07\x12\x03$\x04\
+    \x17\n\x0e\n\x07\x04\x01\x04\0\x02\x07\x01\x12\x03$\x04\x0c\n\x0e\n\x07\
+    \x04\x01\x04\0\x02\x07\x02\x12\x03$\x15\x16\nO\n\x04\x04\x01\x02\0\x12\
+    \x03(\x02\x16\x1aB\x20Represents\x20the\x20metric\x20type,\x20these\x20m\
+    atch\x20the\x20set\x20from\x20Prometheus.\n\n\x0c\n\x05\x04\x01\x02\0\
+    \x06\x12\x03(\x02\x0c\n\x0c\n\x05\x04\x01\x02\0\x01\x12\x03(\r\x11\n\x0c\
+    \n\x05\x04\x01\x02\0\x03\x12\x03(\x14\x15\n\x0b\n\x04\x04\x01\x02\x01\
+    \x12\x03)\x02\x20\n\x0c\n\x05\x04\x01\x02\x01\x05\x12\x03)\x02\x08\n\x0c\
+    \n\x05\x04\x01\x02\x01\x01\x12\x03)\t\x1b\n\x0c\n\x05\x04\x01\x02\x01\
+    \x03\x12\x03)\x1e\x1f\n\x0b\n\x04\x04\x01\x02\x02\x12\x03*\x02\x12\n\x0c\
+    \n\x05\x04\x01\x02\x02\x05\x12\x03*\x02\x08\n\x0c\n\x05\x04\x01\x02\x02\
+    \x01\x12\x03*\t\r\n\x0c\n\x05\x04\x01\x02\x02\x03\x12\x03*\x10\x11\n\x0b\
+    \n\x04\x04\x01\x02\x03\x12\x03+\x02\x12\n\x0c\n\x05\x04\x01\x02\x03\x05\
+    \x12\x03+\x02\x08\n\x0c\n\x05\x04\x01\x02\x03\x01\x12\x03+\t\r\n\x0c\n\
+    \x05\x04\x01\x02\x03\x03\x12\x03+\x10\x11\n\n\n\x02\x04\x02\x12\x04.\02\
+    \x01\n\n\n\x03\x04\x02\x01\x12\x03.\x08\x0e\n\x0b\n\x04\x04\x02\x02\0\
+    \x12\x03/\x02\x16\n\x0c\n\x05\x04\x02\x02\0\x05\x12\x03/\x02\x08\n\x0c\n\
+    \x05\x04\x02\x02\0\x01\x12\x03/\t\x0e\n\x0c\n\x05\x04\x02\x02\0\x03\x12\
+    \x03/\x14\x15\n(\n\x04\x04\x02\x02\x01\x12\x031\x02\x16\x1a\x1b\x20times\
+    tamp\x20is\x20in\x20ms\x20format\n\n\x0c\n\x05\x04\x02\x02\x01\x05\x12\
+    \x031\x02\x07\n\x0c\n\x05\x04\x02\x02\x01\x01\x12\x031\x08\x11\n\x0c\n\
+    \x05\x04\x02\x02\x01\x03\x12\x031\x14\x15\nP\n\x02\x04\x03\x12\x045\09\
+    \x01\x1aD\x20TimeSeries\x20represents\x20samples\x20and\x20labels\x20for\
+    \x20a\x20single\x20time\x20series.\n\n\n\n\x03\x04\x03\x01\x12\x035\x08\
+    \x12\nH\n\x04\x04\x03\x02\0\x12\x037\x02\x1e\x1a;\x20Labels\x20have\x20t\
+    o\x20be\x20sorted\x20by\x20name,\x20starting\x20with\x20__name__.\n\n\
+    \x0c\n\x05\x04\x03\x02\0\x04\x12\x037\x02\n\n\x0c\n\x05\x04\x03\x02\0\
+    \x06\x12\x037\x0b\x10\n\x0c\n\x05\x04\x03\x02\0\x01\x12\x037\x11\x17\n\
+    \x0c\n\x05\x04\x03\x02\0\x03\x12\x037\x1c\x1d\n\x0b\n\x04\x04\x03\x02\
+    \x01\x12\x038\x02\x1e\n\x0c\n\x05\x04\x03\x02\x01\x04\x12\x038\x02\n\n\
+    \x0c\n\x05\x04\x03\x02\x01\x06\x12\x038\x0b\x11\n\x0c\n\x05\x04\x03\x02\
+    \x01\x01\x12\x038\x12\x19\n\x0c\n\x05\x04\x03\x02\x01\x03\x12\x038\x1c\
+    \x1d\n\n\n\x02\x04\x04\x12\x04;\0>\x01\n\n\n\x03\x04\x04\x01\x12\x03;\
+    \x08\r\n\x0b\n\x04\x04\x04\x02\0\x12\x03<\x02\x13\n\x0c\n\x05\x04\x04\
+    \x02\0\x05\x12\x03<\x02\x08\n\x0c\n\x05\x04\x04\x02\0\x01\x12\x03<\t\r\n\
+    \x0c\n\x05\x04\x04\x02\0\x03\x12\x03<\x11\x12\n\x0b\n\x04\x04\x04\x02\
+    \x01\x12\x03=\x02\x13\n\x0c\n\x05\x04\x04\x02\x01\x05\x12\x03=\x02\x08\n\
+    \x0c\n\x05\x04\x04\x02\x01\x01\x12\x03=\t\x0e\n\x0c\n\x05\x04\x04\x02\
+    \x01\x03\x12\x03=\x11\x12b\x06proto3\
+";
+
+static mut file_descriptor_proto_lazy: ::protobuf::lazy::Lazy<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::lazy::Lazy {
+    lock: ::protobuf::lazy::ONCE_INIT,
+    ptr: 0 as *const ::protobuf::descriptor::FileDescriptorProto,
+};
+
+fn parse_descriptor_proto() -> ::protobuf::descriptor::FileDescriptorProto {
+    ::protobuf::parse_from_bytes(file_descriptor_proto_data).unwrap()
+}
+
+pub fn file_descriptor_proto() -> &'static ::protobuf::descriptor::FileDescriptorProto {
+    unsafe {
+        file_descriptor_proto_lazy.get(|| {
+            parse_descriptor_proto()
+        })
+    }
+}
@@ -0,0 +1,379 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+//
+// Sending metric families to a remote_write endpoint, for processes that cannot be scraped.
+// refer to https://prometheus.io/docs/concepts/remote_write_spec/
+
+use hyper::client::Client;
+use hyper::header::Headers;
+use hyper::status::StatusClass;
+use promo_proto::{LabelPair, Metric, MetricFamily, MetricType};
+use protobuf::{Message, RepeatedField};
+use remote_proto::{Label, Sample, TimeSeries, WriteRequest};
+use snap::raw::Encoder;
+use std::collections::VecDeque;
+use std::f64;
+use std::thread;
+use std::time::Duration;
+use text_format::format_float;
+
+/// A remote_write endpoint, e.g. `http://localhost:9090/api/v1/write`.
+#[derive(Clone, Debug)]
+pub struct RemoteWrite {
+    url: String,
+    max_retries: u32,
+    retry_backoff_ms: u64,
+    queue_capacity: usize,
+}
+
+impl RemoteWrite {
+    /// Write to the endpoint at `url`. Failed writes are retried 3 times,
+    /// and up to 100 write requests are queued while it cannot be reached.
+    pub fn new(url: &str) -> Self {
+        RemoteWrite {
+            url: url.to_string(),
+            max_retries: 3,
+            retry_backoff_ms: 100,
+            queue_capacity: 100,
+        }
+    }
+
+    /// How many times a failed write is retried before it is left queued.
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// How long to wait before the first retry, the wait doubles on every
+    /// following one.
+    pub fn retry_backoff_ms(mut self, retry_backoff_ms: u64) -> Self {
+        self.retry_backoff_ms = retry_backoff_ms;
+        self
+    }
+
+    /// How many write requests are kept while the endpoint cannot be
+    /// reached. The oldest one is dropped to make room for a new one.
+    pub fn queue_capacity(mut self, queue_capacity: usize) -> Self {
+        self.queue_capacity = queue_capacity;
+        self
+    }
+
+    /// Send the write request, retrying failures the endpoint may recover
+    /// from. The error tells whether the request is worth sending again.
+    pub fn send(&self, write_request: &WriteRequest) -> Result<(), (bool, String)> {
+        let body = Encoder::new()
+            .compress_vec(&write_request.write_to_bytes().map_err(|e| (false, e.to_string()))?)
+            .map_err(|e| (false, e.to_string()))?;
+        let mut backoff_ms = self.retry_backoff_ms;
+        let mut attempt = 0;
+        loop {
+            match self.post(&body) {
+                Err((true, _)) if attempt < self.max_retries => {
+                    thread::sleep(Duration::from_millis(backoff_ms));
+                    backoff_ms *= 2;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    fn post(&self, body: &[u8]) -> Result<(), (bool, String)> {
+        let mut headers = Headers::new();
+        headers.set_raw("Content-Encoding", vec![b"snappy".to_vec()]);
+        headers.set_raw("Content-Type", vec![b"application/x-protobuf".to_vec()]);
+        headers.set_raw("X-Prometheus-Remote-Write-Version", vec![b"0.1.0".to_vec()]);
+        match Client::new().post(&self.url).headers(headers).body(body).send() {
+            Ok(ref response) if response.status.class() == StatusClass::Success => Ok(()),
+            // Only server errors are worth retrying, the request itself is at fault otherwise
+            Ok(response) => {
+                Err((response.status.class() == StatusClass::ServerError,
+                     format!("remote_write to {} answered {}", self.url, response.status)))
+            }
+            Err(e) => Err((true, format!("Unable to remote_write to {}: {}", self.url, e))),
+        }
+    }
+}
+
+/// The write requests waiting to be sent, in the order they were made.
+pub struct Queue {
+    remote: RemoteWrite,
+    pending: VecDeque<WriteRequest>,
+    dropped: u64,
+}
+
+impl Queue {
+    pub fn new(remote: RemoteWrite) -> Self {
+        Queue {
+            remote,
+            pending: VecDeque::new(),
+            dropped: 0,
+        }
+    }
+
+    /// Queue a write request, dropping the oldest one when the queue is full.
+    pub fn push(&mut self, write_request: WriteRequest) {
+        if self.remote.queue_capacity == 0 {
+            self.dropped += 1;
+            return;
+        }
+        while self.pending.len() >= self.remote.queue_capacity {
+            self.pending.pop_front();
+            self.dropped += 1;
+        }
+        self.pending.push_back(write_request);
+    }
+
+    /// Send the queued write requests in order. Sending stops at the first
+    /// one the endpoint may still accept later, requests it rejects are dropped.
+    pub fn flush(&mut self) -> Result<(), String> {
+        let mut rejected = None;
+        while let Some(write_request) = self.pending.pop_front() {
+            match self.remote.send(&write_request) {
+                Ok(()) => {}
+                Err((true, e)) => {
+                    self.pending.push_front(write_request);
+                    return Err(e);
+                }
+                Err((false, e)) => {
+                    self.dropped += 1;
+                    rejected = Some(e);
+                }
+            }
+        }
+        rejected.map_or(Ok(()), Err)
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// How many write requests were dropped, because the queue was full or
+    /// the endpoint rejected them.
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+}
+
+/// Turn the metric families into time series, one per sample of the text
+/// format. Metrics without a timestamp are stamped with `timestamp_ms`.
+pub fn write_request<'a, I>(metric_families: I, timestamp_ms: i64) -> WriteRequest
+    where I: IntoIterator<Item = &'a MetricFamily>
+{
+    let mut timeseries = Vec::new();
+    for family in metric_families {
+        let name = family.get_name();
+        for metric in family.get_metric() {
+            let timestamp_ms = if metric.has_timestamp_ms() {
+                metric.get_timestamp_ms()
+            } else {
+                timestamp_ms
+            };
+            let mut series = |suffix: &str, extra_label: Option<(&str, &str)>, value: f64| {
+                let name = format!("{}{}", name, suffix);
+                timeseries.push(time_series(&name,
+                                            metric.get_label(),
+                                            extra_label,
+                                            value,
+                                            timestamp_ms));
+            };
+            match family.get_field_type() {
+                MetricType::COUNTER => series("", None, metric.get_counter().get_value()),
+                MetricType::GAUGE => series("", None, metric.get_gauge().get_value()),
+                MetricType::UNTYPED => series("", None, metric.get_untyped().get_value()),
+                MetricType::SUMMARY => {
+                    let summary = metric.get_summary();
+                    for quantile in summary.get_quantile() {
+                        let q = format_float(quantile.get_quantile());
+                        series("", Some(("quantile", &q)), quantile.get_value());
+                    }
+                    series("_sum", None, summary.get_sample_sum());
+                    series("_count", None, summary.get_sample_count() as f64);
+                }
+                MetricType::HISTOGRAM => write_histogram(metric, &mut series),
+            }
+        }
+    }
+    let mut write_request = WriteRequest::new();
+    write_request.set_timeseries(RepeatedField::from_vec(timeseries));
+    write_request
+}
+
+fn write_histogram<F>(metric: &Metric, series: &mut F)
+    where F: FnMut(&str, Option<(&str, &str)>, f64)
+{
+    let histogram = metric.get_histogram();
+    let mut has_inf_bucket = false;
+    for bucket in histogram.get_bucket() {
+        has_inf_bucket |= bucket.get_upper_bound() == f64::INFINITY;
+        let le = format_float(bucket.get_upper_bound());
+        series("_bucket", Some(("le", &le)), bucket.get_cumulative_count() as f64);
+    }
+    if !has_inf_bucket {
+        series("_bucket", Some(("le", "+Inf")), histogram.get_sample_count() as f64);
+    }
+    series("_sum", None, histogram.get_sample_sum());
+    series("_count", None, histogram.get_sample_count() as f64);
+}
+
+// The labels are sorted by name, with __name__ first as it sorts before any other label
+fn time_series(name: &str,
+               labels: &[LabelPair],
+               extra_label: Option<(&str, &str)>,
+               value: f64,
+               timestamp_ms: i64)
+               -> TimeSeries {
+    let mut pairs: Vec<(&str, &str)> = labels.iter()
+        .map(|label| (label.get_name(), label.get_value()))
+        .chain(extra_label)
+        .chain(Some(("__name__", name)))
+        .collect();
+    pairs.sort();
+    let labels = pairs.into_iter()
+        .map(|(name, value)| {
+            let mut label = Label::new();
+            label.set_name(name.to_string());
+            label.set_value(value.to_string());
+            label
+        })
+        .collect();
+    let mut sample = Sample::new();
+    sample.set_value(value);
+    sample.set_timestamp(timestamp_ms);
+    let mut time_series = TimeSeries::new();
+    time_series.set_labels(RepeatedField::from_vec(labels));
+    time_series.set_samples(RepeatedField::from_vec(vec![sample]));
+    time_series
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use promo_proto::{Bucket, Counter, Histogram};
+    use protobuf::parse_from_bytes;
+    use pushgateway::test::answering;
+    use snap::raw::Decoder;
+
+    fn label(name: &str, value: &str) -> LabelPair {
+        let mut label_pair = LabelPair::new();
+        label_pair.set_name(name.to_string());
+        label_pair.set_value(value.to_string());
+        label_pair
+    }
+
+    fn counter(name: &str, value: f64) -> MetricFamily {
+        let mut counter = Counter::new();
+        counter.set_value(value);
+        let mut metric = Metric::new();
+        metric.set_counter(counter);
+        metric.set_label(RepeatedField::from_vec(vec![label("zone", "eu"), label("app", "x")]));
+        let mut family = MetricFamily::new();
+        family.set_name(name.to_string());
+        family.set_field_type(MetricType::COUNTER);
+        family.set_metric(RepeatedField::from_vec(vec![metric]));
+        family
+    }
+
+    // The labels of every series as name=value lists, and their sample
+    fn flatten(write_request: &WriteRequest) -> Vec<(Vec<String>, f64, i64)> {
+        write_request.get_timeseries()
+            .iter()
+            .map(|series| {
+                let labels = series.get_labels()
+                    .iter()
+                    .map(|l| format!("{}={}", l.get_name(), l.get_value()))
+                    .collect();
+                let sample = &series.get_samples()[0];
+                (labels, sample.get_value(), sample.get_timestamp())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn counters_and_histograms() {
+        let mut bucket = Bucket::new();
+        bucket.set_upper_bound(0.5);
+        bucket.set_cumulative_count(3);
+        let mut histogram = Histogram::new();
+        histogram.set_bucket(RepeatedField::from_vec(vec![bucket]));
+        histogram.set_sample_sum(1.25);
+        histogram.set_sample_count(4);
+        let mut metric = Metric::new();
+        metric.set_histogram(histogram);
+        metric.set_timestamp_ms(7);
+        let mut family = MetricFamily::new();
+        family.set_name("latency".to_string());
+        family.set_field_type(MetricType::HISTOGRAM);
+        family.set_metric(RepeatedField::from_vec(vec![metric]));
+
+        let strings = |labels: &[&str]| labels.iter().map(|l| l.to_string()).collect::<Vec<_>>();
+        assert_eq!(flatten(&write_request(&[counter("requests", 2.0), family], 1000)),
+                   vec![(strings(&["__name__=requests", "app=x", "zone=eu"]), 2.0, 1000),
+                        (strings(&["__name__=latency_bucket", "le=0.5"]), 3.0, 7),
+                        (strings(&["__name__=latency_bucket", "le=+Inf"]), 4.0, 7),
+                        (strings(&["__name__=latency_sum"]), 1.25, 7),
+                        (strings(&["__name__=latency_count"]), 4.0, 7)]);
+    }
+
+    #[test]
+    fn snappy_compressed_protobuf() {
+        let (url, requests) = answering(vec!["204 No Content"]);
+        let request = write_request(&[counter("requests", 2.0)], 1000);
+        RemoteWrite::new(&url).send(&request).unwrap();
+
+        let received = requests.recv().unwrap();
+        assert_eq!(received.method, "POST");
+        assert_eq!(received.content_type.unwrap(), "application/x-protobuf");
+        let body = Decoder::new().decompress_vec(&received.body).unwrap();
+        let sent: WriteRequest = parse_from_bytes(&body).unwrap();
+        assert_eq!(flatten(&sent), flatten(&request));
+    }
+
+    #[test]
+    fn failed_writes_stay_queued() {
+        let (url, requests) =
+            answering(vec!["503 Service Unavailable", "503 Service Unavailable", "200 OK"]);
+        let remote = RemoteWrite::new(&url).max_retries(1).retry_backoff_ms(1);
+        let mut queue = Queue::new(remote);
+        queue.push(write_request(&[counter("requests", 1.0)], 1000));
+        assert!(queue.flush().is_err());
+        assert_eq!(queue.len(), 1);
+
+        queue.push(write_request(&[counter("requests", 2.0)], 2000));
+        queue.flush().unwrap();
+        assert!(queue.is_empty());
+        assert_eq!(queue.dropped(), 0);
+        assert_eq!(requests.iter().take(4).count(), 4);
+    }
+
+    #[test]
+    fn rejected_writes_are_dropped() {
+        let (url, requests) = answering(vec!["400 Bad Request"]);
+        let mut queue = Queue::new(RemoteWrite::new(&url).retry_backoff_ms(1));
+        queue.push(WriteRequest::new());
+        assert!(queue.flush().is_err());
+        assert!(queue.is_empty());
+        assert_eq!(queue.dropped(), 1);
+        // Not retried
+        requests.recv().unwrap();
+        assert!(requests.recv_timeout(Duration::from_millis(100)).is_err());
+    }
+
+    #[test]
+    fn the_queue_is_bounded() {
+        let mut queue = Queue::new(RemoteWrite::new("http://127.0.0.1:1").queue_capacity(2));
+        for i in 0..3 {
+            queue.push(write_request(&[counter("requests", 1.0)], i));
+        }
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.dropped(), 1);
+        assert_eq!(queue.pending[0].get_timeseries()[0].get_samples()[0].get_timestamp(), 1);
+    }
+}
@@ -0,0 +1,302 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+//
+// The Prometheus text exposition format, version 0.0.4.
+// refer to https://prometheus.io/docs/instrumenting/exposition_formats/
+
+use promo_proto::{LabelPair, Metric, MetricFamily, MetricType};
+use std::f64;
+use std::fmt::Write;
+
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Render the metric families in the text exposition format. Families
+/// without any metric are skipped.
+pub fn families_to_text<'a, I>(metric_families: I) -> String
+    where I: IntoIterator<Item = &'a MetricFamily>
+{
+    let mut text = String::new();
+    for family in metric_families {
+        if family.get_metric().is_empty() {
+            continue;
+        }
+        write_family(&mut text, family);
+    }
+    text
+}
+
+fn write_family(text: &mut String, family: &MetricFamily) {
+    let name = family.get_name();
+    if family.has_help() {
+        let _ = writeln!(text, "# HELP {} {}", name, escape_help(family.get_help()));
+    }
+    let _ = writeln!(text, "# TYPE {} {}", name, type_name(family.get_field_type()));
+
+    for metric in family.get_metric() {
+        let labels = metric.get_label();
+        match family.get_field_type() {
+            MetricType::COUNTER => {
+                write_sample(text, name, labels, None, metric.get_counter().get_value(), metric)
+            }
+            MetricType::GAUGE => {
+                write_sample(text, name, labels, None, metric.get_gauge().get_value(), metric)
+            }
+            MetricType::UNTYPED => {
+                write_sample(text, name, labels, None, metric.get_untyped().get_value(), metric)
+            }
+            MetricType::SUMMARY => {
+                let summary = metric.get_summary();
+                for quantile in summary.get_quantile() {
+                    let q = format_float(quantile.get_quantile());
+                    write_sample(text,
+                                 name,
+                                 labels,
+                                 Some(("quantile", &q)),
+                                 quantile.get_value(),
+                                 metric);
+                }
+                write_sample(text,
+                             &format!("{}_sum", name),
+                             labels,
+                             None,
+                             summary.get_sample_sum(),
+                             metric);
+                write_sample(text,
+                             &format!("{}_count", name),
+                             labels,
+                             None,
+                             summary.get_sample_count() as f64,
+                             metric);
+            }
+            MetricType::HISTOGRAM => {
+                let histogram = metric.get_histogram();
+                let bucket_name = format!("{}_bucket", name);
+                let mut has_inf_bucket = false;
+                for bucket in histogram.get_bucket() {
+                    let upper_bound = bucket.get_upper_bound();
+                    has_inf_bucket |= upper_bound == f64::INFINITY;
+                    let le = format_float(upper_bound);
+                    write_sample(text,
+                                 &bucket_name,
+                                 labels,
+                                 Some(("le", &le)),
+                                 bucket.get_cumulative_count() as f64,
+                                 metric);
+                }
+                // The +Inf bucket is mandatory and always holds every sample
+                if !has_inf_bucket {
+                    write_sample(text,
+                                 &bucket_name,
+                                 labels,
+                                 Some(("le", "+Inf")),
+                                 histogram.get_sample_count() as f64,
+                                 metric);
+                }
+                write_sample(text,
+                             &format!("{}_sum", name),
+                             labels,
+                             None,
+                             histogram.get_sample_sum(),
+                             metric);
+                write_sample(text,
+                             &format!("{}_count", name),
+                             labels,
+                             None,
+                             histogram.get_sample_count() as f64,
+                             metric);
+            }
+        }
+    }
+}
+
+fn write_sample(text: &mut String,
+                name: &str,
+                labels: &[LabelPair],
+                extra_label: Option<(&str, &str)>,
+                value: f64,
+                metric: &Metric) {
+    text.push_str(name);
+    write_labels(text, labels, extra_label);
+    text.push(' ');
+    text.push_str(&format_float(value));
+    if metric.has_timestamp_ms() {
+        let _ = write!(text, " {}", metric.get_timestamp_ms());
+    }
+    text.push('\n');
+}
+
+pub(crate) fn write_labels(text: &mut String,
+                           labels: &[LabelPair],
+                           extra_label: Option<(&str, &str)>) {
+    if labels.is_empty() && extra_label.is_none() {
+        return;
+    }
+    let pairs = labels.iter()
+        .map(|label| (label.get_name(), label.get_value()))
+        .chain(extra_label);
+    text.push('{');
+    for (i, (name, value)) in pairs.enumerate() {
+        if i > 0 {
+            text.push(',');
+        }
+        let _ = write!(text, "{}=\"{}\"", name, escape_label_value(value));
+    }
+    text.push('}');
+}
+
+fn type_name(metric_type: MetricType) -> &'static str {
+    match metric_type {
+        MetricType::COUNTER => "counter",
+        MetricType::GAUGE => "gauge",
+        MetricType::SUMMARY => "summary",
+        MetricType::UNTYPED => "untyped",
+        MetricType::HISTOGRAM => "histogram",
+    }
+}
+
+/// Escape backslashes and line feeds in a `# HELP` docstring.
+pub(crate) fn escape_help(help: &str) -> String {
+    help.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+/// Escape backslashes, double quotes and line feeds in a label value.
+pub(crate) fn escape_label_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// Format a sample value the way Prometheus parses it: `NaN`, `+Inf` and
+/// `-Inf` are spelled out, whole numbers have no fractional part.
+pub(crate) fn format_float(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value == f64::INFINITY {
+        "+Inf".to_string()
+    } else if value == f64::NEG_INFINITY {
+        "-Inf".to_string()
+    } else if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{}", value)
+    } else {
+        format!("{:?}", value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use promo_proto::{Bucket, Counter, Gauge, Histogram, Quantile, Summary};
+    use protobuf::repeated::RepeatedField;
+
+    fn label(name: &str, value: &str) -> LabelPair {
+        let mut label_pair = LabelPair::new();
+        label_pair.set_name(name.to_string());
+        label_pair.set_value(value.to_string());
+        label_pair
+    }
+
+    fn family(name: &str, metric_type: MetricType, metric: Metric) -> MetricFamily {
+        let mut family = MetricFamily::new();
+        family.set_name(name.to_string());
+        family.set_field_type(metric_type);
+        family.set_metric(RepeatedField::from_vec(vec![metric]));
+        family
+    }
+
+    #[test]
+    fn counter_with_help_and_labels() {
+        let mut counter = Counter::new();
+        counter.set_value(1027.0);
+        let mut metric = Metric::new();
+        metric.set_counter(counter);
+        metric.set_label(RepeatedField::from_vec(vec![label("method", "post"),
+                                                      label("code", "200")]));
+        metric.set_timestamp_ms(1395066363000);
+        let mut family = family("http_requests_total", MetricType::COUNTER, metric);
+        family.set_help("The total number of HTTP requests.".to_string());
+
+        assert_eq!(families_to_text(&[family]),
+                   "# HELP http_requests_total The total number of HTTP requests.\n\
+                    # TYPE http_requests_total counter\n\
+                    http_requests_total{method=\"post\",code=\"200\"} 1027 1395066363000\n");
+    }
+
+    #[test]
+    fn escaping() {
+        let mut gauge = Gauge::new();
+        gauge.set_value(1.5);
+        let mut metric = Metric::new();
+        metric.set_gauge(gauge);
+        metric.set_label(RepeatedField::from_vec(vec![label("path", "C:\\dir\n\"x\"")]));
+        let mut family = family("escaped", MetricType::GAUGE, metric);
+        family.set_help("A \\ backslash\nand a new line".to_string());
+
+        assert_eq!(families_to_text(&[family]),
+                   "# HELP escaped A \\\\ backslash\\nand a new line\n\
+                    # TYPE escaped gauge\n\
+                    escaped{path=\"C:\\\\dir\\n\\\"x\\\"\"} 1.5\n");
+    }
+
+    #[test]
+    fn float_formatting() {
+        assert_eq!(format_float(f64::NAN), "NaN");
+        assert_eq!(format_float(f64::INFINITY), "+Inf");
+        assert_eq!(format_float(f64::NEG_INFINITY), "-Inf");
+        assert_eq!(format_float(3.0), "3");
+        assert_eq!(format_float(-0.25), "-0.25");
+        assert_eq!(format_float(1e20), "1e20");
+        assert_eq!(format_float(1.5e-7), "1.5e-7");
+    }
+
+    #[test]
+    fn summary() {
+        let mut quantile = Quantile::new();
+        quantile.set_quantile(0.99);
+        quantile.set_value(0.003);
+        let mut summary = Summary::new();
+        summary.set_quantile(RepeatedField::from_vec(vec![quantile]));
+        summary.set_sample_sum(0.5);
+        summary.set_sample_count(100);
+        let mut metric = Metric::new();
+        metric.set_summary(summary);
+        metric.set_label(RepeatedField::from_vec(vec![label("handler", "/")]));
+
+        assert_eq!(families_to_text(&[family("rpc_duration_seconds",
+                                             MetricType::SUMMARY,
+                                             metric)]),
+                   "# TYPE rpc_duration_seconds summary\n\
+                    rpc_duration_seconds{handler=\"/\",quantile=\"0.99\"} 0.003\n\
+                    rpc_duration_seconds_sum{handler=\"/\"} 0.5\n\
+                    rpc_duration_seconds_count{handler=\"/\"} 100\n");
+    }
+
+    #[test]
+    fn histogram_gets_an_inf_bucket() {
+        let mut bucket = Bucket::new();
+        bucket.set_upper_bound(0.5);
+        bucket.set_cumulative_count(129389);
+        let mut histogram = Histogram::new();
+        histogram.set_bucket(RepeatedField::from_vec(vec![bucket]));
+        histogram.set_sample_sum(53423.0);
+        histogram.set_sample_count(144320);
+        let mut metric = Metric::new();
+        metric.set_histogram(histogram);
+
+        assert_eq!(families_to_text(&[family("request_duration_seconds",
+                                             MetricType::HISTOGRAM,
+                                             metric)]),
+                   "# TYPE request_duration_seconds histogram\n\
+                    request_duration_seconds_bucket{le=\"0.5\"} 129389\n\
+                    request_duration_seconds_bucket{le=\"+Inf\"} 144320\n\
+                    request_duration_seconds_sum 53423\n\
+                    request_duration_seconds_count 144320\n");
+    }
+
+    #[test]
+    fn empty_families_are_skipped() {
+        let mut family = MetricFamily::new();
+        family.set_name("empty".to_string());
+        assert_eq!(families_to_text(&[family]), "");
+    }
+}
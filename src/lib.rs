@@ -12,7 +12,13 @@
 //! Current reporters include:
 //!
 //! - Graphite/Carbon/Whisper
+//! - StatsD/DogStatsD
+//! - InfluxDB
+//! - OpenTSDB
+//! - Ganglia
+//! - JSON, as the Dropwizard metrics servlet writes it, to a file or over HTTP
 //! - Console/Syslog/Journald (via stdout)
+//! - Anything else, by implementing `reporter::ScheduledBackend`
 
 #![warn(missing_docs)]
 #![deny(trivial_numeric_casts,
@@ -21,7 +27,9 @@
 
 extern crate time;
 extern crate histogram;
+extern crate rand;
 
 pub mod metrics;
+pub mod registry;
 pub mod reporter;
 pub mod utils;
@@ -0,0 +1,340 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use histogram;
+use metrics::reservoir::{ExpDecayReservoir, HistogramReservoir, Reservoir, WeightedSample};
+use std::sync::{Arc, Mutex};
+
+/// The percentiles reported when none are configured.
+pub const DEFAULT_PERCENTILES: [f64; 8] = [50.0, 75.0, 95.0, 98.0, 99.0, 99.9, 99.99, 99.999];
+
+/// Naive implementation of a `Histogram` sampling values into a `Reservoir`.
+#[derive(Debug)]
+pub struct StdHistogram {
+    /// The recorded values.
+    data: Mutex<StdHistogramData>,
+    /// The percentiles computed on `snapshot`.
+    percentiles: Vec<f64>,
+}
+
+#[derive(Debug)]
+struct StdHistogramData {
+    count: u64,
+    sum: u64,
+    /// Pairs of `(upper bound, number of values at or below it)`, sorted.
+    buckets: Vec<(f64, u64)>,
+    reservoir: Box<dyn Reservoir>,
+}
+
+/// A snapshot of the distribution recorded by a `Histogram`.
+#[derive(Debug)]
+pub struct HistogramSnapshot {
+    /// The number of recorded values.
+    pub count: u64,
+    /// The smallest recorded value.
+    pub min: u64,
+    /// The largest recorded value.
+    pub max: u64,
+    /// The arithmetic mean of the recorded values.
+    pub mean: f64,
+    /// The standard deviation of the recorded values.
+    pub stddev: f64,
+    /// Pairs of `(percentile, value)`, in the order they were configured.
+    pub percentiles: Vec<(f64, u64)>,
+    /// The sum of every recorded value, unlike the statistics above it only
+    /// ever goes up until the histogram is cleared. `0` if the histogram does
+    /// not keep it.
+    pub sum: u64,
+    /// Pairs of `(upper bound, number of recorded values at or below it)`,
+    /// counted as the values are recorded, see `StdHistogram::with_buckets`.
+    pub buckets: Vec<(f64, u64)>,
+}
+
+impl HistogramSnapshot {
+    /// Compute the statistics of the samples kept by a `Reservoir`, out of
+    /// `count` recorded values.
+    pub fn from_samples(count: u64,
+                        mut samples: Vec<WeightedSample>,
+                        percentiles: &[f64])
+                        -> HistogramSnapshot {
+        samples.sort_by_key(|s| s.value);
+        let total_weight: f64 = samples.iter().map(|s| s.weight).sum();
+        if samples.is_empty() || total_weight == 0.0 {
+            return HistogramSnapshot {
+                count,
+                min: 0,
+                max: 0,
+                mean: 0.0,
+                stddev: 0.0,
+                percentiles: percentiles.iter().map(|&p| (p, 0)).collect(),
+                sum: 0,
+                buckets: Vec::new(),
+            };
+        }
+
+        let mean = samples.iter().map(|s| s.value as f64 * s.weight).sum::<f64>() / total_weight;
+        let variance = samples.iter()
+            .map(|s| {
+                let diff = s.value as f64 - mean;
+                diff * diff * s.weight
+            })
+            .sum::<f64>() / total_weight;
+
+        // The share of the total weight found before each sample.
+        let mut cumulative = Vec::with_capacity(samples.len());
+        let mut sum = 0.0;
+        for s in &samples {
+            cumulative.push(sum / total_weight);
+            sum += s.weight;
+        }
+
+        // Each percentile is the value of the last sample starting at or below it.
+        let value_at = |percentile: f64| {
+            let quantile = percentile / 100.0;
+            let position = cumulative.iter().take_while(|&&c| c <= quantile).count();
+            samples[position.max(1) - 1].value
+        };
+
+        HistogramSnapshot {
+            count,
+            min: samples[0].value,
+            max: samples[samples.len() - 1].value,
+            mean,
+            stddev: variance.sqrt(),
+            percentiles: percentiles.iter().map(|&p| (p, value_at(p))).collect(),
+            sum: 0,
+            buckets: Vec::new(),
+        }
+    }
+
+    /// Return the value recorded for the given percentile, if it was computed.
+    pub fn percentile(&self, percentile: f64) -> Option<u64> {
+        self.percentiles
+            .iter()
+            .find(|&&(p, _)| p == percentile)
+            .map(|&(_, value)| value)
+    }
+}
+
+/// `Histogram` is a `Metric` that measures the statistical distribution of
+/// values, such as response sizes or latencies.
+pub trait Histogram: Send + Sync {
+    /// Clear the histogram, dropping all recorded values.
+    fn clear(&self);
+    /// Record a value.
+    fn update(&self, value: u64);
+    /// Take a snapshot of the distribution for use with a `Reporter`.
+    fn snapshot(&self) -> HistogramSnapshot;
+}
+
+impl Histogram for StdHistogram {
+    fn clear(&self) {
+        let mut data = self.data.lock().unwrap();
+        data.count = 0;
+        data.sum = 0;
+        for bucket in &mut data.buckets {
+            bucket.1 = 0;
+        }
+        data.reservoir.clear();
+    }
+
+    fn update(&self, value: u64) {
+        let mut data = self.data.lock().unwrap();
+        data.count += 1;
+        data.sum = data.sum.saturating_add(value);
+        for bucket in &mut data.buckets {
+            if value as f64 <= bucket.0 {
+                bucket.1 += 1;
+            }
+        }
+        data.reservoir.update(value);
+    }
+
+    fn snapshot(&self) -> HistogramSnapshot {
+        let (count, sum, buckets, samples) = {
+            let data = self.data.lock().unwrap();
+            (data.count, data.sum, data.buckets.clone(), data.reservoir.samples())
+        };
+        let mut snapshot = HistogramSnapshot::from_samples(count, samples, &self.percentiles);
+        snapshot.sum = sum;
+        snapshot.buckets = buckets;
+        snapshot
+    }
+}
+
+impl StdHistogram {
+    /// Create a new `StdHistogram` biased towards the last 5 minutes of
+    /// values, reporting the default percentiles.
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// Create a new `StdHistogram` reporting the given percentiles.
+    pub fn with_percentiles(percentiles: Vec<f64>) -> Arc<Self> {
+        StdHistogram::with_reservoir_and_percentiles(ExpDecayReservoir::default(), percentiles)
+    }
+
+    /// Create a new `StdHistogram` sampling values into the given reservoir,
+    /// reporting the default percentiles.
+    pub fn with_reservoir<R: Reservoir + 'static>(reservoir: R) -> Arc<Self> {
+        StdHistogram::with_reservoir_and_percentiles(reservoir, DEFAULT_PERCENTILES.to_vec())
+    }
+
+    /// Create a new `StdHistogram` sampling values into the given reservoir,
+    /// reporting the given percentiles.
+    pub fn with_reservoir_and_percentiles<R: Reservoir + 'static>(reservoir: R,
+                                                                  percentiles: Vec<f64>)
+                                                                  -> Arc<Self> {
+        Arc::new(StdHistogram::from_reservoir(Box::new(reservoir), percentiles))
+    }
+
+    /// Create a new `StdHistogram` like `new` that also counts the values at
+    /// or below each of the given upper bounds, e.g. for Prometheus histograms.
+    pub fn with_buckets(upper_bounds: Vec<f64>) -> Arc<Self> {
+        let mut histogram = StdHistogram::default();
+        let mut upper_bounds: Vec<f64> =
+            upper_bounds.into_iter().filter(|bound| !bound.is_nan()).collect();
+        upper_bounds.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        upper_bounds.dedup();
+        histogram.data.get_mut().unwrap().buckets =
+            upper_bounds.into_iter().map(|bound| (bound, 0)).collect();
+        Arc::new(histogram)
+    }
+
+    /// Create a new `StdHistogram` counting every value into an already
+    /// configured `histogram::Histogram`, reporting the default percentiles.
+    pub fn from_histogram(h: histogram::Histogram) -> Arc<Self> {
+        StdHistogram::with_reservoir(HistogramReservoir::new(h))
+    }
+
+    pub(crate) fn from_reservoir(reservoir: Box<dyn Reservoir>, percentiles: Vec<f64>) -> Self {
+        StdHistogram {
+            data: Mutex::new(StdHistogramData {
+                count: 0,
+                sum: 0,
+                buckets: Vec::new(),
+                reservoir,
+            }),
+            percentiles,
+        }
+    }
+}
+
+impl Default for StdHistogram {
+    fn default() -> Self {
+        StdHistogram::from_reservoir(Box::new(ExpDecayReservoir::default()),
+                                     DEFAULT_PERCENTILES.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use metrics::reservoir::{SlidingWindowReservoir, UniformReservoir};
+    use std::thread;
+
+    #[test]
+    fn empty_snapshot() {
+        let h = StdHistogram::new();
+        let s = h.snapshot();
+
+        assert_eq!(s.count, 0);
+        assert_eq!(s.min, 0);
+        assert_eq!(s.max, 0);
+        assert_eq!(s.mean, 0.0);
+        assert_eq!(s.percentile(99.0), Some(0));
+    }
+
+    #[test]
+    fn update_after_sharing() {
+        let h = StdHistogram::with_reservoir(UniformReservoir::default());
+        let shared = h.clone();
+        thread::spawn(move || for i in 1..101 {
+                shared.update(i);
+            })
+            .join()
+            .unwrap();
+
+        let s = h.snapshot();
+        assert_eq!(s.count, 100);
+        assert_eq!(s.min, 1);
+        assert_eq!(s.max, 100);
+        assert_eq!(s.mean, 50.5);
+        assert!((s.stddev - 28.866).abs() < 0.001);
+        assert_eq!(s.percentile(50.0), Some(51));
+        assert_eq!(s.percentile(99.0), Some(100));
+    }
+
+    #[test]
+    fn configured_percentiles() {
+        let h = StdHistogram::with_reservoir_and_percentiles(UniformReservoir::default(),
+                                                             vec![10.0, 90.0]);
+        for i in 1..11 {
+            h.update(i);
+        }
+
+        let s = h.snapshot();
+        assert_eq!(s.percentiles, vec![(10.0, 2), (90.0, 10)]);
+        assert_eq!(s.percentile(50.0), None);
+
+        h.clear();
+        assert_eq!(h.snapshot().count, 0);
+    }
+
+    #[test]
+    fn count_includes_values_no_longer_sampled() {
+        let h = StdHistogram::with_reservoir(SlidingWindowReservoir::new(10));
+        for i in 0..100 {
+            h.update(i);
+        }
+
+        let s = h.snapshot();
+        assert_eq!(s.count, 100);
+        assert_eq!(s.min, 90);
+        assert_eq!(s.max, 99);
+    }
+
+    #[test]
+    fn weighted_percentiles() {
+        let samples = vec![WeightedSample { value: 1, weight: 1.0 },
+                           WeightedSample { value: 2, weight: 2.0 },
+                           WeightedSample { value: 3, weight: 1.0 }];
+        let s = HistogramSnapshot::from_samples(4, samples, &[0.0, 25.0, 50.0, 75.0, 100.0]);
+
+        assert_eq!(s.percentiles,
+                   vec![(0.0, 1), (25.0, 2), (50.0, 2), (75.0, 3), (100.0, 3)]);
+        assert_eq!(s.mean, 2.0);
+        assert!((s.stddev - 0.5f64.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn sum_and_buckets_are_counted() {
+        let h = StdHistogram::with_buckets(vec![100.0, 0.0, 4.5, 9.0]);
+        for i in 0..20 {
+            h.update(i % 10);
+        }
+
+        let s = h.snapshot();
+        assert_eq!(s.sum, 90);
+        assert_eq!(s.buckets, vec![(0.0, 2), (4.5, 10), (9.0, 20), (100.0, 20)]);
+        assert!(StdHistogram::new().snapshot().buckets.is_empty());
+
+        h.clear();
+        let s = h.snapshot();
+        assert_eq!(s.sum, 0);
+        assert_eq!(s.buckets, vec![(0.0, 0), (4.5, 0), (9.0, 0), (100.0, 0)]);
+    }
+
+    #[test]
+    fn from_histogram() {
+        let h = StdHistogram::from_histogram(histogram::Histogram::new());
+        h.update(7);
+
+        let s = h.snapshot();
+        assert_eq!(s.count, 1);
+        assert_eq!(s.percentile(50.0), Some(7));
+    }
+}
@@ -10,19 +10,48 @@ use std::sync::Arc;
 
 mod counter;
 mod gauge;
+mod histogram;
 mod meter;
+mod timer;
+pub mod reservoir;
 
 pub use self::counter::{Counter, CounterSnapshot, StdCounter};
 pub use self::gauge::{Gauge, GaugeSnapshot, StdGauge};
+pub use self::histogram::{Histogram, HistogramSnapshot, StdHistogram, DEFAULT_PERCENTILES};
 pub use self::meter::{Meter, MeterSnapshot, StdMeter};
+pub use self::timer::{StdTimer, Timer, TimerContext, TimerExt, TimerSnapshot};
 
 /// a Metric
-use histogram::Histogram;
-
 #[allow(missing_docs)]
+#[derive(Clone)]
 pub enum Metric {
-    Counter(Arc<Counter>),
-    Gauge(Arc<Gauge>),
-    Meter(Arc<Meter>),
-    Histogram(Histogram),
+    Counter(Arc<dyn Counter>),
+    Gauge(Arc<dyn Gauge>),
+    Meter(Arc<dyn Meter>),
+    Histogram(Arc<dyn Histogram>),
+    Timer(Arc<dyn Timer>),
+}
+
+/// A snapshot of any kind of `Metric`.
+#[allow(missing_docs)]
+#[derive(Debug)]
+pub enum MetricSnapshot {
+    Counter(CounterSnapshot),
+    Gauge(GaugeSnapshot),
+    Meter(MeterSnapshot),
+    Histogram(HistogramSnapshot),
+    Timer(TimerSnapshot),
+}
+
+impl Metric {
+    /// Take a snapshot of the metric, whatever its kind.
+    pub fn snapshot(&self) -> MetricSnapshot {
+        match *self {
+            Metric::Counter(ref x) => MetricSnapshot::Counter(x.snapshot()),
+            Metric::Gauge(ref x) => MetricSnapshot::Gauge(x.snapshot()),
+            Metric::Meter(ref x) => MetricSnapshot::Meter(x.snapshot()),
+            Metric::Histogram(ref x) => MetricSnapshot::Histogram(x.snapshot()),
+            Metric::Timer(ref x) => MetricSnapshot::Timer(x.snapshot()),
+        }
+    }
 }
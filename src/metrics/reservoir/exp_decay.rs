@@ -0,0 +1,245 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use metrics::reservoir::{Reservoir, WeightedSample, DEFAULT_SIZE};
+use rand::{thread_rng, Rng};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::mem;
+use std::time::{Duration, Instant};
+
+/// The default alpha, which heavily biases the reservoir to the past 5 minutes.
+pub const DEFAULT_ALPHA: f64 = 0.015;
+const RESCALE_THRESHOLD_SECS: u64 = 60 * 60;
+
+/// A `Reservoir` biased towards recent values using Cormode et al's forward
+/// decay priority sampling, as used by Dropwizard metrics.
+///
+/// <http://dimacs.rutgers.edu/~graham/pubs/papers/fwddecay.pdf>
+#[derive(Debug)]
+pub struct ExpDecayReservoir {
+    size: usize,
+    alpha: f64,
+    start: Instant,
+    next_rescale: Instant,
+    samples: BinaryHeap<PrioritizedSample>,
+}
+
+#[derive(Debug)]
+struct PrioritizedSample {
+    priority: f64,
+    sample: WeightedSample,
+}
+
+// `BinaryHeap` is a max-heap, so order by reversed priority to keep the
+// sample with the lowest priority on top, ready to be evicted.
+impl Ord for PrioritizedSample {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.partial_cmp(&self.priority).unwrap_or(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for PrioritizedSample {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for PrioritizedSample {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for PrioritizedSample {}
+
+impl ExpDecayReservoir {
+    /// Create a new `ExpDecayReservoir` keeping up to `size` samples, with
+    /// the given exponential decay factor; the higher `alpha`, the more
+    /// biased the reservoir is towards newer values.
+    pub fn new(size: usize, alpha: f64) -> Self {
+        ExpDecayReservoir::starting_at(size, alpha, Instant::now())
+    }
+
+    fn starting_at(size: usize, alpha: f64, now: Instant) -> Self {
+        ExpDecayReservoir {
+            size,
+            alpha,
+            start: now,
+            next_rescale: now + Duration::from_secs(RESCALE_THRESHOLD_SECS),
+            samples: BinaryHeap::with_capacity(size + 1),
+        }
+    }
+
+    // Weights only change once per second, so values recorded within the
+    // same second weigh exactly the same.
+    fn weight(&self, now: Instant) -> f64 {
+        (self.alpha * now.duration_since(self.start).as_secs() as f64).exp()
+    }
+
+    fn update_at(&mut self, value: u64, now: Instant) {
+        self.rescale_if_needed(now);
+
+        let weight = self.weight(now);
+        // Draw from (0, 1] so the priority stays finite.
+        let priority = weight / (1.0 - thread_rng().gen::<f64>());
+        let sample = PrioritizedSample {
+            priority,
+            sample: WeightedSample { value, weight },
+        };
+
+        if self.samples.len() < self.size {
+            self.samples.push(sample);
+        } else if let Some(lowest) = self.samples.peek().map(|s| s.priority) {
+            if lowest < priority {
+                self.samples.pop();
+                self.samples.push(sample);
+            }
+        }
+    }
+
+    // Landmarks have to be moved forward regularly, otherwise the weights
+    // of new samples eventually overflow.
+    fn rescale_if_needed(&mut self, now: Instant) {
+        if now < self.next_rescale {
+            return;
+        }
+        let old_start = self.start;
+        self.start = now;
+        self.next_rescale = now + Duration::from_secs(RESCALE_THRESHOLD_SECS);
+
+        let factor = (-self.alpha * now.duration_since(old_start).as_secs() as f64).exp();
+        let samples = mem::take(&mut self.samples);
+        self.samples = samples.into_iter()
+            .map(|mut s| {
+                s.priority *= factor;
+                s.sample.weight *= factor;
+                s
+            })
+            .filter(|s| s.sample.weight != 0.0)
+            .collect();
+    }
+}
+
+impl Default for ExpDecayReservoir {
+    fn default() -> Self {
+        ExpDecayReservoir::new(DEFAULT_SIZE, DEFAULT_ALPHA)
+    }
+}
+
+impl Reservoir for ExpDecayReservoir {
+    fn update(&mut self, value: u64) {
+        self.update_at(value, Instant::now());
+    }
+
+    fn samples(&self) -> Vec<WeightedSample> {
+        self.samples.iter().map(|s| s.sample).collect()
+    }
+
+    fn clear(&mut self) {
+        let now = Instant::now();
+        self.samples.clear();
+        self.start = now;
+        self.next_rescale = now + Duration::from_secs(RESCALE_THRESHOLD_SECS);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use metrics::HistogramSnapshot;
+
+    fn median(r: &ExpDecayReservoir) -> u64 {
+        let s = HistogramSnapshot::from_samples(0, r.samples(), &[50.0]);
+        s.percentile(50.0).unwrap()
+    }
+
+    #[test]
+    fn a_reservoir_of_100_out_of_1000_elements() {
+        let mut r = ExpDecayReservoir::new(100, 0.99);
+        for i in 0..1000 {
+            r.update(i);
+        }
+
+        let samples = r.samples();
+        assert_eq!(samples.len(), 100);
+        assert!(samples.iter().all(|s| s.value < 1000));
+    }
+
+    #[test]
+    fn a_reservoir_of_100_out_of_10_elements() {
+        let mut r = ExpDecayReservoir::new(100, 0.99);
+        for i in 0..10 {
+            r.update(i);
+        }
+
+        assert_eq!(r.samples().len(), 10);
+    }
+
+    #[test]
+    fn spot_lift() {
+        // 10 values per second of 177 for 2 minutes, then 9999 for a minute:
+        // the recent values dominate the median although they are fewer.
+        let start = Instant::now();
+        let mut r = ExpDecayReservoir::starting_at(1000, DEFAULT_ALPHA, start);
+        let mut now = start;
+        for _ in 0..(120 * 10) {
+            r.update_at(177, now);
+            now += Duration::from_millis(100);
+        }
+        for _ in 0..(60 * 10) {
+            r.update_at(9999, now);
+            now += Duration::from_millis(100);
+        }
+
+        assert_eq!(median(&r), 9999);
+    }
+
+    #[test]
+    fn spot_fall() {
+        let start = Instant::now();
+        let mut r = ExpDecayReservoir::starting_at(1000, DEFAULT_ALPHA, start);
+        let mut now = start;
+        for _ in 0..(120 * 10) {
+            r.update_at(9998, now);
+            now += Duration::from_millis(100);
+        }
+        for _ in 0..(60 * 10) {
+            r.update_at(178, now);
+            now += Duration::from_millis(100);
+        }
+
+        assert_eq!(median(&r), 178);
+    }
+
+    #[test]
+    fn long_periods_of_inactivity_should_not_corrupt_sampling_state() {
+        let start = Instant::now();
+        let mut r = ExpDecayReservoir::starting_at(10, 0.015, start);
+
+        // Fill the reservoir, then go quiet for 15 hours, well past the
+        // rescale threshold, before recording new values.
+        let mut now = start;
+        for _ in 0..1000 {
+            r.update_at(1000, now);
+            now += Duration::from_millis(100);
+        }
+        now += Duration::from_secs(15 * 60 * 60);
+        r.update_at(2000, now);
+
+        let samples = r.samples();
+        assert!(samples.len() <= 10);
+        assert!(samples.iter().all(|s| s.weight.is_finite() && s.weight > 0.0));
+        assert!(samples.iter().any(|s| s.value == 2000));
+
+        // The new value dominates completely once the old ones decayed.
+        for _ in 0..1000 {
+            r.update_at(3000, now);
+            now += Duration::from_millis(100);
+        }
+        assert_eq!(median(&r), 3000);
+    }
+}
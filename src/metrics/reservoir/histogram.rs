@@ -0,0 +1,64 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use histogram;
+use metrics::reservoir::{Reservoir, WeightedSample};
+
+/// A `Reservoir` counting every value ever recorded into the buckets of a
+/// `histogram::Histogram`, trading precision for bounded memory.
+#[derive(Debug, Default)]
+pub struct HistogramReservoir {
+    data: histogram::Histogram,
+}
+
+impl HistogramReservoir {
+    /// Create a new `HistogramReservoir` recording into an already
+    /// configured `histogram::Histogram`.
+    pub fn new(data: histogram::Histogram) -> Self {
+        HistogramReservoir { data }
+    }
+}
+
+impl Reservoir for HistogramReservoir {
+    fn update(&mut self, value: u64) {
+        // Values above the configured maximum cannot be stored, so they are dropped.
+        let _ = self.data.increment(value);
+    }
+
+    fn samples(&self) -> Vec<WeightedSample> {
+        self.data
+            .into_iter()
+            .filter(|b| b.count() > 0)
+            .map(|b| {
+                WeightedSample {
+                    value: b.value(),
+                    weight: b.count() as f64,
+                }
+            })
+            .collect()
+    }
+
+    fn clear(&mut self) {
+        self.data.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn buckets_are_weighted_by_count() {
+        let mut r = HistogramReservoir::default();
+        r.update(3);
+        r.update(3);
+        r.update(5);
+
+        assert_eq!(r.samples(),
+                   vec![WeightedSample { value: 3, weight: 2.0 },
+                        WeightedSample { value: 5, weight: 1.0 }]);
+    }
+}
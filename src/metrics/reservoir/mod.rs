@@ -0,0 +1,46 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Reservoirs decide which of the values recorded by a `Histogram` are kept
+//! around to compute its snapshot.
+
+use std::fmt::Debug;
+
+mod exp_decay;
+mod histogram;
+mod sliding_time_window;
+mod sliding_window;
+mod uniform;
+
+pub use self::exp_decay::{ExpDecayReservoir, DEFAULT_ALPHA};
+pub use self::histogram::HistogramReservoir;
+pub use self::sliding_time_window::{SlidingTimeWindowReservoir, DEFAULT_MAX_SIZE};
+pub use self::sliding_window::SlidingWindowReservoir;
+pub use self::uniform::UniformReservoir;
+
+/// The number of samples kept by default, which offers a 99.9% confidence
+/// level with a 5% margin of error assuming a normal distribution.
+pub const DEFAULT_SIZE: usize = 1028;
+
+/// A value kept by a `Reservoir` along with its relative weight.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WeightedSample {
+    /// The recorded value.
+    pub value: u64,
+    /// How much the value counts towards the statistics, relative to the other samples.
+    pub weight: f64,
+}
+
+/// `Reservoir` is a statistically representative sample of the values
+/// recorded by a `Histogram`.
+pub trait Reservoir: Debug + Send {
+    /// Record a value.
+    fn update(&mut self, value: u64);
+    /// Return the samples currently kept, in no particular order.
+    fn samples(&self) -> Vec<WeightedSample>;
+    /// Drop all samples.
+    fn clear(&mut self);
+}
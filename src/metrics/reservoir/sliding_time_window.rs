@@ -0,0 +1,132 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use metrics::reservoir::{Reservoir, WeightedSample};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// The most values a `SlidingTimeWindowReservoir` keeps by default.
+pub const DEFAULT_MAX_SIZE: usize = 1 << 16;
+
+/// A `Reservoir` keeping the values recorded within the last `window`.
+///
+/// At most `max_size` values are kept, past that the oldest ones are dropped
+/// even if they are still within the window, so that memory stays bounded
+/// however fast values are recorded.
+#[derive(Debug)]
+pub struct SlidingTimeWindowReservoir {
+    window: Duration,
+    max_size: usize,
+    values: VecDeque<(Instant, u64)>,
+}
+
+impl SlidingTimeWindowReservoir {
+    /// Create a new `SlidingTimeWindowReservoir` keeping the values recorded
+    /// within the given `window`, up to `DEFAULT_MAX_SIZE` of them.
+    pub fn new(window: Duration) -> Self {
+        SlidingTimeWindowReservoir::with_max_size(window, DEFAULT_MAX_SIZE)
+    }
+
+    /// Create a new `SlidingTimeWindowReservoir` keeping the values recorded
+    /// within the given `window`, up to `max_size` of them.
+    ///
+    /// # Panics
+    ///
+    /// If `max_size` is `0`.
+    pub fn with_max_size(window: Duration, max_size: usize) -> Self {
+        assert!(max_size > 0, "a SlidingTimeWindowReservoir must keep at least one value");
+        SlidingTimeWindowReservoir {
+            window,
+            max_size,
+            values: VecDeque::new(),
+        }
+    }
+
+    fn update_at(&mut self, value: u64, now: Instant) {
+        self.trim(now);
+        if self.values.len() == self.max_size {
+            self.values.pop_front();
+        }
+        self.values.push_back((now, value));
+    }
+
+    fn samples_at(&self, now: Instant) -> Vec<WeightedSample> {
+        self.values
+            .iter()
+            .filter(|&&(recorded, _)| now.duration_since(recorded) < self.window)
+            .map(|&(_, value)| WeightedSample { value, weight: 1.0 })
+            .collect()
+    }
+
+    fn trim(&mut self, now: Instant) {
+        while let Some(&(recorded, _)) = self.values.front() {
+            if now.duration_since(recorded) < self.window {
+                break;
+            }
+            self.values.pop_front();
+        }
+    }
+}
+
+impl Reservoir for SlidingTimeWindowReservoir {
+    fn update(&mut self, value: u64) {
+        self.update_at(value, Instant::now());
+    }
+
+    fn samples(&self) -> Vec<WeightedSample> {
+        self.samples_at(Instant::now())
+    }
+
+    fn clear(&mut self) {
+        self.values.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn values_at(r: &SlidingTimeWindowReservoir, now: Instant) -> Vec<u64> {
+        r.samples_at(now).iter().map(|s| s.value).collect()
+    }
+
+    #[test]
+    fn stores_measurements_with_duplicate_ticks() {
+        let now = Instant::now();
+        let mut r = SlidingTimeWindowReservoir::new(Duration::from_secs(10));
+        r.update_at(1, now);
+        r.update_at(2, now);
+
+        assert_eq!(values_at(&r, now), vec![1, 2]);
+    }
+
+    #[test]
+    fn bounds_measurements_to_a_time_window() {
+        let start = Instant::now();
+        let mut r = SlidingTimeWindowReservoir::new(Duration::from_secs(10));
+        for i in 0..20 {
+            r.update_at(i, start + Duration::from_secs(i));
+        }
+
+        let now = start + Duration::from_secs(19);
+        assert_eq!(values_at(&r, now), (10..20).collect::<Vec<u64>>());
+        assert_eq!(r.values.len(), 10);
+
+        let later = start + Duration::from_secs(25);
+        assert_eq!(values_at(&r, later), (16..20).collect::<Vec<u64>>());
+    }
+
+    #[test]
+    fn bounds_measurements_to_the_max_size() {
+        let now = Instant::now();
+        let mut r = SlidingTimeWindowReservoir::with_max_size(Duration::from_secs(10), 3);
+        for i in 0..5 {
+            r.update_at(i, now);
+        }
+
+        assert_eq!(values_at(&r, now), vec![2, 3, 4]);
+    }
+}
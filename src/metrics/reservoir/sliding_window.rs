@@ -0,0 +1,90 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use metrics::reservoir::{Reservoir, WeightedSample, DEFAULT_SIZE};
+use std::collections::VecDeque;
+
+/// A `Reservoir` keeping the last `size` recorded values.
+#[derive(Debug)]
+pub struct SlidingWindowReservoir {
+    size: usize,
+    values: VecDeque<u64>,
+}
+
+impl SlidingWindowReservoir {
+    /// Create a new `SlidingWindowReservoir` keeping the last `size` values.
+    ///
+    /// # Panics
+    ///
+    /// If `size` is `0`, since such a window could never hold a value.
+    pub fn new(size: usize) -> Self {
+        assert!(size > 0, "a SlidingWindowReservoir must keep at least one value");
+        SlidingWindowReservoir {
+            size,
+            values: VecDeque::with_capacity(size),
+        }
+    }
+}
+
+impl Default for SlidingWindowReservoir {
+    fn default() -> Self {
+        SlidingWindowReservoir::new(DEFAULT_SIZE)
+    }
+}
+
+impl Reservoir for SlidingWindowReservoir {
+    fn update(&mut self, value: u64) {
+        if self.values.len() == self.size {
+            self.values.pop_front();
+        }
+        self.values.push_back(value);
+    }
+
+    fn samples(&self) -> Vec<WeightedSample> {
+        self.values
+            .iter()
+            .map(|&value| WeightedSample { value, weight: 1.0 })
+            .collect()
+    }
+
+    fn clear(&mut self) {
+        self.values.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn values(r: &SlidingWindowReservoir) -> Vec<u64> {
+        r.samples().iter().map(|s| s.value).collect()
+    }
+
+    #[test]
+    fn handles_small_data_streams() {
+        let mut r = SlidingWindowReservoir::new(3);
+        r.update(1);
+        r.update(2);
+
+        assert_eq!(values(&r), vec![1, 2]);
+    }
+
+    #[test]
+    fn only_keeps_the_most_recent_from_big_data_streams() {
+        let mut r = SlidingWindowReservoir::new(3);
+        for i in 1..5 {
+            r.update(i);
+        }
+
+        assert_eq!(values(&r), vec![2, 3, 4]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_an_empty_window() {
+        SlidingWindowReservoir::new(0);
+    }
+}
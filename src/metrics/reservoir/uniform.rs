@@ -0,0 +1,115 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use metrics::reservoir::{Reservoir, WeightedSample, DEFAULT_SIZE};
+use rand::{thread_rng, Rng};
+
+/// A `Reservoir` keeping a uniform random sample of every value ever
+/// recorded, using Vitter's Algorithm R.
+#[derive(Debug)]
+pub struct UniformReservoir {
+    size: usize,
+    count: u64,
+    values: Vec<u64>,
+}
+
+impl UniformReservoir {
+    /// Create a new `UniformReservoir` keeping up to `size` samples.
+    pub fn new(size: usize) -> Self {
+        UniformReservoir {
+            size,
+            count: 0,
+            values: Vec::with_capacity(size),
+        }
+    }
+}
+
+impl Default for UniformReservoir {
+    fn default() -> Self {
+        UniformReservoir::new(DEFAULT_SIZE)
+    }
+}
+
+impl Reservoir for UniformReservoir {
+    fn update(&mut self, value: u64) {
+        self.count += 1;
+        if self.values.len() < self.size {
+            self.values.push(value);
+        } else {
+            let r = thread_rng().gen_range(0, self.count);
+            if r < self.size as u64 {
+                self.values[r as usize] = value;
+            }
+        }
+    }
+
+    fn samples(&self) -> Vec<WeightedSample> {
+        self.values
+            .iter()
+            .map(|&value| WeightedSample { value, weight: 1.0 })
+            .collect()
+    }
+
+    fn clear(&mut self) {
+        self.count = 0;
+        self.values.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn keeps_everything_below_size() {
+        let mut r = UniformReservoir::new(100);
+        for i in 0..10 {
+            r.update(i);
+        }
+
+        let mut values: Vec<u64> = r.samples().iter().map(|s| s.value).collect();
+        values.sort();
+        assert_eq!(values, (0..10).collect::<Vec<u64>>());
+    }
+
+    #[test]
+    fn a_reservoir_of_100_out_of_1000_elements() {
+        let mut r = UniformReservoir::new(100);
+        for i in 0..1000 {
+            r.update(i);
+        }
+
+        let samples = r.samples();
+        assert_eq!(samples.len(), 100);
+        assert!(samples.iter().all(|s| s.value < 1000));
+    }
+
+    #[test]
+    fn samples_are_uniformly_distributed() {
+        // Every value has the same chance of being kept, so the mean of the
+        // kept values tends to the mean of all values.
+        let trials = 200;
+        let mut total = 0.0;
+        for _ in 0..trials {
+            let mut r = UniformReservoir::new(100);
+            for i in 0..1000 {
+                r.update(i);
+            }
+            let samples = r.samples();
+            total += samples.iter().map(|s| s.value as f64).sum::<f64>() / samples.len() as f64;
+        }
+        let mean = total / trials as f64;
+        assert!((mean - 499.5).abs() < 15.0, "mean was {}", mean);
+    }
+
+    #[test]
+    fn clear() {
+        let mut r = UniformReservoir::new(10);
+        r.update(1);
+        r.clear();
+        assert!(r.samples().is_empty());
+    }
+}
@@ -0,0 +1,165 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use metrics::{Histogram, HistogramSnapshot, Meter, MeterSnapshot, StdHistogram, StdMeter,
+              DEFAULT_PERCENTILES};
+use metrics::reservoir::Reservoir;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+/// Naive implementation of a `Timer`, combining a `StdMeter` for the rate of
+/// events with a `StdHistogram` of their durations.
+#[derive(Debug, Default)]
+pub struct StdTimer {
+    /// The rate at which durations are recorded.
+    meter: StdMeter,
+    /// The distribution of the recorded durations, in nanoseconds.
+    histogram: StdHistogram,
+}
+
+/// A snapshot of the rates and durations recorded by a `Timer`.
+#[derive(Debug)]
+pub struct TimerSnapshot {
+    /// The count and rates of timed events.
+    pub meter: MeterSnapshot,
+    /// The distribution of the durations, in nanoseconds.
+    pub histogram: HistogramSnapshot,
+}
+
+/// `Timer` is a `Metric` that measures both the rate at which a piece of code
+/// is called and the distribution of its duration.
+pub trait Timer: Send + Sync {
+    /// Record a duration.
+    fn update(&self, duration: Duration);
+    /// Take a snapshot of the rates and durations for use with a `Reporter`.
+    fn snapshot(&self) -> TimerSnapshot;
+}
+
+/// Timing helpers for every `Timer`, including the `Arc<dyn Timer>` of
+/// `Metric::Timer` and `MetricRegistry::timer`.
+pub trait TimerExt: Timer {
+    /// Run the closure and record how long it took.
+    fn time<F, T>(&self, f: F) -> T
+        where F: FnOnce() -> T
+    {
+        let _context = self.start();
+        f()
+    }
+
+    /// Start timing; the duration is recorded when the returned context is dropped.
+    fn start(&self) -> TimerContext<'_, Self> {
+        TimerContext {
+            timer: self,
+            start: Instant::now(),
+        }
+    }
+}
+
+impl<T: Timer + ?Sized> TimerExt for T {}
+
+/// A running measurement started by `TimerExt::start`, recorded when dropped.
+pub struct TimerContext<'a, T: Timer + ?Sized + 'a> {
+    timer: &'a T,
+    start: Instant,
+}
+
+impl<'a, T: Timer + ?Sized> TimerContext<'a, T> {
+    /// Record the elapsed time now rather than when the context goes out of scope.
+    pub fn stop(self) {}
+}
+
+impl<'a, T: Timer + ?Sized> Drop for TimerContext<'a, T> {
+    fn drop(&mut self) {
+        self.timer.update(self.start.elapsed());
+    }
+}
+
+impl Timer for StdTimer {
+    fn update(&self, duration: Duration) {
+        let nanos = duration.as_secs() * NANOS_PER_SEC + duration.subsec_nanos() as u64;
+        self.meter.mark(1);
+        self.histogram.update(nanos);
+    }
+
+    fn snapshot(&self) -> TimerSnapshot {
+        TimerSnapshot {
+            meter: self.meter.snapshot(),
+            histogram: self.histogram.snapshot(),
+        }
+    }
+}
+
+impl StdTimer {
+    /// Create a new `StdTimer`.
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// Create a new `StdTimer` sampling durations into the given reservoir.
+    pub fn with_reservoir<R: Reservoir + 'static>(reservoir: R) -> Arc<Self> {
+        Arc::new(StdTimer {
+            meter: StdMeter::default(),
+            histogram: StdHistogram::from_reservoir(Box::new(reservoir),
+                                                    DEFAULT_PERCENTILES.to_vec()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn update() {
+        let t = StdTimer::new();
+        t.update(Duration::from_millis(10));
+        t.update(Duration::from_millis(20));
+
+        let s = t.snapshot();
+        assert_eq!(s.meter.count, 2);
+        assert_eq!(s.histogram.count, 2);
+        assert!(s.histogram.min >= 9_000_000 && s.histogram.min <= 11_000_000);
+        assert!(s.histogram.max >= 19_000_000 && s.histogram.max <= 21_000_000);
+    }
+
+    #[test]
+    fn time_closure() {
+        let t = StdTimer::new();
+        let value = t.time(|| {
+            thread::sleep(Duration::from_millis(5));
+            42
+        });
+
+        let s = t.snapshot();
+        assert_eq!(value, 42);
+        assert_eq!(s.meter.count, 1);
+        assert!(s.histogram.min >= 5_000_000);
+    }
+
+    #[test]
+    fn context_records_on_drop() {
+        let t = StdTimer::new();
+        {
+            let _context = t.start();
+            assert_eq!(t.snapshot().meter.count, 0);
+        }
+        assert_eq!(t.snapshot().meter.count, 1);
+
+        t.start().stop();
+        assert_eq!(t.snapshot().meter.count, 2);
+    }
+
+    #[test]
+    fn trait_objects() {
+        let t: Arc<dyn Timer> = StdTimer::new();
+        assert_eq!(t.time(|| 42), 42);
+        t.start().stop();
+        assert_eq!(t.snapshot().meter.count, 2);
+    }
+}
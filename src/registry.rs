@@ -0,0 +1,201 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A registry of named metrics, shared by any number of reporters.
+
+use metrics::{Counter, Gauge, Histogram, Meter, Metric, MetricSnapshot, StdCounter, StdGauge,
+              StdHistogram, StdMeter, StdTimer, Timer};
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+
+/// `MetricRegistry` owns a set of uniquely named metrics independently of
+/// the reporters they are sent to.
+///
+/// Attach it to reporters with `Reporter::attach`; metrics added to or
+/// removed from the registry afterwards are picked up by every reporter.
+#[derive(Default)]
+pub struct MetricRegistry {
+    metrics: RwLock<BTreeMap<String, Metric>>,
+}
+
+impl MetricRegistry {
+    /// Create a new, empty `MetricRegistry`.
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// Return the `Counter` registered under `name`, registering a new
+    /// `StdCounter` if there is none.
+    pub fn counter(&self, name: &str) -> Result<Arc<dyn Counter>, String> {
+        match self.get_or_insert(name, || Metric::Counter(StdCounter::new())) {
+            Metric::Counter(x) => Ok(x),
+            _ => Err(format!("{} is already registered as another kind of metric", name)),
+        }
+    }
+
+    /// Return the `Gauge` registered under `name`, registering a new
+    /// `StdGauge` if there is none.
+    pub fn gauge(&self, name: &str) -> Result<Arc<dyn Gauge>, String> {
+        match self.get_or_insert(name, || Metric::Gauge(StdGauge::new())) {
+            Metric::Gauge(x) => Ok(x),
+            _ => Err(format!("{} is already registered as another kind of metric", name)),
+        }
+    }
+
+    /// Return the `Meter` registered under `name`, registering a new
+    /// `StdMeter` if there is none.
+    pub fn meter(&self, name: &str) -> Result<Arc<dyn Meter>, String> {
+        match self.get_or_insert(name, || Metric::Meter(StdMeter::new())) {
+            Metric::Meter(x) => Ok(x),
+            _ => Err(format!("{} is already registered as another kind of metric", name)),
+        }
+    }
+
+    /// Return the `Histogram` registered under `name`, registering a new
+    /// `StdHistogram` if there is none.
+    pub fn histogram(&self, name: &str) -> Result<Arc<dyn Histogram>, String> {
+        match self.get_or_insert(name, || Metric::Histogram(StdHistogram::new())) {
+            Metric::Histogram(x) => Ok(x),
+            _ => Err(format!("{} is already registered as another kind of metric", name)),
+        }
+    }
+
+    /// Return the `Timer` registered under `name`, registering a new
+    /// `StdTimer` if there is none.
+    pub fn timer(&self, name: &str) -> Result<Arc<dyn Timer>, String> {
+        match self.get_or_insert(name, || Metric::Timer(StdTimer::new())) {
+            Metric::Timer(x) => Ok(x),
+            _ => Err(format!("{} is already registered as another kind of metric", name)),
+        }
+    }
+
+    /// Register an existing metric under `name`, failing if the name is taken.
+    pub fn register<S: Into<String>>(&self, name: S, metric: Metric) -> Result<(), String> {
+        let name = name.into();
+        let mut metrics = self.metrics.write().unwrap();
+        if metrics.contains_key(&name) {
+            return Err(format!("{} is already registered", name));
+        }
+        metrics.insert(name, metric);
+        Ok(())
+    }
+
+    /// Remove the metric registered under `name`, returning it if there was one.
+    pub fn remove(&self, name: &str) -> Option<Metric> {
+        self.metrics.write().unwrap().remove(name)
+    }
+
+    /// Return the registered names, in order.
+    pub fn names(&self) -> Vec<String> {
+        self.metrics.read().unwrap().keys().cloned().collect()
+    }
+
+    /// Return the registered metrics along with their names, in order.
+    pub fn metrics(&self) -> Vec<(String, Metric)> {
+        self.metrics
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(name, metric)| (name.clone(), metric.clone()))
+            .collect()
+    }
+
+    /// Take a snapshot of every registered metric, in order. No metric can be
+    /// added or removed while the snapshot is taken.
+    pub fn snapshot(&self) -> Vec<(String, MetricSnapshot)> {
+        self.metrics
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(name, metric)| (name.clone(), metric.snapshot()))
+            .collect()
+    }
+
+    fn get_or_insert<F: FnOnce() -> Metric>(&self, name: &str, create: F) -> Metric {
+        if let Some(metric) = self.metrics.read().unwrap().get(name) {
+            return metric.clone();
+        }
+        self.metrics
+            .write()
+            .unwrap()
+            .entry(name.to_string())
+            .or_insert_with(create)
+            .clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use metrics::TimerExt;
+
+    #[test]
+    fn get_or_create() {
+        let registry = MetricRegistry::new();
+        registry.counter("requests").unwrap().inc();
+        registry.counter("requests").unwrap().add(2);
+
+        assert_eq!(registry.counter("requests").unwrap().snapshot().value, 3);
+        assert_eq!(registry.names(), vec!["requests".to_string()]);
+    }
+
+    #[test]
+    fn kinds_do_not_collide() {
+        let registry = MetricRegistry::new();
+        registry.gauge("connections").unwrap().set(3);
+
+        assert!(registry.counter("connections").is_err());
+        assert!(registry.meter("connections").is_err());
+        assert!(registry.histogram("connections").is_err());
+        assert!(registry.timer("connections").is_err());
+        assert!(registry.register("connections", Metric::Counter(StdCounter::new())).is_err());
+    }
+
+    #[test]
+    fn register_and_remove() {
+        let registry = MetricRegistry::new();
+        let m = StdMeter::new();
+        registry.register("meter", Metric::Meter(m.clone())).unwrap();
+        m.mark(5);
+
+        assert_eq!(registry.meter("meter").unwrap().count(), 5);
+        assert!(registry.remove("meter").is_some());
+        assert!(registry.remove("meter").is_none());
+        assert!(registry.names().is_empty());
+    }
+
+    #[test]
+    fn time_with_a_registered_timer() {
+        let registry = MetricRegistry::new();
+        assert_eq!(registry.timer("x").unwrap().time(|| 42), 42);
+        registry.timer("x").unwrap().start().stop();
+        assert_eq!(registry.timer("x").unwrap().snapshot().meter.count, 2);
+    }
+
+    #[test]
+    fn snapshot_every_metric() {
+        let registry = MetricRegistry::new();
+        registry.counter("b").unwrap().inc();
+        registry.histogram("a").unwrap().update(7);
+
+        let snapshot = registry.snapshot();
+        assert_eq!(snapshot.len(), 2);
+        match snapshot[0] {
+            (ref name, MetricSnapshot::Histogram(ref s)) => {
+                assert_eq!(name, "a");
+                assert_eq!(s.max, 7);
+            }
+            _ => panic!("expected the histogram first"),
+        }
+        match snapshot[1] {
+            (ref name, MetricSnapshot::Counter(ref s)) => {
+                assert_eq!(name, "b");
+                assert_eq!(s.value, 1);
+            }
+            _ => panic!("expected the counter last"),
+        }
+    }
+}
@@ -1,231 +1,429 @@
-/// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
 // http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
 // <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-// CarbonReporter sends a message to a carbon end point at a regular basis.
-use std::thread;
-use reporter::{Reporter, ReporterMsg};
-use metrics::{CounterSnapshot, GaugeSnapshot, MeterSnapshot, Metric};
-use histogram::Histogram;
-use time;
+// CarbonReporter sends a message to a carbon end point at a regular basis, in the plaintext or
+// the pickle protocol.
+// refer to https://graphite.readthedocs.io/en/latest/feeding-carbon.html
+use reporter::{pack_lines, ScheduledBackend, ScheduledReporter, Snapshot};
+use metrics::MetricSnapshot;
 use time::Timespec;
 use std::io::Write;
-use std::io::Error;
-use std::sync::mpsc;
-use std::net::TcpStream;
-use std::collections::HashMap;
-
-struct CarbonMetricEntry {
-    metric_name: String,
-    metric: Metric,
+use std::io::{Error, ErrorKind};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::net::{TcpStream, ToSocketAddrs, UdpSocket};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+use rand;
+
+/// How the labels given to `addl` are written into the metric paths.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CarbonLabels {
+    /// As the tags of a Graphite 1.1 tagged series, `name;k1=v1;k2=v2`.
+    Tags,
+    /// As path segments, `name.k1.v1.k2.v2`, for Carbon before 1.1.
+    PathSegments,
 }
 
+/// How the datapoints are written to Carbon.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CarbonProtocol {
+    /// One plaintext line written at a time, usually to port 2003.
+    Plaintext,
+    /// The plaintext lines of a whole tick, written at once.
+    BatchedPlaintext,
+    /// The datapoints of a tick as length prefixed pickled lists, usually to
+    /// port 2004.
+    Pickle,
+}
+
+/// How the datapoints get to Carbon.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CarbonTransport {
+    /// A TCP connection, made again when it breaks.
+    Tcp,
+    /// UDP datagrams of at most `max_datagram` bytes, as many plaintext lines
+    /// as fit into each. The pickle protocol is not taken over UDP, so the
+    /// lines of a tick are always written as plaintext.
+    Udp { max_datagram: usize },
+}
+
+impl CarbonTransport {
+    /// Write to a UDP listener, in datagrams that fit an ethernet frame.
+    pub fn udp() -> Self {
+        CarbonTransport::Udp { max_datagram: 1400 }
+    }
+}
+
+// Carbon relays send at most this many datapoints per pickle too
+const MAX_DATAPOINTS_PER_PICKLE: usize = 500;
+
+// Carbon overwrites a datapoint with the same path and timestamp, so replaying some that were
+// partly written before a connection broke does no harm
 struct CarbonStream {
     graphite_stream: Option<TcpStream>,
+    udp_socket: Option<UdpSocket>,
     host_and_port: String,
+    protocol: CarbonProtocol,
+    transport: CarbonTransport,
+    // The datapoints not yet written, the oldest are dropped when it is full
+    unsent: VecDeque<(String, String, i64)>,
+    buffer_capacity: usize,
+    dropped: Arc<AtomicUsize>,
+    connect_timeout: Duration,
+    write_timeout: Duration,
+    backoff: Backoff,
+}
+
+// Exponential backoff between attempts to connect, with jitter so that many reporters do not
+// reconnect to a restarted Carbon at once
+struct Backoff {
+    initial: Duration,
+    max: Duration,
+    failures: u32,
+    next_attempt: Option<Instant>,
 }
 
 // TODO perhaps we autodiscover the host and port
 //
 pub struct CarbonReporter {
-    metrics: mpsc::Sender<Result<ReporterMsg, &'static str>>,
+    reporter: ScheduledReporter,
+    dropped: Arc<AtomicUsize>,
+}
+
+// Writes the datapoints of every report, and whatever could not be written before
+struct CarbonBackend {
+    prefix: String,
+    labels: CarbonLabels,
+    carbon: CarbonStream,
+}
+
+/// Configures a `CarbonReporter` before it starts reporting.
+pub struct CarbonReporterBuilder {
     reporter_name: String,
-    join_handle: thread::JoinHandle<Result<(), String>>,
+    host_and_port: String,
+    prefix: String,
+    delay_ms: u64,
+    protocol: CarbonProtocol,
+    transport: CarbonTransport,
+    labels: CarbonLabels,
+    buffer_capacity: usize,
+    connect_timeout_ms: u64,
+    write_timeout_ms: u64,
+    backoff_ms: (u64, u64),
+}
+
+impl Backoff {
+    fn new(initial: Duration, max: Duration) -> Self {
+        Backoff {
+            initial,
+            max,
+            failures: 0,
+            next_attempt: None,
+        }
+    }
+
+    fn ready(&self) -> bool {
+        self.next_attempt.is_none_or(|next_attempt| Instant::now() >= next_attempt)
+    }
+
+    fn failed(&mut self) {
+        let delay = self.initial
+            .checked_mul(1 << self.failures.min(16))
+            .map_or(self.max, |delay| delay.min(self.max));
+        self.failures += 1;
+        // Anywhere from half the delay to all of it
+        let jitter = delay.mul_f64(rand::random::<f64>() / 2.0);
+        self.next_attempt = Some(Instant::now() + delay - jitter);
+    }
+
+    fn succeeded(&mut self) {
+        self.failures = 0;
+        self.next_attempt = None;
+    }
 }
 
 impl CarbonStream {
-    pub fn new<S: Into<String>>(host_and_port: S) -> Self {
+    pub fn new<S: Into<String>>(host_and_port: S, protocol: CarbonProtocol) -> Self {
         CarbonStream {
             host_and_port: host_and_port.into(),
             graphite_stream: None,
+            udp_socket: None,
+            protocol,
+            transport: CarbonTransport::Tcp,
+            unsent: VecDeque::new(),
+            buffer_capacity: 10_000,
+            dropped: Arc::new(AtomicUsize::new(0)),
+            connect_timeout: Duration::from_secs(5),
+            write_timeout: Duration::from_secs(5),
+            backoff: Backoff::new(Duration::from_millis(100), Duration::from_secs(60)),
         }
     }
 
     pub fn connect(&mut self) -> Result<(), Error> {
-        let graphite_stream = try!(TcpStream::connect(&(*self.host_and_port)));
-        self.graphite_stream = Some(graphite_stream);
-        Ok(())
+        let mut last_error = Error::new(ErrorKind::InvalidInput, "no address to connect to");
+        for address in self.host_and_port.to_socket_addrs()? {
+            match TcpStream::connect_timeout(&address, self.connect_timeout) {
+                Ok(graphite_stream) => {
+                    graphite_stream.set_write_timeout(Some(self.write_timeout))?;
+                    self.graphite_stream = Some(graphite_stream);
+                    return Ok(());
+                }
+                Err(e) => last_error = e,
+            }
+        }
+        Err(last_error)
     }
 
-    pub fn write<S: Into<String>>(&mut self,
-                                  metric_path: S,
-                                  value: S,
-                                  timespec: Timespec)
-                                  -> Result<(), Error> {
-        if self.graphite_stream.is_none() {
-            try!(self.connect());
+    // Queue a datapoint. Plaintext over TCP writes it straight away, but if that fails it stays
+    // queued with the others until the next flush.
+    pub fn write<S: Into<String>>(&mut self, metric_path: S, value: S, timespec: Timespec) {
+        if self.unsent.len() >= self.buffer_capacity {
+            self.unsent.pop_front();
+            self.dropped.fetch_add(1, Ordering::Relaxed);
         }
-        if let Some(ref mut stream) = self.graphite_stream {
-            let carbon_command =
-                format!("{} {} {}\n", metric_path.into(), value.into(), timespec.sec).into_bytes();
-            try!(stream.write_all(&carbon_command));
+        self.unsent.push_back((metric_path.into(), value.into(), timespec.sec));
+        if self.protocol == CarbonProtocol::Plaintext && self.transport == CarbonTransport::Tcp {
+            let _ = self.flush();
         }
-        Ok(())
     }
-}
 
-impl Reporter for CarbonReporter {
-    fn get_unique_reporter_name(&self) -> &str {
-        &self.reporter_name
-    }
-    fn stop(self) -> Result<thread::JoinHandle<Result<(), String>>, String> {
-        match self.metrics.send(Err("stop")) {
-            Ok(_) => Ok(self.join_handle),
-            Err(x) => Err(format!("Unable to stop reporter {}", x)),
+    // Write the unsent datapoints. They are kept if that fails, and the connection is made again
+    // once the backoff is over.
+    pub fn flush(&mut self) -> Result<(), Error> {
+        if self.unsent.is_empty() {
+            return Ok(());
+        }
+        if let CarbonTransport::Udp { max_datagram } = self.transport {
+            return self.send_datagrams(max_datagram);
+        }
+        if self.graphite_stream.is_none() {
+            if !self.backoff.ready() {
+                return Err(Error::new(ErrorKind::NotConnected, "waiting to reconnect to carbon"));
+            }
+            if let Err(e) = self.connect() {
+                self.backoff.failed();
+                return Err(e);
+            }
+        }
+        let points: Vec<_> = self.unsent.iter().cloned().collect();
+        let carbon_command = match self.protocol {
+            CarbonProtocol::Plaintext | CarbonProtocol::BatchedPlaintext => {
+                points.iter()
+                    .map(|&(ref path, ref value, ts)| format!("{} {} {}\n", path, value, ts))
+                    .collect::<String>()
+                    .into_bytes()
+            }
+            CarbonProtocol::Pickle => {
+                let mut carbon_command = Vec::new();
+                for batch in points.chunks(MAX_DATAPOINTS_PER_PICKLE) {
+                    let payload = pickle(batch);
+                    carbon_command.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+                    carbon_command.extend_from_slice(&payload);
+                }
+                carbon_command
+            }
+        };
+        let result = match self.graphite_stream {
+            Some(ref mut stream) => stream.write_all(&carbon_command),
+            None => Ok(()),
+        };
+        match result {
+            Ok(()) => {
+                self.unsent.clear();
+                self.backoff.succeeded();
+                Ok(())
+            }
+            Err(e) => {
+                self.graphite_stream = None;
+                self.backoff.failed();
+                Err(e)
+            }
         }
     }
-    fn addl<S: Into<String>>(&mut self,
-                             name: S,
-                             metric: Metric,
-                             _labels: Option<HashMap<String, String>>)
-                             -> Result<(), String> {
-        // Todo maybe do something about the labels
-        match self.metrics
-            .send(Ok(ReporterMsg::AddMetric(name.into(), metric, None))) {
-            Ok(_) => Ok(()),
-            Err(x) => Err(format!("Unable to send metric reporter{}", x)),
+
+    // Send the unsent datapoints as plaintext lines, never splitting a line across datagrams
+    fn send_datagrams(&mut self, max_datagram: usize) -> Result<(), Error> {
+        if self.udp_socket.is_none() {
+            self.udp_socket = Some(UdpSocket::bind("0.0.0.0:0")?);
+        }
+        let lines = self.unsent
+            .iter()
+            .map(|&(ref path, ref value, ts)| format!("{} {} {}", path, value, ts))
+            .collect();
+        if let Some(ref socket) = self.udp_socket {
+            for datagram in pack_lines(lines, max_datagram) {
+                socket.send_to(datagram.as_bytes(), &*self.host_and_port)?;
+            }
         }
+        self.unsent.clear();
+        Ok(())
     }
-    fn remove<S: Into<String>>(&mut self, name: S) -> Result<(), String> {
-        match self.metrics
-            .send(Ok(ReporterMsg::RemoveMetric(name.into()))) {
-            Ok(_) => Ok(()),
-            Err(x) => Err(format!("Unable to remove metric {}", x)),
+}
+
+// Pickle the datapoints as protocol 2 does [(path, (timestamp, value)), ...], which is what the
+// pickle receiver of Carbon loads
+fn pickle(points: &[(String, String, i64)]) -> Vec<u8> {
+    let mut pickled = vec![0x80, 2, b']', b'('];
+    for &(ref path, ref value, ts) in points {
+        // BINUNICODE
+        pickled.push(b'X');
+        pickled.extend_from_slice(&(path.len() as u32).to_le_bytes());
+        pickled.extend_from_slice(path.as_bytes());
+        if ts >= i64::from(i32::MIN) && ts <= i64::from(i32::MAX) {
+            // BININT
+            pickled.push(b'J');
+            pickled.extend_from_slice(&(ts as i32).to_le_bytes());
+        } else {
+            // LONG1
+            pickled.extend_from_slice(&[0x8a, 8]);
+            pickled.extend_from_slice(&ts.to_le_bytes());
         }
+        // BINFLOAT
+        pickled.push(b'G');
+        pickled.extend_from_slice(&value.parse::<f64>().unwrap_or(f64::NAN).to_be_bytes());
+        // TUPLE2 twice, for (timestamp, value) and then (path, (timestamp, value))
+        pickled.extend_from_slice(&[0x86, 0x86]);
     }
+    // APPENDS and STOP
+    pickled.extend_from_slice(b"e.");
+    pickled
 }
 
+forward_to_scheduled_reporter!(CarbonReporter);
+
 fn prefix(metric_line: String, prefix_str: &str) -> String {
     format!("{}.{}", prefix_str, metric_line)
 }
 
-fn send_meter_metric(metric_name: &str,
-                     meter: MeterSnapshot,
-                     carbon: &mut CarbonStream,
-                     prefix_string: String,
-                     ts: Timespec)
-                     -> Result<(), Error> {
-    let prefix_str = &(*prefix_string);
-
-    let count = meter.count.to_string();
-    let m1_rate = meter.rates[0].to_string();
-    let m5_rate = meter.rates[1].to_string();
-    let m15_rate = meter.rates[2].to_string();
-    let mean_rate = meter.mean.to_string();
-    try!(carbon.write(prefix(format!("{}.count", metric_name), prefix_str),
-                      count,
-                      ts));
-    try!(carbon.write(prefix(format!("{}.m1", metric_name), prefix_str),
-                      m1_rate,
-                      ts));
-    try!(carbon.write(prefix(format!("{}.m5", metric_name), prefix_str),
-                      m5_rate,
-                      ts));
-    try!(carbon.write(prefix(format!("{}.m15", metric_name), prefix_str),
-                      m15_rate,
-                      ts));
-    try!(carbon.write(prefix(format!("{}.mean", metric_name), prefix_str),
-                      mean_rate,
-                      ts));
-    Ok(())
+// The name of a percentile in paths, fields and keys, 99.9 is p999 as Dropwizard names it
+pub fn percentile_suffix(percentile: f64) -> String {
+    format!("p{}", percentile.to_string().replace('.', ""))
 }
 
-fn send_gauge_metric(metric_name: &str,
-                     gauge: GaugeSnapshot,
-                     carbon: &mut CarbonStream,
-                     prefix_string: String,
-                     ts: Timespec)
-                     -> Result<(), Error> {
-    let prefix_str = &(*prefix_string);
-    try!(carbon.write(prefix(format!("{}", metric_name), prefix_str),
-                      gauge.value.to_string(),
-                      ts));
-    Ok(())
+// The values a snapshot expands to, as suffixes of its path along with the values. Meters and
+// histograms expand to several, e.g. `.count` and `.m1`.
+pub fn expand(snapshot: &MetricSnapshot) -> Vec<(String, String)> {
+    let field = |suffix: &str, value: String| (suffix.to_string(), value);
+    match snapshot {
+        MetricSnapshot::Counter(x) => vec![field("", x.value.to_string())],
+        MetricSnapshot::Gauge(x) => vec![field("", x.value.to_string())],
+        MetricSnapshot::Meter(x) => {
+            vec![field(".count", x.count.to_string()),
+                 field(".m1", x.rates[0].to_string()),
+                 field(".m5", x.rates[1].to_string()),
+                 field(".m15", x.rates[2].to_string()),
+                 field(".mean", x.mean.to_string())]
+        }
+        MetricSnapshot::Histogram(x) => {
+            let mut fields = vec![field(".count", x.count.to_string()),
+                                  field(".max", x.max.to_string()),
+                                  field(".mean", x.mean.to_string()),
+                                  field(".min", x.min.to_string()),
+                                  field(".stddev", x.stddev.to_string())];
+            for &(percentile, value) in &x.percentiles {
+                fields.push((format!(".{}", percentile_suffix(percentile)), value.to_string()));
+            }
+            fields
+        }
+        MetricSnapshot::Timer(x) => {
+            let (meter, durations) = (&x.meter, &x.histogram);
+            // Durations are recorded in nanoseconds but reported in milliseconds, like Dropwizard
+            let to_ms = |nanos: f64| (nanos / 1_000_000.0).to_string();
+            let mut fields = vec![field(".count", meter.count.to_string()),
+                                  field(".m1", meter.rates[0].to_string()),
+                                  field(".m5", meter.rates[1].to_string()),
+                                  field(".m15", meter.rates[2].to_string()),
+                                  field(".mean_rate", meter.mean.to_string()),
+                                  field(".max", to_ms(durations.max as f64)),
+                                  field(".mean", to_ms(durations.mean)),
+                                  field(".min", to_ms(durations.min as f64)),
+                                  field(".stddev", to_ms(durations.stddev))];
+            for &(percentile, value) in &durations.percentiles {
+                fields.push((format!(".{}", percentile_suffix(percentile)), to_ms(value as f64)));
+            }
+            fields
+        }
+    }
+}
+
+// Graphite takes neither of ; ! ^ = in tag names, nor ; or a leading ~ in tag values. Spaces
+// would end the path in the plaintext protocol.
+fn sanitize_tag(tag: &str) -> String {
+    tag.chars()
+        .map(|c| match c {
+            ';' | '~' | '!' | '^' | '=' | ' ' | '\n' => '_',
+            c => c,
+        })
+        .collect()
+}
+
+// Dots would add more path segments
+fn sanitize_segment(segment: &str) -> String {
+    sanitize_tag(segment).replace('.', "_")
+}
+
+pub fn metric_path(metric_path: String,
+                   labels: &[(String, String)],
+                   style: CarbonLabels)
+                   -> String {
+    let mut metric_path = metric_path;
+    for (name, value) in labels {
+        match style {
+            CarbonLabels::Tags => {
+                metric_path.push_str(&format!(";{}={}", sanitize_tag(name), sanitize_tag(value)))
+            }
+            CarbonLabels::PathSegments => {
+                metric_path.push_str(&format!(".{}.{}",
+                                              sanitize_segment(name),
+                                              sanitize_segment(value)))
+            }
+        }
+    }
+    metric_path
 }
 
-fn send_counter_metric(metric_name: &str,
-                       counter: CounterSnapshot,
-                       carbon: &mut CarbonStream,
-                       prefix_string: String,
-                       ts: Timespec)
-                       -> Result<(), Error> {
-    let prefix_str = &(*prefix_string);
-    try!(carbon.write(prefix(format!("{}", metric_name), prefix_str),
-                      counter.value.to_string(),
-                      ts));
-    Ok(())
+// The paths and values a snapshot is written as. Labels go after a meter's or a histogram's
+// suffix as tags, before it as path segments, so that the values of a metric stay together.
+pub fn paths(metric_name: &str,
+             labels: &[(String, String)],
+             style: CarbonLabels,
+             snapshot: &MetricSnapshot,
+             prefix_str: &str)
+             -> Vec<(String, String)> {
+    expand(snapshot)
+        .into_iter()
+        .map(|(suffix, value)| {
+            let path = match style {
+                CarbonLabels::Tags => {
+                    metric_path(prefix(format!("{}{}", metric_name, suffix), prefix_str),
+                                labels,
+                                style)
+                }
+                CarbonLabels::PathSegments => {
+                    let path =
+                        metric_path(prefix(metric_name.to_string(), prefix_str), labels, style);
+                    format!("{}{}", path, suffix)
+                }
+            };
+            (path, value)
+        })
+        .collect()
 }
-fn send_histogram_metric(metric_name: &str,
-                         histogram: &Histogram,
-                         carbon: &mut CarbonStream,
-                         prefix_string: String,
-                         ts: Timespec)
-                         -> Result<(), Error> {
-    let prefix_str = &(*prefix_string);
-    let count = histogram.into_iter().count();
-    let max = histogram.percentile(100.0).unwrap();
-    let min = histogram.percentile(0.0).unwrap();
-
-    let p50 = histogram.percentile(50.0).unwrap();
-    let p75 = histogram.percentile(75.0).unwrap();
-    let p95 = histogram.percentile(95.0).unwrap();
-    let p98 = histogram.percentile(98.0).unwrap();
-    let p99 = histogram.percentile(99.0).unwrap();
-    let p999 = histogram.percentile(99.9).unwrap();
-    let p9999 = histogram.percentile(99.99).unwrap();
-    let p99999 = histogram.percentile(99.999).unwrap();
-
-    try!(carbon.write(prefix(format!("{}.count", metric_name), prefix_str),
-                      count.to_string(),
-                      ts));
-
-    try!(carbon.write(prefix(format!("{}.max", metric_name), prefix_str),
-                      max.to_string(),
-                      ts));
-
-    // carbon
-    // .write(prefix(format!("{}.mean", metric_name), prefix_str),
-    // mean.into_string(),
-    // ts);
-
-    try!(carbon.write(prefix(format!("{}.min", metric_name), prefix_str),
-                      min.to_string(),
-                      ts));
-
-    try!(carbon.write(prefix(format!("{}.p50", metric_name), prefix_str),
-                      p50.to_string(),
-                      ts));
-
-    try!(carbon.write(prefix(format!("{}.p75", metric_name), prefix_str),
-                      p75.to_string(),
-                      ts));
-
-    try!(carbon.write(prefix(format!("{}.p95", metric_name), prefix_str),
-                      p95.to_string(),
-                      ts));
-
-    try!(carbon.write(prefix(format!("{}.p98", metric_name), prefix_str),
-                      p98.to_string(),
-                      ts));
-
-    try!(carbon.write(prefix(format!("{}.p99", metric_name), prefix_str),
-                      p99.to_string(),
-                      ts));
-
-    try!(carbon.write(prefix(format!("{}.p999", metric_name), prefix_str),
-                      p999.to_string(),
-                      ts));
-
-    try!(carbon.write(prefix(format!("{}.p9999", metric_name), prefix_str),
-                      p9999.to_string(),
-                      ts));
-
-    try!(carbon.write(prefix(format!("{}.p99999", metric_name), prefix_str),
-                      p99999.to_string(),
-                      ts));
-    Ok(())
+
+fn send_metric(metric_name: &str,
+               labels: &[(String, String)],
+               style: CarbonLabels,
+               snapshot: &MetricSnapshot,
+               carbon: &mut CarbonStream,
+               prefix_str: &str,
+               ts: Timespec) {
+    for (path, value) in paths(metric_name, labels, style, snapshot, prefix_str) {
+        carbon.write(path, value, ts);
+    }
 }
 
 impl CarbonReporter {
@@ -234,109 +432,142 @@ impl CarbonReporter {
                                                                      prefix: S3,
                                                                      aggregation_timer: u64)
                                                                      -> Self {
-        let (tx, rx) = mpsc::channel();
-        let hp = host_and_port.into();
-        let pr = prefix.into();
-        let rn = reporter_name.into();
+        CarbonReporter::builder(reporter_name, host_and_port, prefix, aggregation_timer).build()
+    }
 
-        CarbonReporter {
-            metrics: tx,
-            reporter_name: rn.clone(),
-            join_handle: report_to_carbon_continuously(pr, hp, aggregation_timer, rx),
+    /// Configure a reporter, which writes one plaintext line at a time over
+    /// TCP with the labels as tags unless told otherwise.
+    pub fn builder<S1, S2, S3>(reporter_name: S1,
+                               host_and_port: S2,
+                               prefix: S3,
+                               aggregation_timer: u64)
+                               -> CarbonReporterBuilder
+        where S1: Into<String>,
+              S2: Into<String>,
+              S3: Into<String>
+    {
+        CarbonReporterBuilder {
+            reporter_name: reporter_name.into(),
+            host_and_port: host_and_port.into(),
+            prefix: prefix.into(),
+            delay_ms: aggregation_timer,
+            protocol: CarbonProtocol::Plaintext,
+            transport: CarbonTransport::Tcp,
+            labels: CarbonLabels::Tags,
+            buffer_capacity: 10_000,
+            connect_timeout_ms: 5_000,
+            write_timeout_ms: 5_000,
+            backoff_ms: (100, 60_000),
         }
     }
+
+    /// The number of datapoints dropped so far, because Carbon could not be
+    /// reached before the buffer of unsent ones was full.
+    pub fn dropped_points(&self) -> usize {
+        self.dropped.load(Ordering::Relaxed)
+    }
 }
-fn report_to_carbon_continuously(prefix: String,
-                                 host_and_port: String,
-                                 delay_ms: u64,
-                                 rx: mpsc::Receiver<Result<ReporterMsg, &'static str>>)
-                                 -> thread::JoinHandle<Result<(), String>> {
-    thread::spawn(move || {
-        let mut carbon = CarbonStream::new(host_and_port);
-        let mut stop = false;
-        let mut metrics = HashMap::new();
-
-        while !stop {
-            while let Ok(msg) = rx.try_recv() {
-                match msg {
-                    Ok(ReporterMsg::AddMetric(name, metric, _)) => {
-                        metrics.insert(name.clone(),
-                                       CarbonMetricEntry {
-                                           metric_name: name,
-                                           metric: metric,
-                                       });
-                    }
-                    Ok(ReporterMsg::RemoveMetric(name)) => {
-                        metrics.remove(&name);
-                    }
-                    Err(_) => stop = true,
-                }
-            }
-            let ts = time::get_time();
-            let delay_ms = delay_ms as i64;
-            let next_tick_ms = ((ts.sec * 1000 + ts.nsec as i64 / 1_000_000) / delay_ms + 1) *
-                               delay_ms;
-            let next_tick = Timespec {
-                sec: (next_tick_ms / 1000),
-                nsec: ((next_tick_ms % 1000) * 1_000_000) as i32,
-            };
-            thread::sleep((next_tick - ts).to_std().unwrap());
-            for (_, entry) in &metrics {
-                let metric_name = &entry.metric_name;
-                let metric = &entry.metric;
-                // Maybe one day we can do more to handle this failure
-                let result = match *metric {
-                    Metric::Meter(ref x) => {
-                        send_meter_metric(metric_name,
-                                          x.snapshot(),
-                                          &mut carbon,
-                                          prefix.clone(),
-                                          ts)
-                    }
-                    Metric::Gauge(ref x) => {
-                        send_gauge_metric(metric_name,
-                                          x.snapshot(),
-                                          &mut carbon,
-                                          prefix.clone(),
-                                          ts)
-                    }
-                    Metric::Counter(ref x) => {
-                        send_counter_metric(metric_name,
-                                            x.snapshot(),
-                                            &mut carbon,
-                                            prefix.clone(),
-                                            ts)
-                    }
-                    Metric::Histogram(ref x) => {
-                        send_histogram_metric(metric_name, &x, &mut carbon, prefix.clone(), ts)
-                    }
-                };
-                // if an error happens, just stop and wait for next loop.
-                if let Err(_) = result {
-                    break;
-                }
-            }
-        }
-        Ok(())
-    })
 
+impl CarbonReporterBuilder {
+    pub fn protocol(mut self, protocol: CarbonProtocol) -> Self {
+        self.protocol = protocol;
+        self
+    }
+
+    pub fn transport(mut self, transport: CarbonTransport) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    pub fn labels(mut self, labels: CarbonLabels) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// How many unsent datapoints are kept, to be written once Carbon can be
+    /// reached again.
+    pub fn buffer_capacity(mut self, buffer_capacity: usize) -> Self {
+        self.buffer_capacity = buffer_capacity;
+        self
+    }
+
+    pub fn connect_timeout_ms(mut self, connect_timeout_ms: u64) -> Self {
+        self.connect_timeout_ms = connect_timeout_ms;
+        self
+    }
 
+    pub fn write_timeout_ms(mut self, write_timeout_ms: u64) -> Self {
+        self.write_timeout_ms = write_timeout_ms;
+        self
+    }
+
+    /// The time to wait before reconnecting, which doubles after each failure
+    /// up to `max_ms`.
+    pub fn backoff_ms(mut self, initial_ms: u64, max_ms: u64) -> Self {
+        self.backoff_ms = (initial_ms, max_ms);
+        self
+    }
+
+    /// Start reporting.
+    pub fn build(self) -> CarbonReporter {
+        let mut carbon = CarbonStream::new(self.host_and_port, self.protocol);
+        carbon.transport = self.transport;
+        carbon.buffer_capacity = self.buffer_capacity;
+        // A zero timeout is refused by the socket
+        carbon.connect_timeout = Duration::from_millis(self.connect_timeout_ms.max(1));
+        carbon.write_timeout = Duration::from_millis(self.write_timeout_ms.max(1));
+        carbon.backoff = Backoff::new(Duration::from_millis(self.backoff_ms.0),
+                                      Duration::from_millis(self.backoff_ms.1));
+        let dropped = carbon.dropped.clone();
+        let backend = CarbonBackend {
+            prefix: self.prefix,
+            labels: self.labels,
+            carbon,
+        };
+        CarbonReporter {
+            reporter: ScheduledReporter::new(self.reporter_name, self.delay_ms, backend),
+            dropped,
+        }
+    }
 }
 
+impl ScheduledBackend for CarbonBackend {
+    fn report(&mut self, snapshot: &Snapshot, timestamp: Timespec) {
+        for metric in &snapshot.metrics {
+            send_metric(&metric.name,
+                        &metric.sorted_labels(),
+                        self.labels,
+                        &metric.snapshot,
+                        &mut self.carbon,
+                        &self.prefix,
+                        timestamp);
+        }
+        // The datapoints stay buffered when Carbon can't be reached, and are written after
+        // reconnecting
+        let _ = self.carbon.flush();
+    }
 
+    fn flush(&mut self) {
+        let _ = self.carbon.flush();
+    }
+}
 
 #[cfg(test)]
 mod test {
-    use histogram::Histogram;
-    use metrics::{Counter, Gauge, Meter, Metric, StdCounter, StdGauge, StdMeter};
-    use std::collections::HashSet;
+    use metrics::{Counter, Gauge, Histogram, Meter, Metric, MetricSnapshot, StdCounter, StdGauge,
+                  StdHistogram, StdMeter, StdTimer, Timer};
+    use std::collections::{HashMap, HashSet};
     use std::io::BufRead;
     use std::io::BufReader;
-    use std::net::TcpListener;
+    use std::net::{TcpListener, UdpSocket};
     use std::thread;
-    use std::time::Duration;
-    use super::CarbonReporter;
-    use reporter::Reporter;
+    use std::time::{Duration, Instant};
+    use std::sync::atomic::Ordering;
+    use std::io::Read;
+    use super::{metric_path, pickle, Backoff, CarbonBackend, CarbonLabels, CarbonProtocol,
+                CarbonReporter, CarbonStream, CarbonTransport};
+    use registry::MetricRegistry;
+    use reporter::{ReportedMetric, Reporter, ScheduledBackend, Snapshot};
     use time;
 
     #[test]
@@ -350,13 +581,14 @@ mod test {
         let g = StdGauge::new();
         g.set(2);
 
-        let mut h = Histogram::configure()
-            .max_value(100)
-            .precision(1)
-            .build()
-            .unwrap();
+        let h = StdHistogram::new();
+        h.update(1);
+
+        let t = StdTimer::new();
+        t.update(Duration::from_millis(3));
 
-        h.increment_by(1, 1).unwrap();
+        let registry = MetricRegistry::new();
+        registry.counter("registered").unwrap().inc();
 
         let test_host_and_port = "127.0.0.1:34254";
         let listener = TcpListener::bind(test_host_and_port).unwrap();
@@ -366,12 +598,14 @@ mod test {
         reporter.remove("meter2").unwrap();
         reporter.add("counter1", Metric::Counter(c.clone())).unwrap();
         reporter.add("gauge1", Metric::Gauge(g.clone())).unwrap();
-        reporter.add("histogram", Metric::Histogram(h)).unwrap();
+        reporter.add("histogram", Metric::Histogram(h.clone())).unwrap();
+        reporter.add("timer", Metric::Timer(t.clone())).unwrap();
+        reporter.attach(registry.clone()).unwrap();
 
         let stream = listener.incoming().next().expect("client did not show up").unwrap();
         let buffer = BufReader::new(stream);
         thread::sleep(Duration::from_secs(2));
-        reporter.stop().unwrap().join().unwrap().unwrap();
+        reporter.stop().unwrap();
 
         let lines: Vec<String> = buffer.lines().map(|l| l.unwrap()).collect();
         let now = time::get_time();
@@ -396,6 +630,239 @@ mod test {
         assert!(metrics_seen.contains("asd.asdf.counter1"));
         assert!(metrics_seen.contains("asd.asdf.meter1.count"));
         assert!(metrics_seen.contains("asd.asdf.histogram.p95"));
+        assert!(metrics_seen.contains("asd.asdf.timer.m1"));
+        assert!(metrics_seen.contains("asd.asdf.timer.p999"));
+        assert!(metrics_seen.contains("asd.asdf.registered"));
         assert!(!metrics_seen.contains("asd.asdf.meter2.count"));
     }
+
+    #[test]
+    fn pickled_datapoints() {
+        let points = vec![("a.b".to_string(), "1.5".to_string(), 1_500_000_000)];
+        let mut expected = vec![0x80, 2, b']', b'(', b'X', 3, 0, 0, 0, b'a', b'.', b'b', b'J'];
+        expected.extend_from_slice(&1_500_000_000i32.to_le_bytes());
+        expected.push(b'G');
+        expected.extend_from_slice(&1.5f64.to_be_bytes());
+        expected.extend_from_slice(&[0x86, 0x86, b'e', b'.']);
+        assert_eq!(pickle(&points), expected);
+    }
+
+    #[test]
+    fn pickle_protocol() {
+        let c = StdCounter::new();
+        c.add(2);
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let mut reporter = CarbonReporter::builder("pickle", address, "app", 100)
+            .protocol(CarbonProtocol::Pickle)
+            .build();
+        reporter.add("counter", Metric::Counter(c)).unwrap();
+
+        let mut stream = listener.incoming().next().unwrap().unwrap();
+        let mut length = [0; 4];
+        stream.read_exact(&mut length).unwrap();
+        let mut payload = vec![0; u32::from_be_bytes(length) as usize];
+        stream.read_exact(&mut payload).unwrap();
+        reporter.stop().unwrap();
+
+        assert_eq!(&payload[..9], &[0x80, 2, b']', b'(', b'X', 11, 0, 0, 0]);
+        assert_eq!(&payload[9..20], b"app.counter");
+        assert_eq!(&payload[payload.len() - 13..payload.len() - 4],
+                   &[&[b'G'][..], &2.0f64.to_be_bytes()[..]].concat()[..]);
+    }
+
+    #[test]
+    fn batched_plaintext() {
+        let g = StdGauge::new();
+        g.set(7);
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let mut reporter = CarbonReporter::builder("batched", address, "app", 100)
+            .protocol(CarbonProtocol::BatchedPlaintext)
+            .build();
+        reporter.add("gauge", Metric::Gauge(g)).unwrap();
+
+        let stream = listener.incoming().next().unwrap().unwrap();
+        let line = BufReader::new(stream).lines().next().unwrap().unwrap();
+        reporter.stop().unwrap();
+        assert!(line.starts_with("app.gauge 7 "));
+    }
+
+    #[test]
+    fn labels_as_tags_or_path_segments() {
+        let labels = vec![("a;b".to_string(), "~x".to_string()),
+                          ("host".to_string(), "web.1".to_string())];
+        assert_eq!(metric_path("app.hits".to_string(), &labels, CarbonLabels::Tags),
+                   "app.hits;a_b=_x;host=web.1");
+        assert_eq!(metric_path("app.hits".to_string(), &labels, CarbonLabels::PathSegments),
+                   "app.hits.a_b._x.host.web_1");
+    }
+
+    #[test]
+    fn labelled_series() {
+        let (eu, us) = (StdCounter::new(), StdCounter::new());
+        us.inc();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let mut reporter = CarbonReporter::builder("tags", address, "app", 100)
+            .protocol(CarbonProtocol::BatchedPlaintext)
+            .build();
+        let region = |region: &str| {
+            Some([("region".to_string(), region.to_string())].iter().cloned().collect())
+        };
+        reporter.addl("meter", Metric::Meter(StdMeter::new()), region("eu")).unwrap();
+        reporter.addl("jobs", Metric::Counter(eu), region("eu")).unwrap();
+        reporter.addl("jobs", Metric::Counter(us), region("us")).unwrap();
+
+        let stream = listener.incoming().next().unwrap().unwrap();
+        let mut lines: Vec<String> = BufReader::new(stream)
+            .lines()
+            .take(7)
+            .map(|line| line.unwrap().split(' ').take(2).collect::<Vec<_>>().join(" "))
+            .collect();
+        reporter.stop().unwrap();
+        lines.sort();
+        assert_eq!(&lines[..3],
+                   &["app.jobs;region=eu 0",
+                     "app.jobs;region=us 1",
+                     "app.meter.count;region=eu 0"]);
+    }
+
+    fn unbound_address() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.local_addr().unwrap().to_string()
+    }
+
+    #[test]
+    fn backoff() {
+        let mut backoff = Backoff::new(Duration::from_millis(100), Duration::from_millis(300));
+        assert!(backoff.ready());
+        for &max_delay in &[100, 200, 300, 300] {
+            let before = Instant::now();
+            backoff.failed();
+            let delay = backoff.next_attempt.unwrap() - before;
+            assert!(delay <= Duration::from_millis(max_delay + 10), "{:?}", delay);
+            assert!(delay >= Duration::from_millis(max_delay / 2), "{:?}", delay);
+            assert!(!backoff.ready());
+        }
+        backoff.succeeded();
+        assert!(backoff.ready());
+    }
+
+    #[test]
+    fn unsent_datapoints_are_replayed() {
+        let address = unbound_address();
+        let mut carbon = CarbonStream::new(address.clone(), CarbonProtocol::BatchedPlaintext);
+        carbon.buffer_capacity = 3;
+        carbon.backoff = Backoff::new(Duration::from_millis(0), Duration::from_millis(0));
+        for ts in 1..6 {
+            carbon.write("a", "1", time::Timespec::new(ts, 0));
+        }
+        assert!(carbon.flush().is_err());
+        assert_eq!(carbon.dropped.load(Ordering::Relaxed), 2);
+
+        let listener = TcpListener::bind(&*address).unwrap();
+        carbon.flush().unwrap();
+        drop(carbon);
+        let mut written = String::new();
+        listener.accept().unwrap().0.read_to_string(&mut written).unwrap();
+        assert_eq!(written, "a 1 3\na 1 4\na 1 5\n");
+    }
+
+    #[test]
+    fn reconnect_after_the_connection_drops() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let mut carbon = CarbonStream::new(address, CarbonProtocol::Plaintext);
+        carbon.backoff = Backoff::new(Duration::from_millis(0), Duration::from_millis(0));
+        carbon.write("a", "0", time::Timespec::new(0, 0));
+        drop(listener.accept().unwrap());
+
+        // Writing to the closed connection fails sooner or later, the datapoint is kept
+        let mut ts = 1;
+        carbon.write("a", "1", time::Timespec::new(ts, 0));
+        while carbon.unsent.is_empty() {
+            assert!(ts < 100);
+            ts += 1;
+            thread::sleep(Duration::from_millis(10));
+            carbon.write("a", "1", time::Timespec::new(ts, 0));
+        }
+        carbon.flush().unwrap();
+        drop(carbon);
+        let mut written = String::new();
+        listener.accept().unwrap().0.read_to_string(&mut written).unwrap();
+        assert_eq!(written, format!("a 1 {}\n", ts));
+    }
+
+    #[test]
+    fn every_field_is_replayed() {
+        let address = unbound_address();
+        let mut carbon = CarbonStream::new(address.clone(), CarbonProtocol::Plaintext);
+        carbon.backoff = Backoff::new(Duration::from_millis(0), Duration::from_millis(0));
+        let mut backend = CarbonBackend {
+            prefix: "app".to_string(),
+            labels: CarbonLabels::Tags,
+            carbon,
+        };
+        let meter = StdMeter::new();
+        meter.mark(3);
+        let snapshot = Snapshot {
+            metrics: vec![ReportedMetric {
+                              name: "meter".to_string(),
+                              labels: HashMap::new(),
+                              snapshot: MetricSnapshot::Meter(meter.snapshot()),
+                          }],
+        };
+        backend.report(&snapshot, time::Timespec::new(7, 0));
+        assert_eq!(backend.carbon.unsent.len(), 5);
+
+        let listener = TcpListener::bind(&*address).unwrap();
+        backend.flush();
+        drop(backend);
+        let mut written = String::new();
+        listener.accept().unwrap().0.read_to_string(&mut written).unwrap();
+        let paths: Vec<&str> = written.lines()
+            .map(|line| line.split(' ').next().unwrap())
+            .collect();
+        assert_eq!(paths,
+                   vec!["app.meter.count", "app.meter.m1", "app.meter.m5", "app.meter.m15",
+                        "app.meter.mean"]);
+        assert!(written.starts_with("app.meter.count 3 7\n"));
+    }
+
+    #[test]
+    fn udp() {
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        socket.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        let address = socket.local_addr().unwrap().to_string();
+
+        let mut reporter = CarbonReporter::builder("udp", address, "app", 100)
+            .transport(CarbonTransport::Udp { max_datagram: 64 })
+            .build();
+        for name in &["jobs", "retries", "failures"] {
+            let c = StdCounter::new();
+            c.inc();
+            reporter.add(name, Metric::Counter(c)).unwrap();
+        }
+        let meter = StdMeter::new();
+        meter.mark(1);
+        reporter.add("meter", Metric::Meter(meter)).unwrap();
+
+        // A tick is 8 lines, none of which may be split across the datagrams
+        let mut lines = Vec::new();
+        let mut buf = [0; 1500];
+        while lines.len() < 8 {
+            let (len, _) = socket.recv_from(&mut buf).unwrap();
+            assert!(len <= 64);
+            let datagram = String::from_utf8_lossy(&buf[..len]).into_owned();
+            lines.extend(datagram.lines().map(|line| line.to_string()));
+        }
+        reporter.stop().unwrap();
+        for line in &lines {
+            let fields: Vec<&str> = line.split(' ').collect();
+            assert_eq!(fields.len(), 3, "{}", line);
+            assert!(fields[0].starts_with("app."));
+        }
+        assert!(lines.iter().any(|line| line.starts_with("app.meter.count 1 ")));
+    }
 }
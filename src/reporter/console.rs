@@ -4,94 +4,257 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use metrics::Metric;
-use reporter::{Reporter, ReporterMsg};
-use std::time::Duration;
-use std::thread;
-use std::sync::mpsc;
+// ConsoleReporter writes the metrics in the layout of the Dropwizard console reporter, grouped
+// by type under a banner with the time of the report.
+use metrics::{HistogramSnapshot, MeterSnapshot, MetricSnapshot};
+use reporter::{ScheduledBackend, ScheduledReporter, Snapshot};
+use std::io::{self, Write};
 use std::collections::HashMap;
+use time::{self, Timespec};
+
+// The width of the banner and of the headers
+const CONSOLE_WIDTH: usize = 80;
+
+/// The unit rates and durations are written in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TimeUnit {
+    Nanoseconds,
+    Microseconds,
+    Milliseconds,
+    Seconds,
+    Minutes,
+    Hours,
+}
+
+impl TimeUnit {
+    /// The nanoseconds in one unit.
+    pub fn nanos(self) -> f64 {
+        match self {
+            TimeUnit::Nanoseconds => 1.0,
+            TimeUnit::Microseconds => 1e3,
+            TimeUnit::Milliseconds => 1e6,
+            TimeUnit::Seconds => 1e9,
+            TimeUnit::Minutes => 60e9,
+            TimeUnit::Hours => 3600e9,
+        }
+    }
+
+    /// The lower case plural name, e.g. `seconds`.
+    pub fn name(self) -> &'static str {
+        match self {
+            TimeUnit::Nanoseconds => "nanoseconds",
+            TimeUnit::Microseconds => "microseconds",
+            TimeUnit::Milliseconds => "milliseconds",
+            TimeUnit::Seconds => "seconds",
+            TimeUnit::Minutes => "minutes",
+            TimeUnit::Hours => "hours",
+        }
+    }
+}
+
+// Formats the reports, rates are converted from events per second and durations from
+// nanoseconds
+struct ConsoleFormat {
+    rate_unit: TimeUnit,
+    duration_unit: TimeUnit,
+}
 
 pub struct ConsoleReporter {
-    metrics: mpsc::Sender<Result<ReporterMsg, &'static str>>,
+    reporter: ScheduledReporter,
+}
+
+/// Configures a `ConsoleReporter` before it starts reporting.
+pub struct ConsoleReporterBuilder {
     reporter_name: String,
-    join_handle: thread::JoinHandle<Result<(), String>>,
+    delay_ms: u64,
+    output: Box<dyn Write + Send>,
+    format: ConsoleFormat,
 }
 
-impl Reporter for ConsoleReporter {
-    fn get_unique_reporter_name(&self) -> &str {
-        &(*self.reporter_name)
+impl ConsoleFormat {
+    // name = value, with the names right aligned
+    fn row(&self, out: &mut dyn Write, name: &str, value: &str) -> io::Result<()> {
+        writeln!(out, "{:>18} = {}", name, value)
     }
-    fn stop(self) -> Result<thread::JoinHandle<Result<(), String>>, String> {
-        match self.metrics.send(Err("stop")) {
-            Ok(_) => Ok(self.join_handle),
-            Err(x) => Err(format!("Unable to stop reporter: {}", x)),
+
+    fn rates(&self, out: &mut dyn Write, meter: &MeterSnapshot) -> io::Result<()> {
+        // events/second, as the Dropwizard reporter writes them
+        let unit = self.rate_unit.name().trim_end_matches('s');
+        let rate = |per_second: f64| {
+            format!("{:.2} events/{}", per_second * self.rate_unit.nanos() / 1e9, unit)
+        };
+        self.row(out, "count", &meter.count.to_string())?;
+        self.row(out, "mean rate", &rate(meter.mean))?;
+        self.row(out, "1-minute rate", &rate(meter.rates[0]))?;
+        self.row(out, "5-minute rate", &rate(meter.rates[1]))?;
+        self.row(out, "15-minute rate", &rate(meter.rates[2]))
+    }
+
+    // The histogram's values are durations in nanoseconds if there is a unit
+    fn distribution(&self,
+                    out: &mut dyn Write,
+                    histogram: &HistogramSnapshot,
+                    unit: Option<TimeUnit>)
+                    -> io::Result<()> {
+        let value = |value: f64| match unit {
+            Some(unit) => format!("{:.2} {}", value / unit.nanos(), unit.name()),
+            None => format!("{:.2}", value),
+        };
+        match unit {
+            Some(_) => {
+                self.row(out, "min", &value(histogram.min as f64))?;
+                self.row(out, "max", &value(histogram.max as f64))?;
+            }
+            None => {
+                self.row(out, "min", &histogram.min.to_string())?;
+                self.row(out, "max", &histogram.max.to_string())?;
+            }
         }
+        self.row(out, "mean", &value(histogram.mean))?;
+        self.row(out, "stddev", &value(histogram.stddev))?;
+        for &(percentile, v) in &histogram.percentiles {
+            if percentile == 50.0 {
+                self.row(out, "median", &value(v as f64))?;
+            } else {
+                writeln!(out, "{:>17} <= {}", format!("{}%", percentile), value(v as f64))?;
+            }
+        }
+        Ok(())
     }
-    fn addl<S: Into<String>>(&mut self,
-                             name: S,
-                             metric: Metric,
-                             labels: Option<HashMap<String, String>>)
-                             -> Result<(), String> {
-        match self.metrics.send(Ok(ReporterMsg::AddMetric(name.into(), metric, labels))) {
-            Ok(_) => Ok(()),
-            Err(x) => Err(format!("Unable to send metric reporter{}", x)),
+
+    fn metric(&self, out: &mut dyn Write, snapshot: &MetricSnapshot) -> io::Result<()> {
+        match *snapshot {
+            MetricSnapshot::Gauge(ref x) => self.row(out, "value", &x.value.to_string()),
+            MetricSnapshot::Counter(ref x) => self.row(out, "count", &x.value.to_string()),
+            MetricSnapshot::Histogram(ref x) => {
+                self.row(out, "count", &x.count.to_string())?;
+                self.distribution(out, x, None)
+            }
+            MetricSnapshot::Meter(ref x) => self.rates(out, x),
+            MetricSnapshot::Timer(ref x) => {
+                self.rates(out, &x.meter)?;
+                self.distribution(out, &x.histogram, Some(self.duration_unit))
+            }
         }
     }
-    fn remove<S: Into<String>>(&mut self, name: S) -> Result<(), String> {
-        match self.metrics
-            .send(Ok(ReporterMsg::RemoveMetric(name.into()))) {
-            Ok(_) => Ok(()),
-            Err(x) => Err(format!("Unable to remove metric reporter{}", x)),
+
+    // The banner, then the metrics grouped by type and sorted by name
+    fn report(&self,
+              out: &mut dyn Write,
+              ts: Timespec,
+              metrics: &[(String, &MetricSnapshot)])
+              -> io::Result<()> {
+        let date = time::at_utc(ts)
+            .strftime("%Y-%m-%d %H:%M:%S UTC")
+            .map(|date| date.to_string())
+            .unwrap_or_default();
+        writeln!(out, "{} {}", date, "=".repeat(CONSOLE_WIDTH.saturating_sub(date.len() + 1)))?;
+        writeln!(out)?;
+        let mut metrics: Vec<&(String, &MetricSnapshot)> = metrics.iter().collect();
+        metrics.sort_by(|a, b| a.0.cmp(&b.0));
+        let group_of = |snapshot: &MetricSnapshot| match *snapshot {
+            MetricSnapshot::Gauge(_) => 0,
+            MetricSnapshot::Counter(_) => 1,
+            MetricSnapshot::Histogram(_) => 2,
+            MetricSnapshot::Meter(_) => 3,
+            MetricSnapshot::Timer(_) => 4,
+        };
+        let groups = ["Gauges", "Counters", "Histograms", "Meters", "Timers"];
+        for (group, header) in groups.iter().enumerate() {
+            let in_group: Vec<_> = metrics.iter().filter(|m| group_of(m.1) == group).collect();
+            if in_group.is_empty() {
+                continue;
+            }
+            let header = format!("-- {} ", header);
+            writeln!(out,
+                     "{}{}",
+                     header,
+                     "-".repeat(CONSOLE_WIDTH.saturating_sub(header.len())))?;
+            for metric in in_group {
+                writeln!(out, "{}", metric.0)?;
+                self.metric(out, metric.1)?;
+                writeln!(out)?;
+            }
         }
+        writeln!(out)?;
+        out.flush()
     }
 }
 
+// Labels follow the name, as name{k1=v1,k2=v2}
+pub fn display_name(name: &str, labels: &HashMap<String, String>) -> String {
+    if labels.is_empty() {
+        return name.to_string();
+    }
+    let mut labels: Vec<String> = labels.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+    labels.sort();
+    format!("{}{{{}}}", name, labels.join(","))
+}
+
+// Writes the reports out as they come
+struct ConsoleBackend {
+    format: ConsoleFormat,
+    out: Box<dyn Write + Send>,
+}
+
+impl ScheduledBackend for ConsoleBackend {
+    fn report(&mut self, snapshot: &Snapshot, timestamp: Timespec) {
+        let metrics: Vec<(String, &MetricSnapshot)> = snapshot.metrics
+            .iter()
+            .map(|metric| (display_name(&metric.name, &metric.labels), &metric.snapshot))
+            .collect();
+        // The console going away is no reason to stop
+        let _ = self.format.report(&mut *self.out, timestamp, &metrics);
+    }
+}
+
+forward_to_scheduled_reporter!(ConsoleReporter);
+
 impl ConsoleReporter {
     pub fn new<S: Into<String>>(reporter_name: S, delay_ms: u64) -> Self {
-        let (tx, rx) = mpsc::channel();
-        let txc = tx.clone();
-        ConsoleReporter {
-            metrics: tx,
+        ConsoleReporter::builder(reporter_name, delay_ms).build()
+    }
+
+    /// Configure a reporter, which writes to stdout with rates in events per
+    /// second and durations in milliseconds unless told otherwise.
+    pub fn builder<S: Into<String>>(reporter_name: S, delay_ms: u64) -> ConsoleReporterBuilder {
+        ConsoleReporterBuilder {
             reporter_name: reporter_name.into(),
-            join_handle: thread::spawn(move || {
-                for metric in &rx {
-                    match metric {
-                        Ok(ReporterMsg::AddMetric(name, metric_value, labels)) => {
-                            println!("name: {} labels: {:?}", name, labels);
-
-                            match metric_value {
-                                Metric::Meter(ref x) => {
-                                    println!("{:?}", x.snapshot());
-                                }
-                                Metric::Gauge(ref x) => {
-                                    println!("{:?}", x.snapshot());
-                                }
-                                Metric::Counter(ref x) => {
-                                    println!("{:?}", x.snapshot());
-                                }
-                                Metric::Histogram(ref x) => {
-                                    println!("histogram{:?}", x);
-                                }
-                            }
-
-                            let message_copy = ReporterMsg::AddMetric(name, metric_value, labels);
-                            if let Err(x) = txc.send(Ok(message_copy)) {
-                                format!("Unable to forward metric {}", x);
-                            }
-                        }
-                        Ok(ReporterMsg::RemoveMetric(name)) => {
-                            println!("Remove metric {}", name);
-                        }
-                        // Todo log the error somehow
-                        Err(e) => {
-                            println!("Stopping reporter because..:{}", e);
-                            break;
-                        }
-                    }
-                    thread::sleep(Duration::from_millis(delay_ms));
-                }
-                Ok(())
-            }),
+            delay_ms,
+            output: Box::new(io::stdout()),
+            format: ConsoleFormat {
+                rate_unit: TimeUnit::Seconds,
+                duration_unit: TimeUnit::Milliseconds,
+            },
+        }
+    }
+}
+
+impl ConsoleReporterBuilder {
+    /// Write to stderr, a file or anything else instead of stdout.
+    pub fn output(mut self, output: Box<dyn Write + Send>) -> Self {
+        self.output = output;
+        self
+    }
+
+    pub fn rate_unit(mut self, rate_unit: TimeUnit) -> Self {
+        self.format.rate_unit = rate_unit;
+        self
+    }
+
+    pub fn duration_unit(mut self, duration_unit: TimeUnit) -> Self {
+        self.format.duration_unit = duration_unit;
+        self
+    }
+
+    /// Start reporting.
+    pub fn build(self) -> ConsoleReporter {
+        let backend = ConsoleBackend {
+            format: self.format,
+            out: self.output,
+        };
+        ConsoleReporter {
+            reporter: ScheduledReporter::new(self.reporter_name, self.delay_ms, backend),
         }
     }
 }
@@ -99,10 +262,43 @@ impl ConsoleReporter {
 #[cfg(test)]
 mod test {
 
-    use histogram::Histogram;
-    use metrics::{Counter, Gauge, Meter, Metric, StdCounter, StdGauge, StdMeter};
-    use super::ConsoleReporter;
+    use metrics::{Counter, Gauge, Histogram, Meter, Metric, StdCounter, StdGauge, StdHistogram,
+                  StdMeter, StdTimer, Timer};
+    use std::collections::HashMap;
+    use std::io::{self, Write};
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+    use super::{display_name, ConsoleFormat, ConsoleReporter, TimeUnit};
+    use registry::MetricRegistry;
     use reporter::Reporter;
+    use time::Timespec;
+
+    // Somewhere to write to that the test can read back
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Buffer {
+        fn contents(&self) -> String {
+            String::from_utf8_lossy(&self.0.lock().unwrap()).into_owned()
+        }
+    }
+
+    fn format(rate_unit: TimeUnit, duration_unit: TimeUnit) -> ConsoleFormat {
+        ConsoleFormat {
+            rate_unit,
+            duration_unit,
+        }
+    }
 
     #[test]
     fn meter() {
@@ -115,21 +311,157 @@ mod test {
         let g = StdGauge::new();
         g.set(2);
 
-        let mut h = Histogram::configure()
-            .max_value(100)
-            .precision(1)
-            .build()
-            .unwrap();
+        let h = StdHistogram::new();
+        h.update(1);
 
-        h.increment_by(1, 1).unwrap();
+        let t = StdTimer::new();
+        t.update(Duration::from_millis(3));
 
         let mut reporter = ConsoleReporter::new("test", 1);
         reporter.add("meter", Metric::Meter(m.clone())).unwrap();
         reporter.add("clone", Metric::Counter(c.clone())).unwrap();
         reporter.add("gauge", Metric::Gauge(g.clone())).unwrap();
-        reporter.add("histo", Metric::Histogram(h)).unwrap();
+        reporter.add("histo", Metric::Histogram(h.clone())).unwrap();
+        reporter.add("timer", Metric::Timer(t.clone())).unwrap();
+
+        let registry = MetricRegistry::new();
+        registry.meter("registered").unwrap().mark(1);
+        reporter.attach(registry).unwrap();
         reporter.remove("histo").unwrap();
         g.set(4);
-        reporter.stop().unwrap().join().unwrap().unwrap();
+        reporter.stop().unwrap();
+    }
+
+    #[test]
+    fn dropwizard_layout() {
+        let c = StdCounter::new();
+        c.inc();
+        let g = StdGauge::new();
+        g.set(4);
+        let h = StdHistogram::with_percentiles(vec![50.0, 99.9]);
+        h.update(1);
+        let labels = [("region".to_string(), "eu".to_string())].iter().cloned().collect();
+        let snapshots = [Metric::Counter(c).snapshot(),
+                         Metric::Histogram(h).snapshot(),
+                         Metric::Gauge(g).snapshot()];
+        let metrics = vec![("jobs".to_string(), &snapshots[0]),
+                           (display_name("size", &labels), &snapshots[1]),
+                           ("queue".to_string(), &snapshots[2])];
+
+        let mut out = Vec::new();
+        format(TimeUnit::Seconds, TimeUnit::Milliseconds)
+            .report(&mut out, Timespec::new(1_500_000_000, 0), &metrics)
+            .unwrap();
+        let line = |start: &str, fill: &str| format!("{}{}", start, fill.repeat(80 - start.len()));
+        let (banner, gauges, counters, histograms) = (line("2017-07-14 02:40:00 UTC ", "="),
+                                                      line("-- Gauges ", "-"),
+                                                      line("-- Counters ", "-"),
+                                                      line("-- Histograms ", "-"));
+        let expected = vec![&*banner,
+                            "",
+                            &*gauges,
+                            "queue",
+                            "             value = 4",
+                            "",
+                            &*counters,
+                            "jobs",
+                            "             count = 1",
+                            "",
+                            &*histograms,
+                            "size{region=eu}",
+                            "             count = 1",
+                            "               min = 1",
+                            "               max = 1",
+                            "              mean = 1.00",
+                            "            stddev = 0.00",
+                            "            median = 1.00",
+                            "            99.9% <= 1.00",
+                            "",
+                            "",
+                            ""];
+        assert_eq!(String::from_utf8(out).unwrap(), expected.join("\n"));
+    }
+
+    #[test]
+    fn rate_and_duration_units() {
+        let t = StdTimer::new();
+        t.update(Duration::from_millis(3));
+        let snapshot = Metric::Timer(t).snapshot();
+        let metrics = vec![("latency".to_string(), &snapshot)];
+        let mut out = Vec::new();
+        format(TimeUnit::Minutes, TimeUnit::Microseconds)
+            .report(&mut out, Timespec::new(0, 0), &metrics)
+            .unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("\n-- Timers ---"));
+        assert!(out.contains("\n         mean rate = "));
+        assert!(out.contains(" events/minute\n    15-minute rate = "));
+        assert!(out.contains("\n               min = 3000.00 microseconds\n"));
+    }
+
+    #[test]
+    fn any_output() {
+        let c = StdCounter::new();
+        c.inc();
+        let buffer = Buffer::default();
+        let mut reporter = ConsoleReporter::builder("buffer", 1)
+            .output(Box::new(buffer.clone()))
+            .build();
+        reporter.addl("jobs", Metric::Counter(c), Some(HashMap::new())).unwrap();
+        thread::sleep(Duration::from_millis(50));
+        reporter.stop().unwrap();
+        assert!(buffer.contents().contains("-- Counters ----"));
+        assert!(buffer.contents().contains("\njobs\n             count = 1\n"));
+    }
+
+    // The reports written so far, without their banners
+    fn reports(buffer: &Buffer) -> Vec<String> {
+        buffer.contents()
+            .split(" UTC ")
+            .skip(1)
+            .map(|report| report.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn once_per_period() {
+        let buffer = Buffer::default();
+        let mut reporter = ConsoleReporter::builder("periodic", 100)
+            .output(Box::new(buffer.clone()))
+            .build();
+        for name in &["a", "b", "c"] {
+            reporter.add(name, Metric::Counter(StdCounter::new())).unwrap();
+        }
+        thread::sleep(Duration::from_millis(450));
+        reporter.stop().unwrap();
+        let reports = reports(&buffer);
+        // Every period, then the one under way when stopped and a last one
+        assert!(reports.len() >= 5 && reports.len() <= 8, "{}", reports.len());
+        for report in &reports[1..] {
+            assert_eq!(report.matches("count = 0").count(), 3);
+        }
+    }
+
+    #[test]
+    fn remove_at_once() {
+        let buffer = Buffer::default();
+        let mut reporter = ConsoleReporter::builder("remove", 50)
+            .output(Box::new(buffer.clone()))
+            .build();
+        reporter.add("kept", Metric::Counter(StdCounter::new())).unwrap();
+        reporter.add("removed", Metric::Counter(StdCounter::new())).unwrap();
+        // Remove it just after a report, while the next tick is a period away
+        while !reports(&buffer).iter().any(|report| report.contains("\nremoved\n")) {
+            thread::sleep(Duration::from_millis(1));
+        }
+        let written = reports(&buffer).len();
+        reporter.remove("removed").unwrap();
+        thread::sleep(Duration::from_millis(120));
+        reporter.stop().unwrap();
+        let reports = reports(&buffer);
+        assert!(reports.len() > written + 1);
+        for report in &reports[written..] {
+            assert!(report.contains("\nkept\n") && !report.contains("\nremoved\n"));
+        }
     }
 }
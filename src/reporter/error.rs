@@ -0,0 +1,50 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::error::Error;
+use std::fmt;
+
+/// The errors a `Reporter` can run into.
+#[derive(Debug, PartialEq)]
+pub enum ReporterError {
+    /// The reporter was already stopped.
+    Stopped,
+    /// The reporter's worker is gone, so it cannot take any more requests.
+    Disconnected,
+    /// The reporter's worker thread panicked.
+    Panicked,
+    /// The reporter's worker finished with an error.
+    Worker(String),
+    /// Another reporter with the same unique name is already in the set.
+    DuplicateName(String),
+    /// The label name is not valid for, or is reserved by, the reporter's backend.
+    InvalidLabel(String),
+    /// A metric of another kind is already reported under the name, or
+    /// another metric under a name the backend cannot tell apart from it.
+    ConflictingMetric(String),
+}
+
+impl fmt::Display for ReporterError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ReporterError::Stopped => write!(f, "the reporter was already stopped"),
+            ReporterError::Disconnected => write!(f, "the reporter is no longer running"),
+            ReporterError::Panicked => write!(f, "the reporter panicked"),
+            ReporterError::Worker(ref e) => write!(f, "the reporter failed: {}", e),
+            ReporterError::DuplicateName(ref name) => {
+                write!(f, "a reporter named {} already exists", name)
+            }
+            ReporterError::InvalidLabel(ref name) => {
+                write!(f, "{} is not a valid label name", name)
+            }
+            ReporterError::ConflictingMetric(ref name) => {
+                write!(f, "{} conflicts with a metric already reported", name)
+            }
+        }
+    }
+}
+
+impl Error for ReporterError {}
@@ -0,0 +1,359 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// GangliaReporter sends the metrics to gmond at a regular basis, as the gmetric metadata and
+// value packets of the Ganglia 3.1 XDR wire format, named like the Carbon paths.
+// refer to https://github.com/ganglia/monitor-core/blob/master/lib/gm_protocol.x
+use reporter::carbon::{expand, metric_path, CarbonLabels};
+use reporter::{hostname, ScheduledBackend, ScheduledReporter, Snapshot};
+use metrics::MetricSnapshot;
+use time::Timespec;
+use std::io::Error;
+use std::sync::{Arc, Mutex};
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+use std::collections::HashMap;
+
+// The gmetric message ids
+const METADATA_FULL: i32 = 128;
+const VALUE_STRING: i32 = 133;
+
+/// How a value changes between reports, for the RRDs gmond keeps.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Slope {
+    Zero = 0,
+    Positive = 1,
+    Negative = 2,
+    Both = 3,
+    Unspecified = 4,
+}
+
+/// How Ganglia groups, labels and keeps a metric.
+#[derive(Clone, Debug)]
+pub struct GangliaMetricConfig {
+    /// The group the metric is listed under in the web frontend.
+    pub group: String,
+    pub units: String,
+    pub slope: Slope,
+    /// The longest time in seconds between two values.
+    pub tmax: u32,
+    /// How long in seconds the metric is kept once it is no longer sent, `0`
+    /// keeps it forever.
+    pub dmax: u32,
+}
+
+impl Default for GangliaMetricConfig {
+    fn default() -> Self {
+        GangliaMetricConfig {
+            group: String::new(),
+            units: String::new(),
+            slope: Slope::Both,
+            tmax: 60,
+            dmax: 0,
+        }
+    }
+}
+
+pub struct GangliaReporter {
+    reporter: ScheduledReporter,
+    configs: Arc<Mutex<HashMap<String, GangliaMetricConfig>>>,
+}
+
+// Sends the packets of every report to gmond, labels are added to the names as path segments
+struct GangliaBackend {
+    hostname: String,
+    prefix: String,
+    socket: UdpSocket,
+    address: SocketAddr,
+    configs: Arc<Mutex<HashMap<String, GangliaMetricConfig>>>,
+}
+
+forward_to_scheduled_reporter!(GangliaReporter);
+
+// XDR encodes integers in 4 big endian bytes and strings as their length followed by their bytes,
+// padded to a multiple of 4
+struct Xdr(Vec<u8>);
+
+impl Xdr {
+    fn int(&mut self, value: i32) -> &mut Self {
+        self.0.extend_from_slice(&value.to_be_bytes());
+        self
+    }
+
+    fn uint(&mut self, value: u32) -> &mut Self {
+        self.0.extend_from_slice(&value.to_be_bytes());
+        self
+    }
+
+    fn string(&mut self, value: &str) -> &mut Self {
+        self.uint(value.len() as u32);
+        self.0.extend_from_slice(value.as_bytes());
+        let padding = (4 - value.len() % 4) % 4;
+        self.0.extend_from_slice(&[0; 3][..padding]);
+        self
+    }
+}
+
+// Every value is sent as a string, gmond parses it according to the type in the metadata
+fn metadata_packet(hostname: &str, name: &str, config: &GangliaMetricConfig) -> Vec<u8> {
+    let mut xdr = Xdr(Vec::new());
+    xdr.int(METADATA_FULL)
+        .string(hostname)
+        .string(name)
+        .int(0)
+        .string("double")
+        .string(name)
+        .string(&config.units)
+        .int(config.slope as i32)
+        .uint(config.tmax)
+        .uint(config.dmax);
+    if config.group.is_empty() {
+        xdr.int(0);
+    } else {
+        xdr.int(1).string("GROUP").string(&config.group);
+    }
+    xdr.0
+}
+
+fn value_packet(hostname: &str, name: &str, value: &str) -> Vec<u8> {
+    let mut xdr = Xdr(Vec::new());
+    xdr.int(VALUE_STRING)
+        .string(hostname)
+        .string(name)
+        .int(0)
+        .string("%s")
+        .string(value);
+    xdr.0
+}
+
+// The metadata goes along with every value, like gmetric does, so gmond knows about the metric
+// even after it restarted
+fn packets(hostname: &str,
+           metric_name: &str,
+           snapshot: &MetricSnapshot,
+           config: &GangliaMetricConfig)
+           -> Vec<Vec<u8>> {
+    let mut packets = Vec::new();
+    for (suffix, value) in expand(snapshot) {
+        let name = format!("{}{}", metric_name, suffix);
+        packets.push(metadata_packet(hostname, &name, config));
+        packets.push(value_packet(hostname, &name, &value));
+    }
+    packets
+}
+
+fn send(socket: &UdpSocket, address: SocketAddr, packets: Vec<Vec<u8>>) -> Result<(), Error> {
+    for packet in packets {
+        socket.send_to(&packet, address)?;
+    }
+    Ok(())
+}
+
+impl GangliaReporter {
+    /// Send to gmond at `host_and_port`, e.g. `239.2.11.71:8649` to send to
+    /// the default multicast channel.
+    pub fn new<S1: Into<String>, S2: Into<String>>(reporter_name: S1,
+                                                   host_and_port: &str,
+                                                   prefix: S2,
+                                                   delay_ms: u64)
+                                                   -> Result<Self, Error> {
+        let address = host_and_port.to_socket_addrs()?
+            .next()
+            .ok_or_else(|| Error::other(format!("{} did not resolve", host_and_port)))?;
+        let socket = match address {
+            SocketAddr::V4(_) => UdpSocket::bind("0.0.0.0:0")?,
+            SocketAddr::V6(_) => UdpSocket::bind("[::]:0")?,
+        };
+        // Like gmond, only send multicast to the local network, which IPv6 does by default
+        if address.is_ipv4() && address.ip().is_multicast() {
+            socket.set_multicast_ttl_v4(1)?;
+        }
+        let configs = Arc::new(Mutex::new(HashMap::new()));
+        let backend = GangliaBackend {
+            hostname: hostname(),
+            prefix: prefix.into(),
+            socket,
+            address,
+            configs: configs.clone(),
+        };
+        Ok(GangliaReporter {
+            reporter: ScheduledReporter::new(reporter_name, delay_ms, backend),
+            configs,
+        })
+    }
+
+    /// Set the group, units, slope, tmax and dmax of a metric, for every
+    /// value it expands to and whatever its labels. Metrics that are not
+    /// configured use the defaults.
+    pub fn configure(&mut self, name: &str, config: GangliaMetricConfig) {
+        self.configs.lock().unwrap().insert(name.to_string(), config);
+    }
+}
+
+impl ScheduledBackend for GangliaBackend {
+    fn report(&mut self, snapshot: &Snapshot, _timestamp: Timespec) {
+        let default_config = GangliaMetricConfig::default();
+        let configs = self.configs.lock().unwrap().clone();
+        let mut to_send = Vec::new();
+        for metric in &snapshot.metrics {
+            let metric_name = if self.prefix.is_empty() {
+                metric.name.clone()
+            } else {
+                format!("{}.{}", self.prefix, metric.name)
+            };
+            let metric_name =
+                metric_path(metric_name, &metric.sorted_labels(), CarbonLabels::PathSegments);
+            let config = configs.get(&metric.name).unwrap_or(&default_config);
+            to_send.extend(packets(&self.hostname, &metric_name, &metric.snapshot, config));
+        }
+        // UDP only fails locally, try again next time
+        let _ = send(&self.socket, self.address, to_send);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use metrics::{Counter, Meter, Metric, StdCounter, StdMeter};
+    use std::collections::BTreeSet;
+    use std::net::UdpSocket;
+    use std::time::Duration;
+    use super::{metadata_packet, packets, value_packet, GangliaMetricConfig, GangliaReporter,
+                Slope};
+    use reporter::Reporter;
+
+    // Read the XDR back into integers and strings
+    struct Reader<'a>(&'a [u8]);
+
+    impl<'a> Reader<'a> {
+        fn uint(&mut self) -> u32 {
+            let (int, rest) = self.0.split_at(4);
+            self.0 = rest;
+            u32::from_be_bytes([int[0], int[1], int[2], int[3]])
+        }
+
+        fn string(&mut self) -> String {
+            let len = self.uint() as usize;
+            let string = String::from_utf8(self.0[..len].to_vec()).unwrap();
+            self.0 = &self.0[len.div_ceil(4) * 4..];
+            string
+        }
+    }
+
+    #[test]
+    fn xdr_packets() {
+        let config = GangliaMetricConfig {
+            group: "web".to_string(),
+            units: "requests".to_string(),
+            slope: Slope::Positive,
+            tmax: 30,
+            dmax: 300,
+        };
+        let packet = metadata_packet("host1", "app.hits", &config);
+        let mut reader = Reader(&packet);
+        assert_eq!(reader.uint(), 128);
+        assert_eq!(reader.string(), "host1");
+        assert_eq!(reader.string(), "app.hits");
+        assert_eq!(reader.uint(), 0);
+        assert_eq!(reader.string(), "double");
+        assert_eq!(reader.string(), "app.hits");
+        assert_eq!(reader.string(), "requests");
+        assert_eq!((reader.uint(), reader.uint(), reader.uint()), (1, 30, 300));
+        assert_eq!(reader.uint(), 1);
+        assert_eq!((reader.string(), reader.string()), ("GROUP".to_string(), "web".to_string()));
+        assert!(reader.0.is_empty());
+
+        let packet = value_packet("host1", "app.hits", "12.5");
+        assert_eq!(packet.len() % 4, 0);
+        let mut reader = Reader(&packet);
+        assert_eq!(reader.uint(), 133);
+        assert_eq!((reader.string(), reader.string()),
+                   ("host1".to_string(), "app.hits".to_string()));
+        assert_eq!(reader.uint(), 0);
+        assert_eq!((reader.string(), reader.string()), ("%s".to_string(), "12.5".to_string()));
+        assert!(reader.0.is_empty());
+    }
+
+    #[test]
+    fn meters_expand_like_carbon() {
+        let m = StdMeter::new();
+        m.mark(1);
+        let names: Vec<String> = packets("h",
+                                         "app.hits",
+                                         &Metric::Meter(m).snapshot(),
+                                         &GangliaMetricConfig::default())
+            .iter()
+            .step_by(2)
+            .map(|packet| {
+                let mut reader = Reader(packet);
+                reader.uint();
+                reader.string();
+                reader.string()
+            })
+            .collect();
+        assert_eq!(names,
+                   vec!["app.hits.count", "app.hits.m1", "app.hits.m5", "app.hits.m15",
+                        "app.hits.mean"]);
+    }
+
+    #[test]
+    fn reporter() {
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        socket.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        let address = socket.local_addr().unwrap().to_string();
+
+        let c = StdCounter::new();
+        c.add(3);
+        let mut reporter = GangliaReporter::new("ganglia", &address, "app", 100).unwrap();
+        reporter.configure("jobs",
+                           GangliaMetricConfig {
+                               group: "queue".to_string(),
+                               ..GangliaMetricConfig::default()
+                           });
+        reporter.add("jobs", Metric::Counter(c)).unwrap();
+
+        let mut buf = [0; 1500];
+        let (len, _) = socket.recv_from(&mut buf).unwrap();
+        let mut metadata = Reader(&buf[..len]);
+        assert_eq!(metadata.uint(), 128);
+        metadata.string();
+        assert_eq!(metadata.string(), "app.jobs");
+        let (len, _) = socket.recv_from(&mut buf).unwrap();
+        reporter.stop().unwrap();
+        let mut value = Reader(&buf[..len]);
+        assert_eq!(value.uint(), 133);
+        value.string();
+        value.string();
+        value.uint();
+        value.string();
+        assert_eq!(value.string(), "3");
+    }
+
+    #[test]
+    fn labels_are_path_segments() {
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        socket.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        let address = socket.local_addr().unwrap().to_string();
+
+        let mut reporter = GangliaReporter::new("ganglia", &address, "", 100).unwrap();
+        for region in &["eu", "us"] {
+            let labels = [("region".to_string(), region.to_string())].iter().cloned().collect();
+            reporter.addl("jobs", Metric::Counter(StdCounter::new()), Some(labels)).unwrap();
+        }
+
+        // The first report may come before both are added
+        let mut names = BTreeSet::new();
+        let mut buf = [0; 1500];
+        while names.len() < 2 {
+            let (len, _) = socket.recv_from(&mut buf).unwrap();
+            let mut packet = Reader(&buf[..len]);
+            if packet.uint() == 128 {
+                packet.string();
+                names.insert(packet.string());
+            }
+        }
+        reporter.stop().unwrap();
+        assert_eq!(names.into_iter().collect::<Vec<_>>(), vec!["jobs.region.eu", "jobs.region.us"]);
+    }
+}
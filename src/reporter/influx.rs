@@ -0,0 +1,324 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// InfluxReporter writes the metrics to InfluxDB in the line protocol at a regular basis, one
+// measurement per metric tagged with its labels.
+// refer to https://docs.influxdata.com/influxdb/v1/write_protocols/line_protocol_reference/
+use reporter::carbon::percentile_suffix;
+use reporter::{http_post, pack_lines, ScheduledBackend, ScheduledReporter, Snapshot};
+use metrics::{HistogramSnapshot, MetricSnapshot};
+use time::Timespec;
+use std::io::Error;
+use std::net::UdpSocket;
+use std::collections::HashMap;
+
+/// Where the lines are written to.
+#[derive(Clone, Debug)]
+pub enum InfluxTransport {
+    /// A UDP listener of InfluxDB, lines are packed into datagrams of at
+    /// most `max_datagram` bytes.
+    Udp {
+        host_and_port: String,
+        max_datagram: usize,
+    },
+    /// The HTTP `/write` endpoint of a database, lines are POSTed in
+    /// batches of at most `batch_size` lines.
+    Http {
+        host_and_port: String,
+        database: String,
+        batch_size: usize,
+    },
+}
+
+impl InfluxTransport {
+    /// Write to a UDP listener, in datagrams that fit an ethernet frame.
+    pub fn udp<S: Into<String>>(host_and_port: S) -> Self {
+        InfluxTransport::Udp {
+            host_and_port: host_and_port.into(),
+            max_datagram: 1400,
+        }
+    }
+
+    /// Write to the database over HTTP, in batches of 5000 lines as
+    /// InfluxDB recommends.
+    pub fn http<S1: Into<String>, S2: Into<String>>(host_and_port: S1, database: S2) -> Self {
+        InfluxTransport::Http {
+            host_and_port: host_and_port.into(),
+            database: database.into(),
+            batch_size: 5000,
+        }
+    }
+
+    fn write(&self, socket: &UdpSocket, lines: Vec<String>) -> Result<(), Error> {
+        match *self {
+            InfluxTransport::Udp { ref host_and_port, max_datagram } => {
+                for datagram in pack_lines(lines, max_datagram) {
+                    socket.send_to(datagram.as_bytes(), &**host_and_port)?;
+                }
+            }
+            InfluxTransport::Http { ref host_and_port, ref database, batch_size } => {
+                for batch in lines.chunks(batch_size.max(1)) {
+                    let path = format!("/write?db={}&precision=ns", url_encode(database));
+                    http_post(host_and_port, &path, "text/plain", &batch.join("\n"))?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+
+pub struct InfluxReporter {
+    reporter: ScheduledReporter,
+}
+
+// Writes the lines of every report, the socket is bound on the first one
+struct InfluxBackend {
+    prefix: String,
+    transport: InfluxTransport,
+    socket: Option<UdpSocket>,
+}
+
+forward_to_scheduled_reporter!(InfluxReporter);
+
+// Measurements escape commas and spaces, tag keys and values equal signs too. A new line would
+// end the line, it becomes an escaped space.
+fn escape(s: &str, equals: bool) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        let c = if c == '\n' || c == '\r' { ' ' } else { c };
+        if c == ',' || c == ' ' || (equals && c == '=') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+// Percent-encode everything but the unreserved characters of a URL
+fn url_encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            b => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+// A field value, integers are suffixed with i and floats InfluxDB cannot store are left out
+enum Field {
+    Integer(i64),
+    Float(f64),
+}
+
+fn fields_of(snapshot: &MetricSnapshot) -> Vec<(String, Field)> {
+    let field = |key: &str, value: Field| (key.to_string(), value);
+    match snapshot {
+        MetricSnapshot::Counter(x) => vec![field("count", Field::Integer(x.value as i64))],
+        MetricSnapshot::Gauge(x) => vec![field("value", Field::Integer(x.value as i64))],
+        MetricSnapshot::Meter(x) => {
+            vec![field("count", Field::Integer(x.count)),
+                 field("m1", Field::Float(x.rates[0])),
+                 field("m5", Field::Float(x.rates[1])),
+                 field("m15", Field::Float(x.rates[2])),
+                 field("mean", Field::Float(x.mean))]
+        }
+        MetricSnapshot::Histogram(x) => distribution_fields(x, None),
+        MetricSnapshot::Timer(x) => {
+            let meter = &x.meter;
+            let mut fields = vec![field("count", Field::Integer(meter.count)),
+                                  field("m1", Field::Float(meter.rates[0])),
+                                  field("m5", Field::Float(meter.rates[1])),
+                                  field("m15", Field::Float(meter.rates[2])),
+                                  field("mean_rate", Field::Float(meter.mean))];
+            // Durations are recorded in nanoseconds but reported in milliseconds, like Carbon
+            fields.extend(distribution_fields(&x.histogram, Some(1_000_000.0))
+                .into_iter()
+                .skip(1));
+            fields
+        }
+    }
+}
+
+// The values are divided by scale if there is one, they are integers otherwise. 99.9 is
+// written as p999, like the Carbon paths.
+fn distribution_fields(histogram: &HistogramSnapshot,
+                       scale: Option<f64>)
+                       -> Vec<(String, Field)> {
+    let value = |v: u64| match scale {
+        Some(scale) => Field::Float(v as f64 / scale),
+        None => Field::Integer(v as i64),
+    };
+    let scale = scale.unwrap_or(1.0);
+    let mut fields = vec![("count".to_string(), Field::Integer(histogram.count as i64)),
+                          ("max".to_string(), value(histogram.max)),
+                          ("mean".to_string(), Field::Float(histogram.mean / scale)),
+                          ("min".to_string(), value(histogram.min)),
+                          ("stddev".to_string(), Field::Float(histogram.stddev / scale))];
+    for &(percentile, v) in &histogram.percentiles {
+        fields.push((percentile_suffix(percentile), value(v)));
+    }
+    fields
+}
+
+// measurement,tag=v,tag2=v2 field=1i,field2=0.5 timestamp_ns
+fn line(measurement: &str,
+        labels: &HashMap<String, String>,
+        snapshot: &MetricSnapshot,
+        timestamp_ns: i64)
+        -> Option<String> {
+    let fields: Vec<String> = fields_of(snapshot)
+        .into_iter()
+        .filter_map(|(key, value)| match value {
+            Field::Integer(v) => Some(format!("{}={}i", key, v)),
+            Field::Float(v) if v.is_finite() => Some(format!("{}={}", key, v)),
+            Field::Float(_) => None,
+        })
+        .collect();
+    // A point needs at least one field
+    if fields.is_empty() {
+        return None;
+    }
+    let mut tags: Vec<String> = labels.iter()
+        .filter(|&(k, v)| !k.is_empty() && !v.is_empty())
+        .map(|(k, v)| format!("{}={}", escape(k, true), escape(v, true)))
+        .collect();
+    // InfluxDB stores points faster when the tags are sorted by key
+    tags.sort();
+    let mut line = escape(measurement, false);
+    for tag in tags {
+        line.push(',');
+        line.push_str(&tag);
+    }
+    Some(format!("{} {} {}", line, fields.join(","), timestamp_ns))
+}
+
+impl InfluxReporter {
+    pub fn new<S1: Into<String>, S2: Into<String>>(reporter_name: S1,
+                                                   transport: InfluxTransport,
+                                                   prefix: S2,
+                                                   delay_ms: u64)
+                                                   -> Self {
+        let backend = InfluxBackend {
+            prefix: prefix.into(),
+            transport,
+            socket: None,
+        };
+        InfluxReporter { reporter: ScheduledReporter::new(reporter_name, delay_ms, backend) }
+    }
+}
+
+impl ScheduledBackend for InfluxBackend {
+    fn report(&mut self, snapshot: &Snapshot, timestamp: Timespec) {
+        if self.socket.is_none() {
+            self.socket = UdpSocket::bind("0.0.0.0:0").ok();
+        }
+        let timestamp_ns = timestamp.sec * 1_000_000_000 + timestamp.nsec as i64;
+        let lines: Vec<String> = snapshot.metrics
+            .iter()
+            .filter_map(|metric| {
+                let measurement = if self.prefix.is_empty() {
+                    metric.name.clone()
+                } else {
+                    format!("{}.{}", self.prefix, metric.name)
+                };
+                line(&measurement, &metric.labels, &metric.snapshot, timestamp_ns)
+            })
+            .collect();
+        // if an error happens, the points are dropped and we wait for the next loop.
+        if let Some(ref socket) = self.socket {
+            if !lines.is_empty() {
+                let _ = self.transport.write(socket, lines);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use metrics::{Counter, Histogram, Meter, Metric, StdCounter, StdHistogram, StdMeter,
+                  StdTimer, Timer};
+    use std::collections::HashMap;
+    use std::net::UdpSocket;
+    use std::time::Duration;
+    use super::{line, url_encode, InfluxReporter, InfluxTransport};
+    use reporter::{http_stand_in, Reporter};
+
+    fn labels(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|&(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn line_protocol() {
+        let c = StdCounter::new();
+        c.add(3);
+        let tags = labels(&[("region", "us west"), ("host", "a=1,b")]);
+        assert_eq!(line("http requests", &tags, &Metric::Counter(c.clone()).snapshot(), 42)
+                       .unwrap(),
+                   "http\\ requests,host=a\\=1\\,b,region=us\\ west count=3i 42");
+        let tags = labels(&[("tag", "a\nb")]);
+        assert_eq!(line("jobs\r\n", &tags, &Metric::Counter(c).snapshot(), 42).unwrap(),
+                   "jobs\\ \\ ,tag=a\\ b count=3i 42");
+        assert_eq!(url_encode("my db&x=1"), "my%20db%26x%3D1");
+
+        let m = StdMeter::new();
+        m.mark(2);
+        let meter = line("hits", &HashMap::new(), &Metric::Meter(m).snapshot(), 1).unwrap();
+        assert!(meter.starts_with("hits count=2i,m1="));
+        assert!(meter.contains(",m5=") && meter.contains(",m15=") && meter.contains(",mean="));
+
+        let h = StdHistogram::new();
+        for i in 1..11 {
+            h.update(i);
+        }
+        let histogram = line("size", &HashMap::new(), &Metric::Histogram(h).snapshot(), 1)
+            .unwrap();
+        assert!(histogram.starts_with("size count=10i,max=10i,mean=5.5,min=1i,stddev="));
+        assert!(histogram.contains(",p50=6i,") && histogram.contains(",p999=10i,"));
+
+        let t = StdTimer::new();
+        t.update(Duration::from_millis(3));
+        let timer = line("latency", &HashMap::new(), &Metric::Timer(t).snapshot(), 1).unwrap();
+        assert!(timer.contains(",mean_rate=") && timer.contains(",max=3,mean=3,min=3,"));
+    }
+
+    #[test]
+    fn udp() {
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        socket.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        let address = socket.local_addr().unwrap().to_string();
+
+        let c = StdCounter::new();
+        c.inc();
+        let mut reporter = InfluxReporter::new("influx", InfluxTransport::udp(address), "app", 100);
+        reporter.addl("jobs", Metric::Counter(c), Some(labels(&[("queue", "high")]))).unwrap();
+
+        let mut buf = [0; 1500];
+        let (len, _) = socket.recv_from(&mut buf).unwrap();
+        reporter.stop().unwrap();
+        let datagram = String::from_utf8_lossy(&buf[..len]).into_owned();
+        let tokens: Vec<&str> = datagram.split(' ').collect();
+        assert_eq!(&tokens[..2], &["app.jobs,queue=high", "count=1i"]);
+        // Nanoseconds since the epoch
+        assert_eq!(tokens[2].len(), 19);
+    }
+
+    #[test]
+    fn http() {
+        let (address, requests) = http_stand_in();
+        let c = StdCounter::new();
+        let mut reporter =
+            InfluxReporter::new("influx", InfluxTransport::http(address, "metrics"), "", 100);
+        reporter.add("jobs", Metric::Counter(c)).unwrap();
+
+        let (request_line, body) = requests.recv().unwrap();
+        reporter.stop().unwrap();
+        assert_eq!(request_line, "POST /write?db=metrics&precision=ns HTTP/1.1");
+        assert!(String::from_utf8(body).unwrap().starts_with("jobs count=0i "));
+    }
+}
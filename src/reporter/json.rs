@@ -0,0 +1,475 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// JsonReporter writes the metrics in the JSON of the Dropwizard MetricsServlet to a file every
+// period, and serves the latest report over HTTP to whoever asks for it.
+// refer to https://metrics.dropwizard.io/4.0.0/manual/servlets.html
+use reporter::carbon::percentile_suffix;
+use reporter::console::display_name;
+use reporter::{json_string, ScheduledBackend, ScheduledReporter, Snapshot, TimeUnit};
+use metrics::{CounterSnapshot, GaugeSnapshot, HistogramSnapshot, MeterSnapshot, MetricSnapshot,
+              TimerSnapshot};
+use time::Timespec;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{BufRead, BufReader, Error, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// Writes snapshots in the JSON of the Dropwizard `MetricsServlet`, with
+/// rates and durations in the given units.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct JsonFormat {
+    pub rate_unit: TimeUnit,
+    pub duration_unit: TimeUnit,
+}
+
+impl Default for JsonFormat {
+    fn default() -> Self {
+        JsonFormat {
+            rate_unit: TimeUnit::Seconds,
+            duration_unit: TimeUnit::Seconds,
+        }
+    }
+}
+
+// Jackson writes doubles like 1.0, and JSON has no NaN or infinity
+fn number(value: f64) -> String {
+    if value.is_finite() {
+        format!("{:?}", value)
+    } else {
+        "null".to_string()
+    }
+}
+
+// The fields of a JSON object, in order
+fn object(fields: &[(String, String)]) -> String {
+    let fields: Vec<String> =
+        fields.iter().map(|(k, v)| format!("{}:{}", json_string(k), v)).collect();
+    format!("{{{}}}", fields.join(","))
+}
+
+impl JsonFormat {
+    fn rate_unit(&self) -> &'static str {
+        self.rate_unit.name().trim_end_matches('s')
+    }
+
+    // Rates come in events per second
+    fn rate(&self, per_second: f64) -> String {
+        number(per_second * self.rate_unit.nanos() / 1e9)
+    }
+
+    fn rates(&self, meter: &MeterSnapshot) -> Vec<(String, String)> {
+        vec![("m15_rate".to_string(), self.rate(meter.rates[2])),
+             ("m1_rate".to_string(), self.rate(meter.rates[0])),
+             ("m5_rate".to_string(), self.rate(meter.rates[1])),
+             ("mean_rate".to_string(), self.rate(meter.mean))]
+    }
+
+    // The values of a timer's histogram are durations in nanoseconds, converted to the duration
+    // unit like Dropwizard does, which makes them all doubles
+    fn distribution(&self, histogram: &HistogramSnapshot, timer: bool) -> Vec<(String, String)> {
+        let value = |v: f64| if timer {
+            number(v / self.duration_unit.nanos())
+        } else {
+            number(v)
+        };
+        let (min, max) = if timer {
+            (value(histogram.min as f64), value(histogram.max as f64))
+        } else {
+            (histogram.min.to_string(), histogram.max.to_string())
+        };
+        let mut fields = vec![("count".to_string(), histogram.count.to_string()),
+                              ("max".to_string(), max),
+                              ("mean".to_string(), value(histogram.mean)),
+                              ("min".to_string(), min)];
+        fields.extend(histogram.percentiles
+            .iter()
+            .map(|&(percentile, v)| (percentile_suffix(percentile), value(v as f64))));
+        fields.push(("stddev".to_string(), value(histogram.stddev)));
+        fields
+    }
+
+    pub fn gauge(&self, gauge: &GaugeSnapshot) -> String {
+        object(&[("value".to_string(), gauge.value.to_string())])
+    }
+
+    pub fn counter(&self, counter: &CounterSnapshot) -> String {
+        object(&[("count".to_string(), counter.value.to_string())])
+    }
+
+    pub fn histogram(&self, histogram: &HistogramSnapshot) -> String {
+        object(&self.distribution(histogram, false))
+    }
+
+    pub fn meter(&self, meter: &MeterSnapshot) -> String {
+        let mut fields = vec![("count".to_string(), meter.count.to_string())];
+        fields.extend(self.rates(meter));
+        fields.push(("units".to_string(), json_string(&format!("events/{}", self.rate_unit()))));
+        object(&fields)
+    }
+
+    pub fn timer(&self, timer: &TimerSnapshot) -> String {
+        let mut fields = self.distribution(&timer.histogram, true);
+        fields.extend(self.rates(&timer.meter));
+        fields.push(("duration_units".to_string(), json_string(self.duration_unit.name())));
+        let rate_units = format!("calls/{}", self.rate_unit());
+        fields.push(("rate_units".to_string(), json_string(&rate_units)));
+        object(&fields)
+    }
+
+    /// The whole document, the metrics grouped by type and sorted by name.
+    /// Labels are part of the name, as `name{k1=v1,k2=v2}`.
+    pub fn snapshot(&self, snapshot: &Snapshot) -> String {
+        let mut groups: [BTreeMap<String, String>; 5] = Default::default();
+        for metric in &snapshot.metrics {
+            let (group, json) = match metric.snapshot {
+                MetricSnapshot::Gauge(ref x) => (0, self.gauge(x)),
+                MetricSnapshot::Counter(ref x) => (1, self.counter(x)),
+                MetricSnapshot::Histogram(ref x) => (2, self.histogram(x)),
+                MetricSnapshot::Meter(ref x) => (3, self.meter(x)),
+                MetricSnapshot::Timer(ref x) => (4, self.timer(x)),
+            };
+            groups[group].insert(display_name(&metric.name, &metric.labels), json);
+        }
+        let mut fields = vec![("version".to_string(), json_string("4.0.0"))];
+        for (name, group) in ["gauges", "counters", "histograms", "meters", "timers"]
+            .iter()
+            .zip(groups.iter()) {
+            let group: Vec<(String, String)> =
+                group.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+            fields.push((name.to_string(), object(&group)));
+        }
+        object(&fields)
+    }
+}
+
+pub struct JsonReporter {
+    reporter: ScheduledReporter,
+    http_address: Option<SocketAddr>,
+}
+
+/// Configures a `JsonReporter` before it starts reporting.
+pub struct JsonReporterBuilder {
+    reporter_name: String,
+    delay_ms: u64,
+    format: JsonFormat,
+    file: Option<PathBuf>,
+    http: Option<String>,
+}
+
+// Keeps the latest report, and writes it to the file if there is one
+struct JsonBackend {
+    format: JsonFormat,
+    file: Option<PathBuf>,
+    latest: Arc<Mutex<String>>,
+    server: Option<JsonServer>,
+}
+
+// Answers every GET with the latest report, until asked to stop
+struct JsonServer {
+    address: SocketAddr,
+    stop: Arc<AtomicBool>,
+    join_handle: Option<JoinHandle<()>>,
+}
+
+forward_to_scheduled_reporter!(JsonReporter);
+
+impl JsonReporter {
+    /// Write the metrics to `file` every `delay_ms`.
+    pub fn new<S: Into<String>, P: Into<PathBuf>>(reporter_name: S,
+                                                  file: P,
+                                                  delay_ms: u64)
+                                                  -> Result<Self, Error> {
+        JsonReporter::builder(reporter_name, delay_ms).file(file).build()
+    }
+
+    /// Configure a reporter writing nowhere, until told where.
+    pub fn builder<S: Into<String>>(reporter_name: S, delay_ms: u64) -> JsonReporterBuilder {
+        JsonReporterBuilder {
+            reporter_name: reporter_name.into(),
+            delay_ms,
+            format: JsonFormat::default(),
+            file: None,
+            http: None,
+        }
+    }
+
+    /// The address the HTTP endpoint listens on, if there is one.
+    pub fn http_address(&self) -> Option<SocketAddr> {
+        self.http_address
+    }
+}
+
+impl JsonReporterBuilder {
+    /// Write every report to `file`, replacing the previous one at once so
+    /// readers never see half of it.
+    pub fn file<P: Into<PathBuf>>(mut self, file: P) -> Self {
+        self.file = Some(file.into());
+        self
+    }
+
+    /// Serve the latest report to every GET on `host_and_port`, whatever the
+    /// path. Port `0` picks a free one, see `JsonReporter::http_address`.
+    pub fn http<S: Into<String>>(mut self, host_and_port: S) -> Self {
+        self.http = Some(host_and_port.into());
+        self
+    }
+
+    pub fn rate_unit(mut self, rate_unit: TimeUnit) -> Self {
+        self.format.rate_unit = rate_unit;
+        self
+    }
+
+    pub fn duration_unit(mut self, duration_unit: TimeUnit) -> Self {
+        self.format.duration_unit = duration_unit;
+        self
+    }
+
+    /// Start reporting, fails if the HTTP endpoint cannot listen.
+    pub fn build(self) -> Result<JsonReporter, Error> {
+        // Until the first report, there are no metrics to serve
+        let latest = Arc::new(Mutex::new(self.format.snapshot(&Snapshot { metrics: Vec::new() })));
+        let server = match self.http {
+            Some(host_and_port) => Some(JsonServer::start(&host_and_port, latest.clone())?),
+            None => None,
+        };
+        let http_address = server.as_ref().map(|server| server.address);
+        let backend = JsonBackend {
+            format: self.format,
+            file: self.file,
+            latest,
+            server,
+        };
+        Ok(JsonReporter {
+            reporter: ScheduledReporter::new(self.reporter_name, self.delay_ms, backend),
+            http_address,
+        })
+    }
+}
+
+impl ScheduledBackend for JsonBackend {
+    fn report(&mut self, snapshot: &Snapshot, _timestamp: Timespec) {
+        let json = self.format.snapshot(snapshot);
+        if let Some(ref file) = self.file {
+            // Written next to the file then renamed over it, the file is there again next time
+            let mut partial = file.clone().into_os_string();
+            partial.push(".partial");
+            let _ = fs::write(&partial, &json).and_then(|_| fs::rename(&partial, file));
+        }
+        *self.latest.lock().unwrap() = json;
+    }
+
+    // The last report is written, nothing is served anymore
+    fn flush(&mut self) {
+        if let Some(mut server) = self.server.take() {
+            server.stop();
+        }
+    }
+}
+
+impl JsonServer {
+    fn start(host_and_port: &str, latest: Arc<Mutex<String>>) -> Result<Self, Error> {
+        let listener = TcpListener::bind(host_and_port)?;
+        // Accept without blocking, so the thread notices when it has to stop
+        listener.set_nonblocking(true)?;
+        let address = listener.local_addr()?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = stop.clone();
+        let join_handle = thread::spawn(move || {
+            while !stopped.load(Ordering::SeqCst) {
+                match listener.accept() {
+                    Ok((stream, _)) => {
+                        let json = latest.lock().unwrap().clone();
+                        // A client going away is no concern of ours
+                        let _ = serve(stream, &json);
+                    }
+                    // Nobody is asking, or a connection failed before we took it
+                    Err(_) => thread::sleep(Duration::from_millis(50)),
+                }
+            }
+        });
+        Ok(JsonServer {
+            address,
+            stop,
+            join_handle: Some(join_handle),
+        })
+    }
+
+    fn stop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(join_handle) = self.join_handle.take() {
+            let _ = join_handle.join();
+        }
+    }
+}
+
+impl Drop for JsonServer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+// Answer a single request and close the connection
+fn serve(stream: TcpStream, json: &str) -> Result<(), Error> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    stream.set_write_timeout(Some(Duration::from_secs(5)))?;
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // The headers tell us nothing we need
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        header.clear();
+    }
+    let mut stream = reader.into_inner();
+    let (status, body) = match request_line.split_whitespace().next() {
+        Some("GET") => ("200 OK", json),
+        _ => ("405 Method Not Allowed", ""),
+    };
+    write!(stream,
+           "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+            Cache-Control: must-revalidate,no-cache,no-store\r\nConnection: close\r\n\r\n{}",
+           status,
+           body.len(),
+           body)?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod test {
+    use metrics::{Counter, CounterSnapshot, GaugeSnapshot, HistogramSnapshot, MeterSnapshot,
+                  Metric, MetricSnapshot, StdCounter, TimerSnapshot};
+    use reporter::{ReportedMetric, Reporter, Snapshot, TimeUnit};
+    use std::collections::HashMap;
+    use std::env;
+    use std::fs;
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+    use std::process;
+    use std::thread;
+    use std::time::Duration;
+    use super::{JsonFormat, JsonReporter};
+
+    fn reported(name: &str, snapshot: MetricSnapshot, labels: &[(&str, &str)]) -> ReportedMetric {
+        ReportedMetric {
+            name: name.to_string(),
+            labels: labels.iter().map(|&(k, v)| (k.to_string(), v.to_string())).collect(),
+            snapshot,
+        }
+    }
+
+    fn histogram() -> HistogramSnapshot {
+        let mut histogram = HistogramSnapshot::from_samples(2, Vec::new(), &[]);
+        histogram.min = 2_000_000;
+        histogram.max = 4_000_000;
+        histogram.mean = 3e6;
+        histogram.stddev = 1e6;
+        histogram.percentiles = vec![(50.0, 2_000_000), (99.9, 4_000_000)];
+        histogram
+    }
+
+    fn meter() -> MeterSnapshot {
+        MeterSnapshot {
+            count: 2,
+            rates: [0.5, 0.25, f64::NAN],
+            mean: 1.5,
+        }
+    }
+
+    #[test]
+    fn metrics_servlet_schema() {
+        let snapshot = Snapshot {
+            metrics: vec![reported("queue",
+                                   MetricSnapshot::Gauge(GaugeSnapshot { value: -4 }),
+                                   &[]),
+                          reported("jobs",
+                                   MetricSnapshot::Counter(CounterSnapshot { value: 7 }),
+                                   &[("region", "eu")]),
+                          reported("sizes", MetricSnapshot::Histogram(histogram()), &[]),
+                          reported("hits", MetricSnapshot::Meter(meter()), &[])],
+        };
+        assert_eq!(JsonFormat::default().snapshot(&snapshot),
+                   "{\"version\":\"4.0.0\",\
+                    \"gauges\":{\"queue\":{\"value\":-4}},\
+                    \"counters\":{\"jobs{region=eu}\":{\"count\":7}},\
+                    \"histograms\":{\"sizes\":{\"count\":2,\"max\":4000000,\"mean\":3000000.0,\
+                    \"min\":2000000,\"p50\":2000000.0,\"p999\":4000000.0,\"stddev\":1000000.0}},\
+                    \"meters\":{\"hits\":{\"count\":2,\"m15_rate\":null,\"m1_rate\":0.5,\
+                    \"m5_rate\":0.25,\"mean_rate\":1.5,\"units\":\"events/second\"}},\
+                    \"timers\":{}}");
+    }
+
+    #[test]
+    fn timers_in_units() {
+        let timer = TimerSnapshot {
+            meter: meter(),
+            histogram: histogram(),
+        };
+        let format = JsonFormat {
+            rate_unit: TimeUnit::Minutes,
+            duration_unit: TimeUnit::Milliseconds,
+        };
+        assert_eq!(format.timer(&timer),
+                   "{\"count\":2,\"max\":4.0,\"mean\":3.0,\"min\":2.0,\"p50\":2.0,\"p999\":4.0,\
+                    \"stddev\":1.0,\"m15_rate\":null,\"m1_rate\":30.0,\"m5_rate\":15.0,\
+                    \"mean_rate\":90.0,\"duration_units\":\"milliseconds\",\
+                    \"rate_units\":\"calls/minute\"}");
+    }
+
+    #[test]
+    fn file() {
+        let file = env::temp_dir().join(format!("json-file-{}.json", process::id()));
+        let _ = fs::remove_file(&file);
+        let c = StdCounter::new();
+        c.add(5);
+        let mut reporter = JsonReporter::new("json", &file, 50).unwrap();
+        reporter.add("jobs", Metric::Counter(c)).unwrap();
+        thread::sleep(Duration::from_millis(200));
+        reporter.stop().unwrap();
+        let json = fs::read_to_string(&file).unwrap();
+        assert!(json.contains("\"counters\":{\"jobs\":{\"count\":5}}"), "{}", json);
+        fs::remove_file(&file).unwrap();
+    }
+
+    fn get(reporter: &JsonReporter, method: &str) -> String {
+        let mut stream = TcpStream::connect(reporter.http_address().unwrap()).unwrap();
+        write!(stream, "{} /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n", method).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn http() {
+        let c = StdCounter::new();
+        c.add(9);
+        let mut reporter = JsonReporter::builder("json", 50).http("127.0.0.1:0").build().unwrap();
+        let response = get(&reporter, "GET");
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{}", response);
+        assert!(response.contains("Content-Type: application/json\r\n"));
+
+        let labels: HashMap<String, String> =
+            [("region".to_string(), "us".to_string())].iter().cloned().collect();
+        reporter.addl("jobs", Metric::Counter(c), Some(labels)).unwrap();
+        thread::sleep(Duration::from_millis(200));
+        let response = get(&reporter, "GET");
+        assert!(response.ends_with("\"counters\":{\"jobs{region=us}\":{\"count\":9}},\
+                                    \"histograms\":{},\"meters\":{},\"timers\":{}}"),
+                "{}",
+                response);
+        assert!(get(&reporter, "POST").starts_with("HTTP/1.1 405"));
+
+        let address = reporter.http_address().unwrap();
+        reporter.stop().unwrap();
+        assert!(TcpStream::connect(address).is_err());
+    }
+}
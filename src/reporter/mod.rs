@@ -8,43 +8,265 @@
 
 #![allow(missing_docs)]
 
+// Implement `Reporter` for a reporter that hands everything to the `ScheduledReporter` in its
+// `reporter` field
+macro_rules! forward_to_scheduled_reporter {
+    ($reporter:ty) => {
+        impl $crate::reporter::Reporter for $reporter {
+            fn get_unique_reporter_name(&self) -> &str {
+                $crate::reporter::Reporter::get_unique_reporter_name(&self.reporter)
+            }
+            fn stop(&mut self) -> Result<(), $crate::reporter::ReporterError> {
+                $crate::reporter::Reporter::stop(&mut self.reporter)
+            }
+            fn addl(&mut self,
+                    name: &str,
+                    metric: $crate::metrics::Metric,
+                    labels: Option<::std::collections::HashMap<String, String>>)
+                    -> Result<(), $crate::reporter::ReporterError> {
+                $crate::reporter::Reporter::addl(&mut self.reporter, name, metric, labels)
+            }
+            fn remove(&mut self, name: &str) -> Result<(), $crate::reporter::ReporterError> {
+                $crate::reporter::Reporter::remove(&mut self.reporter, name)
+            }
+            fn attach(&mut self,
+                      registry: ::std::sync::Arc<$crate::registry::MetricRegistry>)
+                      -> Result<(), $crate::reporter::ReporterError> {
+                $crate::reporter::Reporter::attach(&mut self.reporter, registry)
+            }
+        }
+    };
+}
+
 mod carbon;
 mod console;
+mod error;
+mod ganglia;
+mod influx;
+mod json;
+mod opentsdb;
+mod scheduled;
+mod set;
+mod statsd;
+mod whisper;
 
-pub use self::carbon::CarbonReporter;
-pub use self::console::ConsoleReporter;
+pub use self::carbon::{CarbonLabels, CarbonProtocol, CarbonReporter, CarbonReporterBuilder,
+                       CarbonTransport};
+pub use self::console::{ConsoleReporter, ConsoleReporterBuilder, TimeUnit};
+pub use self::error::ReporterError;
+pub use self::ganglia::{GangliaMetricConfig, GangliaReporter, Slope};
+pub use self::influx::{InfluxReporter, InfluxTransport};
+pub use self::json::{JsonFormat, JsonReporter, JsonReporterBuilder};
+pub use self::opentsdb::{OpenTsdbReporter, OpenTsdbTransport};
+pub use self::scheduled::{ReportedMetric, ScheduledBackend, ScheduledReporter,
+                          ScheduledReporterBuilder, Snapshot};
+pub use self::set::ReporterSet;
+pub use self::statsd::{StatsdOptions, StatsdReporter};
+pub use self::whisper::{WhisperAggregation, WhisperArchive, WhisperOptions, WhisperReporter};
 
 #[cfg(feature = "prometheus")]
 mod prometheus;
 
 #[cfg(feature = "prometheus")]
-pub use self::prometheus::PrometheusReporter;
+pub use self::prometheus::{HistogramExport, PrometheusReporter, PushFormat, PushMethod,
+                           Pushgateway, RemoteWrite};
 use std::thread::JoinHandle;
 use super::metrics::Metric;
+use super::registry::MetricRegistry;
 use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io::{BufRead, BufReader, Error, Write};
+use std::net::TcpStream;
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
+use time::{self, Timespec};
 
 // Todo create sync wrappers with mutexes.
-// Currently our only reporter runs as a seperate thread so stop returns its handler
-// In future versions we wont be so specific
+// Currently every reporter runs as a seperate thread, driven through a `Worker`
 
 enum ReporterMsg {
     AddMetric(String, Metric, Option<HashMap<String, String>>),
     RemoveMetric(String),
+    AttachRegistry(Arc<MetricRegistry>),
+}
+
+// The thread a reporter runs in, along with the channel used to drive it.
+struct Worker {
+    tx: mpsc::Sender<Result<ReporterMsg, &'static str>>,
+    join_handle: Option<JoinHandle<Result<(), String>>>,
+}
+
+impl Worker {
+    fn new(tx: mpsc::Sender<Result<ReporterMsg, &'static str>>,
+           join_handle: JoinHandle<Result<(), String>>)
+           -> Self {
+        Worker {
+            tx,
+            join_handle: Some(join_handle),
+        }
+    }
+
+    fn send(&self, msg: ReporterMsg) -> Result<(), ReporterError> {
+        if self.join_handle.is_none() {
+            return Err(ReporterError::Stopped);
+        }
+        self.tx.send(Ok(msg)).map_err(|_| ReporterError::Disconnected)
+    }
+
+    // Ask the thread to stop and wait for it to finish
+    fn stop(&mut self) -> Result<(), ReporterError> {
+        let join_handle = self.join_handle.take().ok_or(ReporterError::Stopped)?;
+        // If the thread is already gone, joining it tells us why
+        let _ = self.tx.send(Err("stop"));
+        match join_handle.join() {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(e)) => Err(ReporterError::Worker(e)),
+            Err(_) => Err(ReporterError::Panicked),
+        }
+    }
+}
+
+// The next multiple of delay_ms since the epoch, so that reports line up with the wall clock
+fn next_tick(delay_ms: u64) -> Timespec {
+    let ts = time::get_time();
+    let delay_ms = delay_ms as i64;
+    let next_tick_ms = ((ts.sec * 1000 + ts.nsec as i64 / 1_000_000) / delay_ms + 1) * delay_ms;
+    Timespec {
+        sec: (next_tick_ms / 1000),
+        nsec: ((next_tick_ms % 1000) * 1_000_000) as i32,
+    }
+}
+
+// Pack the lines into datagrams of at most max_bytes, separated by new lines. A line is never
+// split, one longer than max_bytes goes on its own.
+fn pack_lines(lines: Vec<String>, max_bytes: usize) -> Vec<String> {
+    let mut datagrams: Vec<String> = Vec::new();
+    for line in lines {
+        match datagrams.last_mut() {
+            Some(datagram) if datagram.len() + 1 + line.len() <= max_bytes => {
+                datagram.push('\n');
+                datagram.push_str(&line);
+            }
+            _ => datagrams.push(line),
+        }
+    }
+    datagrams
+}
+
+// A minimal HTTP/1.1 client for the backends taking writes over HTTP, any 2xx status is a success
+fn http_post(host_and_port: &str, path: &str, content_type: &str, body: &str) -> Result<(), Error> {
+    let mut stream = TcpStream::connect(host_and_port)?;
+    // A server that never answers should not hold up the reports
+    stream.set_read_timeout(Some(Duration::from_secs(10)))?;
+    write!(stream,
+           "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: {}; charset=utf-8\r\n\
+            Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+           path,
+           host_and_port,
+           content_type,
+           body.len(),
+           body)?;
+    let mut status_line = String::new();
+    BufReader::new(stream).read_line(&mut status_line)?;
+    match status_line.split_whitespace().nth(1) {
+        Some(status) if status.starts_with('2') => Ok(()),
+        _ => Err(Error::other(format!("{} answered {}", host_and_port, status_line.trim()))),
+    }
+}
+
+// A local HTTP server for the tests of the backends writing over HTTP. It answers every request
+// with 204 No Content and sends on its request line and body, until the receiver is dropped.
+#[cfg(test)]
+fn http_stand_in() -> (String, mpsc::Receiver<(String, Vec<u8>)>) {
+    use std::io::Read;
+    use std::net::TcpListener;
+    use std::thread;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || for stream in listener.incoming() {
+        let mut stream = stream.unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).unwrap();
+        let mut content_length = 0;
+        loop {
+            let mut header = String::new();
+            reader.read_line(&mut header).unwrap();
+            if header.trim().is_empty() {
+                break;
+            }
+            let mut header = header.splitn(2, ':');
+            if header.next().unwrap().trim().eq_ignore_ascii_case("content-length") {
+                content_length = header.next().unwrap_or("").trim().parse().unwrap();
+            }
+        }
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
+        stream.write_all(b"HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n").unwrap();
+        if tx.send((request_line.trim().to_string(), body)).is_err() {
+            break;
+        }
+    });
+    (address, rx)
+}
+
+// A JSON string literal, for the backends writing JSON
+fn json_string(s: &str) -> String {
+    let mut json = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+// The name of the host we run on, for the backends that tell hosts apart
+fn hostname() -> String {
+    env::var("HOSTNAME")
+        .ok()
+        .or_else(|| fs::read_to_string("/proc/sys/kernel/hostname").ok())
+        .map(|hostname| hostname.trim().to_string())
+        .filter(|hostname| !hostname.is_empty())
+        .unwrap_or_else(|| "localhost".to_string())
 }
 
+/// `Reporter` is object safe, so reporters of different kinds can be kept
+/// together as `Box<dyn Reporter>`, see `ReporterSet`.
 pub trait Reporter: Send {
     fn get_unique_reporter_name(&self) -> &str;
-    fn stop(self) -> Result<JoinHandle<Result<(), String>>, String>;
+    // Stop reporting and wait for the reporter to finish
+    fn stop(&mut self) -> Result<(), ReporterError>;
 
-    fn addl<S: Into<String>>(&mut self,
-                             name: S,
-                             metric: Metric,
-                             labels: Option<HashMap<String, String>>)
-                             -> Result<(), String>;
-    // This will be added once it is implemented for prometheus
-    fn remove<S: Into<String>>(&mut self, name: S) -> Result<(), String>;
+    fn addl(&mut self,
+            name: &str,
+            metric: Metric,
+            labels: Option<HashMap<String, String>>)
+            -> Result<(), ReporterError>;
+    fn remove(&mut self, name: &str) -> Result<(), ReporterError>;
+    // Report every metric of the registry, including those registered later on
+    fn attach(&mut self, registry: Arc<MetricRegistry>) -> Result<(), ReporterError>;
 
-    fn add<S: Into<String>>(&mut self, name: S, metric: Metric) -> Result<(), String> {
+    fn add(&mut self, name: &str, metric: Metric) -> Result<(), ReporterError> {
         self.addl(name, metric, None)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::pack_lines;
+
+    #[test]
+    fn lines_are_packed_up_to_the_limit() {
+        let lines = vec!["a:1|c".to_string(), "b:1|c".to_string(), "c:1|c".to_string()];
+        assert_eq!(pack_lines(lines.clone(), 11), vec!["a:1|c\nb:1|c", "c:1|c"]);
+        assert_eq!(pack_lines(lines, 3), vec!["a:1|c", "b:1|c", "c:1|c"]);
+    }
+}
@@ -0,0 +1,344 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// OpenTsdbReporter sends the metrics to OpenTSDB at a regular basis, named like the Carbon paths
+// and tagged with their labels.
+// refer to http://opentsdb.net/docs/build/html/user_guide/writing/index.html
+use reporter::carbon::expand;
+use reporter::{hostname, http_post, json_string, ScheduledBackend, ScheduledReporter,
+               Snapshot};
+use metrics::MetricSnapshot;
+use time::Timespec;
+use std::io::{Error, ErrorKind, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+use std::collections::{BTreeMap, HashMap};
+
+/// How the data points are sent to OpenTSDB.
+#[derive(Clone, Debug)]
+pub enum OpenTsdbTransport {
+    /// `put` lines over the telnet style API.
+    Telnet(String),
+    /// JSON POSTed to `/api/put`, in batches of at most `batch_size` points.
+    Http {
+        host_and_port: String,
+        batch_size: usize,
+    },
+}
+
+impl OpenTsdbTransport {
+    /// POST to `/api/put` in batches of 50 points, which fits the default
+    /// request size of OpenTSDB.
+    pub fn http<S: Into<String>>(host_and_port: S) -> Self {
+        OpenTsdbTransport::Http {
+            host_and_port: host_and_port.into(),
+            batch_size: 50,
+        }
+    }
+}
+
+// A data point, tags are sorted so every report writes a series the same
+struct DataPoint {
+    metric: String,
+    timestamp: i64,
+    value: String,
+    tags: BTreeMap<String, String>,
+}
+
+impl DataPoint {
+    fn put_line(&self) -> String {
+        let mut line = format!("put {} {} {}", self.metric, self.timestamp, self.value);
+        for (k, v) in &self.tags {
+            line.push_str(&format!(" {}={}", k, v));
+        }
+        line.push('\n');
+        line
+    }
+
+    fn json(&self) -> String {
+        let tags: Vec<String> = self.tags
+            .iter()
+            .map(|(k, v)| format!("{}:{}", json_string(k), json_string(v)))
+            .collect();
+        format!("{{\"metric\":{},\"timestamp\":{},\"value\":{},\"tags\":{{{}}}}}",
+                json_string(&self.metric),
+                self.timestamp,
+                self.value,
+                tags.join(","))
+    }
+}
+
+// Where the data points go, the telnet connection is made again after it failed
+struct OpenTsdbStream {
+    transport: OpenTsdbTransport,
+    telnet_stream: Option<TcpStream>,
+    // An OpenTSDB that stopped answering must not hold up the reports
+    connect_timeout: Duration,
+    write_timeout: Duration,
+}
+
+impl OpenTsdbStream {
+    fn connect(&self, host_and_port: &str) -> Result<TcpStream, Error> {
+        let mut last_error = Error::new(ErrorKind::InvalidInput, "no address to connect to");
+        for address in host_and_port.to_socket_addrs()? {
+            match TcpStream::connect_timeout(&address, self.connect_timeout) {
+                Ok(telnet_stream) => {
+                    telnet_stream.set_write_timeout(Some(self.write_timeout))?;
+                    return Ok(telnet_stream);
+                }
+                Err(e) => last_error = e,
+            }
+        }
+        Err(last_error)
+    }
+
+    fn write(&mut self, points: &[DataPoint]) -> Result<(), Error> {
+        match self.transport {
+            OpenTsdbTransport::Telnet(ref host_and_port) => {
+                if self.telnet_stream.is_none() {
+                    self.telnet_stream = Some(self.connect(host_and_port)?);
+                }
+                let lines: String = points.iter().map(DataPoint::put_line).collect();
+                let written = match self.telnet_stream {
+                    Some(ref mut stream) => stream.write_all(lines.as_bytes()),
+                    None => Ok(()),
+                };
+                if written.is_err() {
+                    self.telnet_stream = None;
+                }
+                written
+            }
+            OpenTsdbTransport::Http { ref host_and_port, batch_size } => {
+                for batch in points.chunks(batch_size.max(1)) {
+                    let json: Vec<String> = batch.iter().map(DataPoint::json).collect();
+                    let body = format!("[{}]", json.join(","));
+                    http_post(host_and_port, "/api/put", "application/json", &body)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+pub struct OpenTsdbReporter {
+    reporter: ScheduledReporter,
+}
+
+// Writes the data points of every report, they are dropped if that fails
+struct OpenTsdbBackend {
+    prefix: String,
+    default_tags: BTreeMap<String, String>,
+    opentsdb: OpenTsdbStream,
+}
+
+forward_to_scheduled_reporter!(OpenTsdbReporter);
+
+// OpenTSDB only takes a-z, A-Z, 0-9, -, _, . and / in metric names and tags
+fn sanitize(s: &str) -> String {
+    s.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || "-_./".contains(c) { c } else { '_' })
+        .collect()
+}
+
+// The labels are added to the default tags, replacing those with the same name. Values that
+// cannot be written, like NaN, are left out.
+fn data_points(metric_name: &str,
+               snapshot: &MetricSnapshot,
+               labels: &HashMap<String, String>,
+               default_tags: &BTreeMap<String, String>,
+               timestamp: i64)
+               -> Vec<DataPoint> {
+    let mut tags = default_tags.clone();
+    for (k, v) in labels {
+        let (k, v) = (sanitize(k), sanitize(v));
+        if !k.is_empty() && !v.is_empty() {
+            tags.insert(k, v);
+        }
+    }
+    expand(snapshot)
+        .into_iter()
+        .filter(|(_, value)| value.parse::<f64>().map(f64::is_finite).unwrap_or(false))
+        .map(|(suffix, value)| {
+            DataPoint {
+                metric: sanitize(&format!("{}{}", metric_name, suffix)),
+                timestamp,
+                value,
+                tags: tags.clone(),
+            }
+        })
+        .collect()
+}
+
+impl OpenTsdbReporter {
+    /// Create a reporter tagging every data point with the host it runs on.
+    pub fn new<S1: Into<String>, S2: Into<String>>(reporter_name: S1,
+                                                   transport: OpenTsdbTransport,
+                                                   prefix: S2,
+                                                   delay_ms: u64)
+                                                   -> Self {
+        OpenTsdbReporter::with_tags(reporter_name, transport, prefix, delay_ms, HashMap::new())
+    }
+
+    /// Create a reporter tagging every data point with the given tags as well
+    /// as their labels. A `host` tag is added when there are none, as OpenTSDB
+    /// requires at least one.
+    pub fn with_tags<S1: Into<String>, S2: Into<String>>(reporter_name: S1,
+                                                         transport: OpenTsdbTransport,
+                                                         prefix: S2,
+                                                         delay_ms: u64,
+                                                         tags: HashMap<String, String>)
+                                                         -> Self {
+        let mut default_tags: BTreeMap<String, String> = tags.iter()
+            .map(|(k, v)| (sanitize(k), sanitize(v)))
+            .filter(|(k, v)| !k.is_empty() && !v.is_empty())
+            .collect();
+        if default_tags.is_empty() {
+            // OpenTSDB needs a host tag, or any other, on every data point
+            default_tags.insert("host".to_string(), sanitize(&hostname()));
+        }
+        let backend = OpenTsdbBackend {
+            prefix: prefix.into(),
+            default_tags,
+            opentsdb: OpenTsdbStream {
+                transport,
+                telnet_stream: None,
+                connect_timeout: Duration::from_secs(5),
+                write_timeout: Duration::from_secs(5),
+            },
+        };
+        OpenTsdbReporter { reporter: ScheduledReporter::new(reporter_name, delay_ms, backend) }
+    }
+}
+
+impl ScheduledBackend for OpenTsdbBackend {
+    fn report(&mut self, snapshot: &Snapshot, timestamp: Timespec) {
+        let mut points = Vec::new();
+        for metric in &snapshot.metrics {
+            let metric_name = if self.prefix.is_empty() {
+                metric.name.clone()
+            } else {
+                format!("{}.{}", self.prefix, metric.name)
+            };
+            points.extend(data_points(&metric_name,
+                                      &metric.snapshot,
+                                      &metric.labels,
+                                      &self.default_tags,
+                                      timestamp.sec));
+        }
+        // if an error happens, the points are dropped and we wait for the next loop.
+        if !points.is_empty() {
+            let _ = self.opentsdb.write(&points);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use metrics::{Counter, Meter, Metric, StdCounter, StdMeter};
+    use std::collections::{BTreeMap, HashMap};
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+    use std::time::{Duration, Instant};
+    use super::{data_points, DataPoint, OpenTsdbReporter, OpenTsdbStream, OpenTsdbTransport};
+    use reporter::{http_stand_in, Reporter};
+
+    fn tags(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|&(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn put_lines_and_json() {
+        let m = StdMeter::new();
+        m.mark(3);
+        let default_tags: BTreeMap<_, _> = tags(&[("host", "web1"), ("dc", "eu")])
+            .into_iter()
+            .collect();
+        let points = data_points("app.hits",
+                                 &Metric::Meter(m).snapshot(),
+                                 &tags(&[("dc", "us west"), ("path", "/")]),
+                                 &default_tags,
+                                 1000);
+        let metrics: Vec<&str> = points.iter().map(|p| p.metric.as_str()).collect();
+        assert_eq!(metrics,
+                   vec!["app.hits.count", "app.hits.m1", "app.hits.m5", "app.hits.m15",
+                        "app.hits.mean"]);
+        assert_eq!(points[0].put_line(),
+                   "put app.hits.count 1000 3 dc=us_west host=web1 path=/\n");
+        assert_eq!(points[0].json(),
+                   "{\"metric\":\"app.hits.count\",\"timestamp\":1000,\"value\":3,\
+                    \"tags\":{\"dc\":\"us_west\",\"host\":\"web1\",\"path\":\"/\"}}");
+    }
+
+    #[test]
+    fn telnet() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let c = StdCounter::new();
+        c.add(7);
+        let mut reporter =
+            OpenTsdbReporter::new("tsdb", OpenTsdbTransport::Telnet(address), "app", 100);
+        reporter.addl("jobs", Metric::Counter(c), Some(tags(&[("queue", "high")]))).unwrap();
+
+        let stream = listener.incoming().next().unwrap().unwrap();
+        let mut line = String::new();
+        BufReader::new(stream).read_line(&mut line).unwrap();
+        reporter.stop().unwrap();
+
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        assert_eq!(&tokens[..2], &["put", "app.jobs"]);
+        assert_eq!(tokens[3], "7");
+        assert!(tokens[4].starts_with("host="));
+        assert_eq!(tokens[5], "queue=high");
+    }
+
+    #[test]
+    fn a_hung_opentsdb_times_out() {
+        // Connections are accepted but never read from
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let mut opentsdb = OpenTsdbStream {
+            transport: OpenTsdbTransport::Telnet(address),
+            telnet_stream: None,
+            connect_timeout: Duration::from_millis(100),
+            write_timeout: Duration::from_millis(100),
+        };
+        // About a megabyte a write, until the buffers are full
+        let points: Vec<_> = (0..1000)
+            .map(|_| {
+                DataPoint {
+                    metric: "a".repeat(1000),
+                    timestamp: 0,
+                    value: "1".to_string(),
+                    tags: BTreeMap::new(),
+                }
+            })
+            .collect();
+        let started = Instant::now();
+        while opentsdb.write(&points).is_ok() {
+            assert!(started.elapsed() < Duration::from_secs(10));
+        }
+        assert!(opentsdb.telnet_stream.is_none());
+        drop(listener);
+    }
+
+    #[test]
+    fn http() {
+        let (address, requests) = http_stand_in();
+        let mut reporter = OpenTsdbReporter::with_tags("tsdb",
+                                                       OpenTsdbTransport::http(address),
+                                                       "",
+                                                       100,
+                                                       tags(&[("service", "api")]));
+        reporter.add("jobs", Metric::Counter(StdCounter::new())).unwrap();
+
+        let (request_line, body) = requests.recv().unwrap();
+        reporter.stop().unwrap();
+        assert_eq!(request_line, "POST /api/put HTTP/1.1");
+        let body = String::from_utf8(body).unwrap();
+        assert!(body.starts_with("[{\"metric\":\"jobs\",\"timestamp\":"));
+        assert!(body.ends_with(",\"value\":0,\"tags\":{\"service\":\"api\"}}]"));
+    }
+}
@@ -4,93 +4,281 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-// PrometheusReporter aggregates metrics into metric families and passes them every second to the
-// attached prometheus reporter at a regular basis.
+// PrometheusReporter registers a collector with the attached prometheus reporter, which snapshots
+// the added metrics whenever Prometheus scrapes them, or whenever they are pushed to a
+// Pushgateway or remote_write endpoint.
 
 extern crate prometheus_reporter;
 extern crate protobuf;
 use self::prometheus_reporter::PrometheusReporter as Pr;
 use self::prometheus_reporter::promo_proto;
+pub use self::prometheus_reporter::pushgateway::{PushFormat, PushMethod, Pushgateway};
+pub use self::prometheus_reporter::remote_write::RemoteWrite;
 
-use std::time::Duration;
-use std::thread;
-use metrics::Metric;
-use time;
-use std::collections::HashMap;
-use std::sync::mpsc;
-use reporter::{Reporter, ReporterMsg};
+use metrics::{HistogramSnapshot, Metric};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, MutexGuard};
+use registry::MetricRegistry;
+use reporter::{Reporter, ReporterError};
 use self::protobuf::repeated::RepeatedField;
 
+#[derive(Clone)]
 struct PrometheusMetricEntry {
-    name: String,
     metric: Metric,
     labels: HashMap<String, String>,
 }
 
+/// How histograms are exported to Prometheus.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum HistogramExport {
+    /// As a Prometheus histogram of the buckets the histogram counts as
+    /// values are recorded, see `StdHistogram::with_buckets`. Histograms
+    /// counting no buckets are exported as summaries.
+    Buckets,
+    /// As a Prometheus summary of the percentiles the histogram computes.
+    #[default]
+    Quantiles,
+}
+
+// The unit and help text given to a metric with `describe`
+#[derive(Clone, Default)]
+struct Description {
+    unit: String,
+    help: String,
+}
+
+// How metrics are turned into metric families
+struct FamilyConfig {
+    namespace: String,
+    subsystem: String,
+    histogram_export: HistogramExport,
+}
+
+// What the collector snapshots on every scrape
+#[derive(Default)]
+struct Collected {
+    stopped: bool,
+    // Grouped by name and kind, see `kind`
+    entries: HashMap<(String, &'static str), Vec<PrometheusMetricEntry>>,
+    registries: Vec<Arc<MetricRegistry>>,
+    descriptions: HashMap<String, Description>,
+}
+
 // TODO perhaps we autodiscover the host and port
 //
 pub struct PrometheusReporter {
     reporter_name: &'static str,
-    tx: mpsc::Sender<Result<ReporterMsg, &'static str>>,
-    join_handle: thread::JoinHandle<Result<(), String>>,
+    collected: Arc<Mutex<Collected>>,
+    config: Arc<FamilyConfig>,
+    prometheus: Pr,
 }
 impl Reporter for PrometheusReporter {
     fn get_unique_reporter_name(&self) -> &str {
-        &(*self.reporter_name)
+        self.reporter_name
     }
-    fn stop(self) -> Result<thread::JoinHandle<Result<(), String>>, String> {
-        match self.tx.send(Err("stop")) {
-            Ok(_) => Ok(self.join_handle),
-            Err(x) => Err(format!("Unable to stop reporter:{}", x)),
-        }
+    fn stop(&mut self) -> Result<(), ReporterError> {
+        drop(self.running()?);
+        // The last push collects the metrics, so they are only cleared after it
+        self.prometheus.stop().map_err(ReporterError::Worker)?;
+        let mut collected = self.collected.lock().unwrap();
+        *collected = Collected {
+            stopped: true,
+            ..Collected::default()
+        };
+        Ok(())
     }
-    fn addl<S: Into<String>>(&mut self,
-                             name: S,
-                             metric: Metric,
-                             labels: Option<HashMap<String, String>>)
-                             -> Result<(), String> {
-        // TODO return error
-        let ref mut tx = &self.tx;
-        match tx.send(Ok(ReporterMsg::AddMetric(name.into(), metric, labels))) {
-            Ok(_) => Ok(()),
-            Err(x) => Err(format!("Unable to stop reporter: {}", x)),
+    fn addl(&mut self,
+            name: &str,
+            metric: Metric,
+            labels: Option<HashMap<String, String>>)
+            -> Result<(), ReporterError> {
+        let labels = labels.unwrap_or_default();
+        validate_labels(&metric, &labels)?;
+        let mut collected = self.running()?;
+        // A family has a single type, so a name cannot be shared by two kinds of metrics. Nor can
+        // two names be sanitized into the same family name, e.g. a.b and a_b.
+        let conflicting = collected.entries.keys().any(|(n, k)| if n == name {
+            *k != kind(&metric)
+        } else {
+            sanitize(n) == sanitize(name)
+        });
+        if conflicting {
+            return Err(ReporterError::ConflictingMetric(name.to_string()));
         }
+        let entries = collected.entries.entry((name.to_string(), kind(&metric))).or_default();
+        // The same name and labels would be the same series
+        entries.retain(|entry| entry.labels != labels);
+        entries.push(PrometheusMetricEntry { metric, labels });
+        Ok(())
     }
-
-    fn remove<S: Into<String>>(&mut self, name: S) -> Result<(), String> {
-        match self.tx
-            .send(Ok(ReporterMsg::RemoveMetric(name.into()))) {
-            Ok(_) => Ok(()),
-            Err(x) => Err(format!("Unable to remove metric {}", x)),
-        }
+    fn remove(&mut self, name: &str) -> Result<(), ReporterError> {
+        self.running()?.entries.retain(|(n, _), _| n != name);
+        Ok(())
+    }
+    fn attach(&mut self, registry: Arc<MetricRegistry>) -> Result<(), ReporterError> {
+        self.running()?.registries.push(registry);
+        Ok(())
     }
 }
 
 impl PrometheusReporter {
-    pub fn new(reporter_name: &'static str, host_and_port: &'static str, delay_ms: u64) -> Self {
-        let (tx, rx) = mpsc::channel();
-        PrometheusReporter {
-            reporter_name: reporter_name,
-            tx: tx,
-            join_handle: thread::spawn(move || {
-                let mut stop = false;
-                let mut prometheus_reporter = Pr::new(host_and_port);
-                while !stop {
-                    match collect_to_send(&rx) {
-                        // Unwrapping is always dangerous. In this case our prometheus reporter is
-                        // overwhelmed by metrics
-                        Ok((metrics_to_add, metrics_to_remove)) => {
-                            try!(prometheus_reporter.add(metrics_to_add));
-                            try!(prometheus_reporter.remove(metrics_to_remove));
-                        }
-                        Err(_) => stop = true,
-                    }
-                    thread::sleep(Duration::from_millis(delay_ms));
-                }
-                Ok(())
+    /// Create a reporter serving metrics on `host_and_port`. Metric names are
+    /// prefixed with the namespace and subsystem, e.g. `myapp_http_requests`,
+    /// either can be left empty.
+    pub fn new(reporter_name: &'static str,
+               host_and_port: &'static str,
+               namespace: &str,
+               subsystem: &str)
+               -> Self {
+        PrometheusReporter::with_histogram_export(reporter_name,
+                                                  host_and_port,
+                                                  namespace,
+                                                  subsystem,
+                                                  HistogramExport::default())
+    }
+
+    /// Create a reporter exporting histograms as configured rather than as summaries.
+    pub fn with_histogram_export(reporter_name: &'static str,
+                                 host_and_port: &'static str,
+                                 namespace: &str,
+                                 subsystem: &str,
+                                 histogram_export: HistogramExport)
+                                 -> Self {
+        PrometheusReporter::with_prometheus(reporter_name,
+                                            Pr::new(host_and_port),
+                                            namespace,
+                                            subsystem,
+                                            histogram_export)
+    }
+
+    /// Create a reporter pushing metrics to the Pushgateway every `delay_ms`
+    /// rather than serving them. On `stop` they are pushed one last time, or
+    /// the group is deleted if the Pushgateway is configured to.
+    pub fn pushing_to(reporter_name: &'static str,
+                      gateway: Pushgateway,
+                      delay_ms: u64,
+                      namespace: &str,
+                      subsystem: &str,
+                      histogram_export: HistogramExport)
+                      -> Self {
+        PrometheusReporter::with_prometheus(reporter_name,
+                                            Pr::pushing_to(gateway, delay_ms),
+                                            namespace,
+                                            subsystem,
+                                            histogram_export)
+    }
+
+    /// Create a reporter sending metrics to a remote_write endpoint every
+    /// `delay_ms`, with the labels given to `addl`. Write requests that
+    /// cannot be sent are queued, and the queue is flushed on `stop`.
+    pub fn remote_writing(reporter_name: &'static str,
+                          remote: RemoteWrite,
+                          delay_ms: u64,
+                          namespace: &str,
+                          subsystem: &str,
+                          histogram_export: HistogramExport)
+                          -> Self {
+        PrometheusReporter::with_prometheus(reporter_name,
+                                            Pr::remote_writing(remote, delay_ms),
+                                            namespace,
+                                            subsystem,
+                                            histogram_export)
+    }
+
+    fn with_prometheus(reporter_name: &'static str,
+                       prometheus: Pr,
+                       namespace: &str,
+                       subsystem: &str,
+                       histogram_export: HistogramExport)
+                       -> Self {
+        let mut reporter = PrometheusReporter {
+            reporter_name,
+            collected: Arc::new(Mutex::new(Collected::default())),
+            config: Arc::new(FamilyConfig {
+                namespace: namespace.to_string(),
+                subsystem: subsystem.to_string(),
+                histogram_export,
             }),
+            prometheus,
+        };
+        let collected = reporter.collected.clone();
+        let config = reporter.config.clone();
+        reporter.prometheus.register(move || collect(&collected, &config)).unwrap();
+        reporter
+    }
+
+    /// Give a metric a unit, e.g. `bytes` or `seconds`, and a help text.
+    /// The unit is appended to the metric's name unless it already ends with it.
+    /// Timers are reported in `seconds` when they are not described.
+    pub fn describe(&mut self, name: &str, unit: &str, help: &str) {
+        let description = Description {
+            unit: sanitize(unit),
+            help: help.to_string(),
+        };
+        self.collected.lock().unwrap().descriptions.insert(name.to_string(), description);
+    }
+
+    fn running(&self) -> Result<MutexGuard<'_, Collected>, ReporterError> {
+        let collected = self.collected.lock().unwrap();
+        if collected.stopped {
+            return Err(ReporterError::Stopped);
         }
+        Ok(collected)
+    }
+}
+
+/// Sanitize a metric name into `[a-zA-Z_:][a-zA-Z0-9_:]*`, replacing any
+/// other character with an underscore.
+fn sanitize(name: &str) -> String {
+    let mut sanitized: String = name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' || c == ':' { c } else { '_' })
+        .collect();
+    if sanitized.starts_with(|c: char| c.is_ascii_digit()) {
+        sanitized.insert(0, '_');
     }
+    sanitized
+}
+
+// Join the namespace, subsystem and name like the Go client's BuildFQName, then append the unit
+fn full_name(namespace: &str, subsystem: &str, name: &str, unit: &str) -> String {
+    let mut full_name = [namespace, subsystem, name]
+        .iter()
+        .filter(|part| !part.is_empty())
+        .cloned()
+        .collect::<Vec<_>>()
+        .join("_");
+    if !unit.is_empty() && !full_name.ends_with(&format!("_{}", unit)) {
+        full_name.push('_');
+        full_name.push_str(unit);
+    }
+    sanitize(&full_name)
+}
+
+// The kind of a metric, which decides the types of its families
+fn kind(metric: &Metric) -> &'static str {
+    match *metric {
+        Metric::Counter(_) => "counter",
+        Metric::Gauge(_) => "gauge",
+        Metric::Meter(_) => "meter",
+        Metric::Histogram(_) => "histogram",
+        Metric::Timer(_) => "timer",
+    }
+}
+
+// Label names must match [a-zA-Z_][a-zA-Z0-9_]*, names starting with __ are reserved for
+// Prometheus and le and quantile are taken by the buckets and quantiles of distributions.
+fn validate_labels(metric: &Metric, labels: &HashMap<String, String>) -> Result<(), ReporterError> {
+    let distribution = matches!(*metric, Metric::Histogram(_) | Metric::Timer(_));
+    for name in labels.keys() {
+        let valid = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') &&
+                    name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        let reserved = name.starts_with("__") ||
+                       (distribution && (name == "le" || name == "quantile"));
+        if !valid || reserved {
+            return Err(ReporterError::InvalidLabel(name.clone()));
+        }
+    }
+    Ok(())
 }
 
 fn to_repeated_fields_labels(labels: HashMap<String, String>)
@@ -106,93 +294,179 @@ fn to_repeated_fields_labels(labels: HashMap<String, String>)
     RepeatedField::from_vec(repeated_fields)
 }
 
-fn make_metric(metric: &Metric,
-               labels: &HashMap<String, String>)
-               -> (promo_proto::Metric, promo_proto::MetricType) {
-
-    let mut pb_metric = promo_proto::Metric::new();
-    let ts = time::now().to_timespec().sec;
+fn make_metrics(metric: &Metric,
+                labels: &HashMap<String, String>,
+                histogram_export: &HistogramExport)
+                -> Vec<(&'static str, promo_proto::Metric, promo_proto::MetricType)> {
+    // No timestamp, the values are live and Prometheus stamps them with the scrape time
+    let new_metric = || {
+        let mut pb_metric = promo_proto::Metric::new();
+        pb_metric.set_label(to_repeated_fields_labels(labels.clone()));
+        pb_metric
+    };
+    let counter = |value: f64| {
+        let mut pb_metric = new_metric();
+        let mut counter = promo_proto::Counter::new();
+        counter.set_value(value);
+        pb_metric.set_counter(counter);
+        (pb_metric, promo_proto::MetricType::COUNTER)
+    };
+    let gauge = |value: f64| {
+        let mut pb_metric = new_metric();
+        let mut gauge = promo_proto::Gauge::new();
+        gauge.set_value(value);
+        pb_metric.set_gauge(gauge);
+        (pb_metric, promo_proto::MetricType::GAUGE)
+    };
+    let with_suffix = |suffix, (pb_metric, pb_metric_type)| (suffix, pb_metric, pb_metric_type);
 
-    pb_metric.set_timestamp_ms(ts);
-    pb_metric.set_label(to_repeated_fields_labels(labels.clone()));
     match *metric {
-        Metric::Counter(ref x) => {
+        Metric::Counter(ref x) => vec![with_suffix("", counter(x.snapshot().value as f64))],
+        Metric::Gauge(ref x) => vec![with_suffix("", gauge(x.snapshot().value as f64))],
+        Metric::Meter(ref x) => {
             let snapshot = x.snapshot();
-            let mut counter = promo_proto::Counter::new();
-            counter.set_value(snapshot.value as f64);
-            pb_metric.set_counter(counter);
-            (pb_metric, promo_proto::MetricType::COUNTER)
+            vec![with_suffix("", counter(snapshot.count as f64)),
+                 with_suffix("_m1_rate", gauge(snapshot.rates[0])),
+                 with_suffix("_m5_rate", gauge(snapshot.rates[1])),
+                 with_suffix("_m15_rate", gauge(snapshot.rates[2])),
+                 with_suffix("_mean_rate", gauge(snapshot.mean))]
         }
-        Metric::Gauge(ref x) => {
+        Metric::Histogram(ref x) => {
             let snapshot = x.snapshot();
-            let mut gauge = promo_proto::Gauge::new();
-            gauge.set_value(snapshot.value as f64);
-            pb_metric.set_gauge(gauge);
-            (pb_metric, promo_proto::MetricType::GAUGE)
-        }
-        Metric::Meter(_) => {
-            pb_metric.set_summary(promo_proto::Summary::new());
-            (pb_metric, promo_proto::MetricType::SUMMARY)
-
+            let mut pb_metric = new_metric();
+            match *histogram_export {
+                HistogramExport::Buckets if !snapshot.buckets.is_empty() => {
+                    pb_metric.set_histogram(to_histogram(&snapshot));
+                    vec![("", pb_metric, promo_proto::MetricType::HISTOGRAM)]
+                }
+                _ => {
+                    pb_metric.set_summary(to_summary(&snapshot, 1.0));
+                    vec![("", pb_metric, promo_proto::MetricType::SUMMARY)]
+                }
+            }
         }
-        Metric::Histogram(_) => {
-            pb_metric.set_histogram(promo_proto::Histogram::new());
-            (pb_metric, promo_proto::MetricType::HISTOGRAM)
+        Metric::Timer(ref x) => {
+            // Prometheus expects durations in seconds, we record them in nanoseconds
+            let snapshot = x.snapshot();
+            let mut pb_metric = new_metric();
+            pb_metric.set_summary(to_summary(&snapshot.histogram, 1e9));
+            vec![("", pb_metric, promo_proto::MetricType::SUMMARY)]
         }
     }
 }
 
-fn collect_to_send(metric_msgs: &mpsc::Receiver<Result<ReporterMsg, &'static str>>)
-                   -> Result<(Vec<promo_proto::MetricFamily>, Vec<String>), &'static str> {
-    let mut add_entries_group = HashMap::<String, Vec<PrometheusMetricEntry>>::new();
-    let mut remove_entries = Vec::<String>::new();
-    // Group them by name TODO we should include tags and types in the grouping
-    for msg in metric_msgs {
-        match msg {
-            Ok(ReporterMsg::AddMetric(name, metric, labels)) => {
-                let entry = PrometheusMetricEntry {
-                    name: name,
-                    metric: metric,
-                    labels: labels.unwrap_or(HashMap::new()),
-                };
-                let name = entry.name.clone();
-                let mut entries = add_entries_group.remove(&name).unwrap_or(vec![]);
-                entries.push(entry);
-                add_entries_group.insert(name, entries);
-            }
-            Ok(ReporterMsg::RemoveMetric(name)) => {
-                remove_entries.push(name);
-            }
-            Err(x) => return Err(x),
+// Values are divided by scale, e.g. to turn nanoseconds into seconds. The count and sum are the
+// running ones, as Prometheus expects them to only go up.
+fn to_summary(snapshot: &HistogramSnapshot, scale: f64) -> promo_proto::Summary {
+    let mut summary = promo_proto::Summary::new();
+    summary.set_sample_count(snapshot.count);
+    summary.set_sample_sum(snapshot.sum as f64 / scale);
+    let quantiles = snapshot.percentiles
+        .iter()
+        .map(|&(percentile, value)| {
+            let mut quantile = promo_proto::Quantile::new();
+            quantile.set_quantile(percentile / 100.0);
+            quantile.set_value(value as f64 / scale);
+            quantile
+        })
+        .collect();
+    summary.set_quantile(RepeatedField::from_vec(quantiles));
+    summary
+}
+
+// The buckets are counted as values are recorded rather than estimated from the samples, which
+// decay and would make the counts go down
+fn to_histogram(snapshot: &HistogramSnapshot) -> promo_proto::Histogram {
+    let mut histogram = promo_proto::Histogram::new();
+    histogram.set_sample_count(snapshot.count);
+    histogram.set_sample_sum(snapshot.sum as f64);
+    let buckets = snapshot.buckets
+        .iter()
+        .map(|&(upper_bound, cumulative_count)| {
+            let mut bucket = promo_proto::Bucket::new();
+            bucket.set_upper_bound(upper_bound);
+            bucket.set_cumulative_count(cumulative_count);
+            bucket
+        })
+        .collect();
+    histogram.set_bucket(RepeatedField::from_vec(buckets));
+    histogram
+}
+
+// Snapshot every metric, registries are read every time so metrics registered later on are
+// picked up too
+fn collect(collected: &Mutex<Collected>, config: &FamilyConfig) -> Vec<promo_proto::MetricFamily> {
+    let collected = collected.lock().unwrap();
+    let mut entries_group = collected.entries.clone();
+    for registry in &collected.registries {
+        for (name, metric) in registry.metrics() {
+            let entry = PrometheusMetricEntry {
+                metric,
+                labels: HashMap::new(),
+            };
+            let key = (name, kind(&entry.metric));
+            entries_group.entry(key).or_default().push(entry);
         }
     }
-    Ok((metric_entries_to_family(add_entries_group), remove_entries))
-
+    let mut families = metric_entries_to_family(entries_group, config, &collected.descriptions);
+    families.sort_by(|a, b| a.get_name().cmp(b.get_name()));
+    families
 }
 
-fn metric_entries_to_family(entries_group: HashMap<String, Vec<PrometheusMetricEntry>>)
+fn metric_entries_to_family(entries_group: HashMap<(String, &'static str),
+                                                   Vec<PrometheusMetricEntry>>,
+                            config: &FamilyConfig,
+                            descriptions: &HashMap<String, Description>)
                             -> Vec<promo_proto::MetricFamily> {
     let mut families = Vec::new();
-    for (name, metric_entries) in &entries_group {
-        let formatted_metric = format!("{}_{}_{}", "application_name", name, "bytes");
-        // TODO check for 0 length
-
-        let ref e1: PrometheusMetricEntry = metric_entries[0];
-        let (_, pb_metric_type) = make_metric(&e1.metric, &e1.labels);
-
-        let mut family = promo_proto::MetricFamily::new();
-        let mut pb_metrics = Vec::new();
+    // The metrics of attached registries may still share a family name with others, in which
+    // case the first by name is exported and the others are left out
+    let mut groups: Vec<_> = entries_group.iter().collect();
+    groups.sort_by(|a, b| a.0.cmp(b.0));
+    let mut family_names = HashSet::new();
+    for ((name, _), metric_entries) in groups {
+        let mut description = descriptions.get(name).cloned().unwrap_or_default();
+        if let Metric::Timer(_) = metric_entries[0].metric {
+            if description.unit.is_empty() {
+                description.unit = "seconds".to_string();
+            }
+        }
 
+        // Some metrics expand to several families, e.g. a meter's count and rates
+        let mut family_metrics: Vec<(&str, promo_proto::MetricType, Vec<promo_proto::Metric>)> =
+            Vec::new();
         for metric_entry in metric_entries {
-            // TODO maybe don't assume they have the same type
-            let (pb_metric, _) = make_metric(&metric_entry.metric, &metric_entry.labels);
-            pb_metrics.push(pb_metric);
+            for (suffix, pb_metric, pb_metric_type) in
+                make_metrics(&metric_entry.metric, &metric_entry.labels, &config.histogram_export) {
+                match family_metrics.iter().position(|&(s, _, _)| s == suffix) {
+                    Some(i) => family_metrics[i].2.push(pb_metric),
+                    None => family_metrics.push((suffix, pb_metric_type, vec![pb_metric])),
+                }
+            }
         }
 
-        family.set_name(String::from(formatted_metric));
-        family.set_field_type(pb_metric_type);
-        family.set_metric(RepeatedField::from_vec(pb_metrics));
-        families.push(family);
+        for (suffix, pb_metric_type, pb_metrics) in family_metrics {
+            // The unit only applies to the metric itself, not to e.g. a meter's rates
+            let unit = if suffix.is_empty() { description.unit.as_str() } else { "" };
+            let family_name = full_name(&config.namespace,
+                                        &config.subsystem,
+                                        &format!("{}{}", name, suffix),
+                                        unit);
+            if !family_names.insert(family_name.clone()) {
+                continue;
+            }
+            let mut family = promo_proto::MetricFamily::new();
+            family.set_name(family_name);
+            if !unit.is_empty() {
+                family.set_unit(unit.to_string());
+            }
+            if !description.help.is_empty() {
+                family.set_help(description.help.clone());
+            }
+            family.set_field_type(pb_metric_type);
+            family.set_metric(RepeatedField::from_vec(pb_metrics));
+            families.push(family);
+        }
     }
     families
 }
@@ -201,11 +475,165 @@ fn metric_entries_to_family(entries_group: HashMap<String, Vec<PrometheusMetricE
 
 #[cfg(test)]
 mod test {
-    use histogram::Histogram;
     use std::collections::HashMap;
-    use metrics::{Counter, Gauge, Meter, Metric, StdCounter, StdGauge, StdMeter};
-    use super::PrometheusReporter;
-    use reporter::Reporter;
+    use metrics::{Counter, Gauge, Histogram, Meter, Metric, StdCounter, StdGauge, StdHistogram,
+                  StdMeter, StdTimer, Timer};
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+    use std::time::Duration;
+    use super::{collect, full_name, make_metrics, sanitize, HistogramExport, PrometheusReporter,
+                Pushgateway, RemoteWrite};
+    use reporter::ReporterError;
+    use super::promo_proto::MetricType;
+    use registry::MetricRegistry;
+    use reporter::{http_stand_in, Reporter};
+
+    #[test]
+    fn names() {
+        assert_eq!(sanitize("http.requests-total"), "http_requests_total");
+        assert_eq!(sanitize("5xx"), "_5xx");
+        assert_eq!(sanitize("rpc:latency"), "rpc:latency");
+        assert_eq!(full_name("", "", "requests", ""), "requests");
+        assert_eq!(full_name("app", "", "requests", ""), "app_requests");
+        assert_eq!(full_name("app", "http", "response", "bytes"), "app_http_response_bytes");
+        assert_eq!(full_name("app", "http", "response_bytes", "bytes"),
+                   "app_http_response_bytes");
+    }
+
+    #[test]
+    fn reserved_labels_are_rejected() {
+        let mut reporter = PrometheusReporter::new("labels", "0.0.0.0:9091", "", "");
+        let label = |name: &str| {
+            Some([(name.to_string(), "x".to_string())].iter().cloned().collect())
+        };
+
+        assert_eq!(reporter.addl("c", Metric::Counter(StdCounter::new()), label("__name__")),
+                   Err(ReporterError::InvalidLabel("__name__".to_string())));
+        assert_eq!(reporter.addl("c", Metric::Counter(StdCounter::new()), label("a-b")),
+                   Err(ReporterError::InvalidLabel("a-b".to_string())));
+        assert_eq!(reporter.addl("t", Metric::Timer(StdTimer::new()), label("quantile")),
+                   Err(ReporterError::InvalidLabel("quantile".to_string())));
+        assert_eq!(reporter.addl("c", Metric::Counter(StdCounter::new()), label("quantile")),
+                   Ok(()));
+        reporter.stop().unwrap();
+    }
+
+    #[test]
+    fn kinds_do_not_share_a_name() {
+        let mut reporter = PrometheusReporter::new("kinds", "0.0.0.0:9094", "", "");
+        let region = Some([("region".to_string(), "eu".to_string())].iter().cloned().collect());
+        reporter.add("requests", Metric::Counter(StdCounter::new())).unwrap();
+        reporter.addl("requests", Metric::Counter(StdCounter::new()), region).unwrap();
+        assert_eq!(reporter.add("requests", Metric::Gauge(StdGauge::new())),
+                   Err(ReporterError::ConflictingMetric("requests".to_string())));
+
+        let families = collect(&reporter.collected, &reporter.config);
+        assert_eq!(families.len(), 1);
+        assert_eq!(families[0].get_field_type(), MetricType::COUNTER);
+        assert_eq!(families[0].get_metric().len(), 2);
+
+        // Once removed, the name is free for another kind
+        reporter.remove("requests").unwrap();
+        reporter.add("requests", Metric::Gauge(StdGauge::new())).unwrap();
+        let families = collect(&reporter.collected, &reporter.config);
+        assert_eq!(families[0].get_field_type(), MetricType::GAUGE);
+        reporter.stop().unwrap();
+    }
+
+    #[test]
+    fn names_sanitized_alike_conflict() {
+        let mut reporter = PrometheusReporter::new("sanitized", "0.0.0.0:9095", "", "");
+        reporter.add("http.requests", Metric::Counter(StdCounter::new())).unwrap();
+        assert_eq!(reporter.add("http_requests", Metric::Counter(StdCounter::new())),
+                   Err(ReporterError::ConflictingMetric("http_requests".to_string())));
+        reporter.add("a-b", Metric::Gauge(StdGauge::new())).unwrap();
+        assert_eq!(reporter.add("a.b", Metric::Gauge(StdGauge::new())),
+                   Err(ReporterError::ConflictingMetric("a.b".to_string())));
+
+        // Registries are only read on scrape, where a family is only ever exported once
+        let registry = MetricRegistry::new();
+        registry.counter("http_requests").unwrap().inc();
+        reporter.attach(registry).unwrap();
+        let families = collect(&reporter.collected, &reporter.config);
+        let names: Vec<_> = families.iter().map(|family| family.get_name()).collect();
+        assert_eq!(names, vec!["a_b", "http_requests"]);
+        assert_eq!(families[1].get_metric().len(), 1);
+        reporter.stop().unwrap();
+    }
+
+    #[test]
+    fn meter_counts_and_rates() {
+        let m = StdMeter::new();
+        m.mark(3);
+
+        let pb_metrics =
+            make_metrics(&Metric::Meter(m), &HashMap::new(), &HistogramExport::default());
+        let suffixes: Vec<_> = pb_metrics.iter().map(|&(suffix, _, _)| suffix).collect();
+        assert_eq!(suffixes, vec!["", "_m1_rate", "_m5_rate", "_m15_rate", "_mean_rate"]);
+        assert_eq!(pb_metrics[0].2, MetricType::COUNTER);
+        assert_eq!(pb_metrics[0].1.get_counter().get_value(), 3.0);
+        assert!(pb_metrics[1..].iter().all(|&(_, _, t)| t == MetricType::GAUGE));
+    }
+
+    #[test]
+    fn histogram_buckets_or_quantiles() {
+        let h = StdHistogram::with_buckets(vec![5.0, 10.0]);
+        for i in 1..11 {
+            h.update(i);
+        }
+        let metric = Metric::Histogram(h.clone());
+
+        let (_, pb_metric, pb_metric_type) =
+            make_metrics(&metric, &HashMap::new(), &HistogramExport::Buckets).remove(0);
+        assert_eq!(pb_metric_type, MetricType::HISTOGRAM);
+        let histogram = pb_metric.get_histogram();
+        assert_eq!(histogram.get_sample_count(), 10);
+        assert_eq!(histogram.get_sample_sum(), 55.0);
+        let counts: Vec<_> = histogram.get_bucket()
+            .iter()
+            .map(|b| (b.get_upper_bound(), b.get_cumulative_count()))
+            .collect();
+        assert_eq!(counts, vec![(5.0, 5), (10.0, 10)]);
+
+        let (_, pb_metric, pb_metric_type) =
+            make_metrics(&metric, &HashMap::new(), &HistogramExport::Quantiles).remove(0);
+        assert_eq!(pb_metric_type, MetricType::SUMMARY);
+        let summary = pb_metric.get_summary();
+        assert_eq!(summary.get_sample_count(), 10);
+        assert_eq!(summary.get_sample_sum(), 55.0);
+        assert_eq!(summary.get_quantile()[0].get_quantile(), 0.5);
+        assert_eq!(summary.get_quantile()[0].get_value(), 6.0);
+
+        // Without buckets to export, a summary it is
+        let (_, _, pb_metric_type) = make_metrics(&Metric::Histogram(StdHistogram::new()),
+                                                  &HashMap::new(),
+                                                  &HistogramExport::Buckets)
+            .remove(0);
+        assert_eq!(pb_metric_type, MetricType::SUMMARY);
+    }
+
+    #[test]
+    fn histogram_counters_only_go_up() {
+        // Recent values weigh more in the default reservoir, the buckets and sum must not follow
+        let h = StdHistogram::with_buckets(vec![10.0]);
+        let metric = Metric::Histogram(h.clone());
+        let scrape = || {
+            let (_, pb_metric, _) =
+                make_metrics(&metric, &HashMap::new(), &HistogramExport::Buckets).remove(0);
+            let histogram = pb_metric.get_histogram();
+            (histogram.get_bucket()[0].get_cumulative_count(), histogram.get_sample_sum())
+        };
+        for _ in 0..100 {
+            h.update(1);
+        }
+        let before = scrape();
+        for _ in 0..1000 {
+            h.update(1000);
+        }
+        let after = scrape();
+        assert_eq!(before, (100, 100.0));
+        assert_eq!(after, (100, 1_000_100.0));
+    }
 
     #[test]
     fn meter() {
@@ -218,20 +646,136 @@ mod test {
         let g = StdGauge::new();
         g.set(2);
 
-        let mut h = Histogram::configure()
-            .max_value(100)
-            .precision(1)
-            .build()
-            .unwrap();
+        let h = StdHistogram::new();
+        h.update(1);
 
-        h.increment_by(1, 1).unwrap();
+        let t = StdTimer::new();
+        t.update(Duration::from_millis(3));
 
-        let mut reporter = PrometheusReporter::new("test", "0.0.0.0:80", 1024);
+        let mut reporter = PrometheusReporter::new("test", "0.0.0.0:80", "", "");
         let labels = Some(HashMap::new());
         reporter.addl("meter1", Metric::Meter(m.clone()), labels.clone()).unwrap();
         reporter.addl("counter1", Metric::Counter(c.clone()), labels.clone()).unwrap();
         reporter.addl("gauge1", Metric::Gauge(g.clone()), labels.clone()).unwrap();
-        reporter.addl("histogram", Metric::Histogram(h), labels).unwrap();
-        reporter.stop().unwrap().join().unwrap().unwrap();
+        reporter.addl("histogram", Metric::Histogram(h.clone()), labels.clone()).unwrap();
+        reporter.addl("timer", Metric::Timer(t.clone()), labels).unwrap();
+
+        let registry = MetricRegistry::new();
+        registry.counter("registered").unwrap().inc();
+        reporter.attach(registry.clone()).unwrap();
+
+        let names = |reporter: &PrometheusReporter| -> Vec<String> {
+            collect(&reporter.collected, &reporter.config)
+                .iter()
+                .map(|family| family.get_name().to_string())
+                .collect()
+        };
+        assert_eq!(names(&reporter),
+                   vec!["counter1",
+                        "gauge1",
+                        "histogram",
+                        "meter1",
+                        "meter1_m15_rate",
+                        "meter1_m1_rate",
+                        "meter1_m5_rate",
+                        "meter1_mean_rate",
+                        "registered",
+                        "timer_seconds"]);
+
+        reporter.remove("meter1").unwrap();
+        registry.remove("registered");
+        assert_eq!(names(&reporter),
+                   vec!["counter1", "gauge1", "histogram", "timer_seconds"]);
+
+        reporter.stop().unwrap();
+        assert!(names(&reporter).is_empty());
+        assert_eq!(reporter.stop(), Err(ReporterError::Stopped));
+        assert_eq!(reporter.add("late", Metric::Counter(c)), Err(ReporterError::Stopped));
+    }
+
+    #[test]
+    fn snapshots_are_taken_on_scrape() {
+        let c = StdCounter::new();
+        let mut reporter = PrometheusReporter::new("live", "0.0.0.0:9092", "app", "");
+        reporter.add("requests", Metric::Counter(c.clone())).unwrap();
+
+        let value = |reporter: &PrometheusReporter| {
+            let families = collect(&reporter.collected, &reporter.config);
+            assert_eq!(families[0].get_name(), "app_requests");
+            families[0].get_metric()[0].get_counter().get_value()
+        };
+        assert_eq!(value(&reporter), 0.0);
+        c.inc();
+        c.inc();
+        assert_eq!(value(&reporter), 2.0);
+        reporter.stop().unwrap();
+    }
+
+    #[test]
+    fn stop_frees_the_port() {
+        let scrape = || {
+            let mut stream = TcpStream::connect("127.0.0.1:9093").unwrap();
+            stream.write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        };
+        let mut reporter = PrometheusReporter::new("first", "127.0.0.1:9093", "", "");
+        reporter.add("first", Metric::Counter(StdCounter::new())).unwrap();
+        assert!(scrape().contains("\nfirst 0"));
+        reporter.stop().unwrap();
+        assert!(TcpStream::connect("127.0.0.1:9093").is_err());
+
+        let mut reporter = PrometheusReporter::new("second", "127.0.0.1:9093", "", "");
+        reporter.add("second", Metric::Counter(StdCounter::new())).unwrap();
+        let response = scrape();
+        assert!(response.starts_with("HTTP/1.1 200"));
+        assert!(response.contains("\nsecond 0") && !response.contains("first"));
+        reporter.stop().unwrap();
+    }
+
+    #[test]
+    fn push_on_stop() {
+        let (address, requests) = http_stand_in();
+        let url = format!("http://{}", address);
+        let gateway = Pushgateway::new(&url, "batch").grouping("instance", "db1");
+        let mut reporter =
+            PrometheusReporter::pushing_to("push", gateway, 60000, "app", "", Default::default());
+        let c = StdCounter::new();
+        c.inc();
+        reporter.add("runs", Metric::Counter(c)).unwrap();
+        reporter.stop().unwrap();
+
+        let (request_line, body) = requests.recv().unwrap();
+        assert_eq!(request_line, "PUT /metrics/job/batch/instance/db1 HTTP/1.1");
+        assert_eq!(String::from_utf8(body).unwrap(), "# TYPE app_runs counter\napp_runs 1\n");
+    }
+
+    #[test]
+    fn remote_write_on_stop() {
+        let (address, requests) = http_stand_in();
+        let url = format!("http://{}", address);
+        let remote = RemoteWrite::new(&format!("{}/api/v1/write", url));
+        let mut reporter =
+            PrometheusReporter::remote_writing("remote", remote, 60000, "", "", Default::default());
+        let labels = [("region".to_string(), "eu".to_string())].iter().cloned().collect();
+        reporter.addl("runs", Metric::Counter(StdCounter::new()), Some(labels)).unwrap();
+        reporter.stop().unwrap();
+
+        let (request_line, body) = requests.recv().unwrap();
+        assert_eq!(request_line, "POST /api/v1/write HTTP/1.1");
+        assert!(!body.is_empty());
+    }
+
+    #[test]
+    fn delete_on_stop() {
+        let (address, requests) = http_stand_in();
+        let url = format!("http://{}", address);
+        let gateway = Pushgateway::new(&url, "batch").delete_on_stop(true);
+        let mut reporter =
+            PrometheusReporter::pushing_to("delete", gateway, 60000, "", "", Default::default());
+        reporter.stop().unwrap();
+        assert_eq!(requests.recv().unwrap().0, "DELETE /metrics/job/batch HTTP/1.1");
+        assert_eq!(reporter.stop(), Err(ReporterError::Stopped));
     }
 }
@@ -0,0 +1,338 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ScheduledReporter runs the loop of the reporters that write the metrics out every period. It
+// keeps the metrics added to it, takes their snapshots at wall clock boundaries and hands them to
+// a backend, which only has to write them.
+use std::thread;
+use reporter::{next_tick, Reporter, ReporterError, ReporterMsg, Worker};
+use metrics::{Metric, MetricSnapshot};
+use registry::MetricRegistry;
+use time::{self, Timespec};
+use std::sync::{mpsc, Arc};
+use std::collections::HashMap;
+
+/// A metric as it was when the snapshot was taken.
+#[derive(Debug)]
+pub struct ReportedMetric {
+    pub name: String,
+    /// The metrics of the attached registries have none.
+    pub labels: HashMap<String, String>,
+    pub snapshot: MetricSnapshot,
+}
+
+/// The metrics of a tick, those added to the reporter and those of the
+/// attached registries.
+#[derive(Debug)]
+pub struct Snapshot {
+    pub metrics: Vec<ReportedMetric>,
+}
+
+impl ReportedMetric {
+    /// The labels, sorted by name.
+    pub fn sorted_labels(&self) -> Vec<(String, String)> {
+        let mut labels: Vec<(String, String)> =
+            self.labels.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+        labels.sort();
+        labels
+    }
+}
+
+/// Writes the metrics somewhere, every period of a `ScheduledReporter`.
+pub trait ScheduledBackend: Send + 'static {
+    /// Write the metrics as they were at `timestamp`. Failures are the
+    /// backend's to deal with, the next tick comes regardless.
+    fn report(&mut self, snapshot: &Snapshot, timestamp: Timespec);
+    /// Write whatever is still buffered, after the last report when the
+    /// reporter stops.
+    fn flush(&mut self) {}
+}
+
+type MetricFilter = Box<dyn Fn(&str, &MetricSnapshot) -> bool + Send>;
+
+pub struct ScheduledReporter {
+    reporter_name: String,
+    worker: Worker,
+}
+
+/// Configures a `ScheduledReporter` before it starts reporting.
+pub struct ScheduledReporterBuilder<B> {
+    reporter_name: String,
+    delay_ms: u64,
+    backend: B,
+    filters: Vec<MetricFilter>,
+}
+
+impl Reporter for ScheduledReporter {
+    fn get_unique_reporter_name(&self) -> &str {
+        &self.reporter_name
+    }
+    fn stop(&mut self) -> Result<(), ReporterError> {
+        self.worker.stop()
+    }
+    fn addl(&mut self,
+            name: &str,
+            metric: Metric,
+            labels: Option<HashMap<String, String>>)
+            -> Result<(), ReporterError> {
+        self.worker.send(ReporterMsg::AddMetric(name.to_string(), metric, labels))
+    }
+    fn remove(&mut self, name: &str) -> Result<(), ReporterError> {
+        self.worker.send(ReporterMsg::RemoveMetric(name.to_string()))
+    }
+    fn attach(&mut self, registry: Arc<MetricRegistry>) -> Result<(), ReporterError> {
+        self.worker.send(ReporterMsg::AttachRegistry(registry))
+    }
+}
+
+impl ScheduledReporter {
+    pub fn new<S: Into<String>, B: ScheduledBackend>(reporter_name: S,
+                                                     delay_ms: u64,
+                                                     backend: B)
+                                                     -> Self {
+        ScheduledReporter::builder(reporter_name, delay_ms, backend).build()
+    }
+
+    /// Configure a reporter handing every metric to the backend every
+    /// `delay_ms` unless told otherwise.
+    pub fn builder<S: Into<String>, B: ScheduledBackend>(reporter_name: S,
+                                                         delay_ms: u64,
+                                                         backend: B)
+                                                         -> ScheduledReporterBuilder<B> {
+        ScheduledReporterBuilder {
+            reporter_name: reporter_name.into(),
+            delay_ms,
+            backend,
+            filters: Vec::new(),
+        }
+    }
+}
+
+impl<B: ScheduledBackend> ScheduledReporterBuilder<B> {
+    /// Only report the metrics the filter accepts, given their name and
+    /// snapshot. A metric must be accepted by every filter.
+    pub fn filter<F>(mut self, filter: F) -> Self
+        where F: Fn(&str, &MetricSnapshot) -> bool + Send + 'static
+    {
+        self.filters.push(Box::new(filter));
+        self
+    }
+
+    /// Start reporting.
+    pub fn build(self) -> ScheduledReporter {
+        let (tx, rx) = mpsc::channel();
+        ScheduledReporter {
+            reporter_name: self.reporter_name,
+            worker: Worker::new(tx,
+                                report_continuously(self.backend,
+                                                    self.filters,
+                                                    self.delay_ms,
+                                                    rx)),
+        }
+    }
+}
+
+type Metrics = HashMap<(String, Vec<(String, String)>), (Metric, HashMap<String, String>)>;
+
+// Report at every tick until asked to stop, then report one last time and flush. The messages are
+// handled as they arrive, between the ticks.
+fn report_continuously<B: ScheduledBackend>(mut backend: B,
+                                            filters: Vec<MetricFilter>,
+                                            delay_ms: u64,
+                                            rx: mpsc::Receiver<Result<ReporterMsg, &'static str>>)
+                                            -> thread::JoinHandle<Result<(), String>> {
+    thread::spawn(move || {
+        // A tick every 0ms would never come
+        let delay_ms = delay_ms.max(1);
+        let mut metrics = HashMap::new();
+        let mut registries = Vec::new();
+        let mut tick = next_tick(delay_ms);
+
+        loop {
+            let now = time::get_time();
+            if now >= tick {
+                report(&mut backend, &metrics, &registries, &filters, tick);
+                tick = next_tick(delay_ms);
+                continue;
+            }
+            let timeout = (tick - now).to_std().unwrap_or_default();
+            match rx.recv_timeout(timeout) {
+                Ok(Ok(ReporterMsg::AddMetric(name, metric, labels))) => {
+                    let labels = labels.unwrap_or_default();
+                    let mut key: Vec<_> = labels.clone().into_iter().collect();
+                    key.sort();
+                    // The same name with other labels is another metric
+                    metrics.insert((name, key), (metric, labels));
+                }
+                Ok(Ok(ReporterMsg::RemoveMetric(name))) => {
+                    metrics.retain(|(metric_name, _), _| *metric_name != name);
+                }
+                Ok(Ok(ReporterMsg::AttachRegistry(registry))) => {
+                    registries.push(registry);
+                }
+                Ok(Err(_)) => break,
+                Err(mpsc::RecvTimeoutError::Timeout) => {}
+                // Dropped without being stopped, it goes on reporting
+                Err(mpsc::RecvTimeoutError::Disconnected) => thread::sleep(timeout),
+            }
+        }
+        report(&mut backend, &metrics, &registries, &filters, time::get_time());
+        backend.flush();
+        Ok(())
+    })
+}
+
+fn report<B: ScheduledBackend>(backend: &mut B,
+                               metrics: &Metrics,
+                               registries: &[Arc<MetricRegistry>],
+                               filters: &[MetricFilter],
+                               ts: Timespec) {
+    let mut reported: Vec<ReportedMetric> = metrics.iter()
+        .map(|((name, _), (metric, labels))| {
+            ReportedMetric {
+                name: name.clone(),
+                labels: labels.clone(),
+                snapshot: metric.snapshot(),
+            }
+        })
+        .collect();
+    for registry in registries {
+        reported.extend(registry.snapshot().into_iter().map(|(name, snapshot)| {
+            ReportedMetric {
+                name,
+                labels: HashMap::new(),
+                snapshot,
+            }
+        }));
+    }
+    reported.retain(|metric| filters.iter().all(|f| f(&metric.name, &metric.snapshot)));
+    backend.report(&Snapshot { metrics: reported }, ts);
+}
+
+#[cfg(test)]
+mod test {
+    use metrics::{Counter, Metric, MetricSnapshot, StdCounter, StdGauge};
+    use registry::MetricRegistry;
+    use reporter::Reporter;
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::{Duration, Instant};
+    use super::{ScheduledBackend, ScheduledReporter, Snapshot};
+    use time::{self, Timespec};
+
+    // Sends the names and counts of every report, and `None` on flush
+    struct Recorder(mpsc::Sender<Option<Vec<(String, usize)>>>);
+
+    impl ScheduledBackend for Recorder {
+        fn report(&mut self, snapshot: &Snapshot, timestamp: Timespec) {
+            assert!(timestamp.sec > 0);
+            let mut counts: Vec<(String, usize)> = snapshot.metrics
+                .iter()
+                .map(|metric| {
+                    let count = match metric.snapshot {
+                        MetricSnapshot::Counter(ref x) => x.value,
+                        _ => 0,
+                    };
+                    let labels = metric.sorted_labels()
+                        .iter()
+                        .map(|(k, v)| format!(",{}={}", k, v))
+                        .collect::<String>();
+                    (format!("{}{}", metric.name, labels), count)
+                })
+                .collect();
+            counts.sort();
+            self.0.send(Some(counts)).unwrap();
+        }
+        fn flush(&mut self) {
+            self.0.send(None).unwrap();
+        }
+    }
+
+    // Sends the timestamp of every report
+    struct Ticks(mpsc::Sender<Timespec>);
+
+    impl ScheduledBackend for Ticks {
+        fn report(&mut self, _: &Snapshot, timestamp: Timespec) {
+            self.0.send(timestamp).unwrap();
+        }
+    }
+
+    fn labels(region: &str) -> Option<::std::collections::HashMap<String, String>> {
+        Some([("region".to_string(), region.to_string())].iter().cloned().collect())
+    }
+
+    #[test]
+    fn report_every_tick_then_flush() {
+        let (tx, rx) = mpsc::channel();
+        let c = StdCounter::new();
+        c.add(2);
+        let mut reporter = ScheduledReporter::new("recorder", 50, Recorder(tx));
+        reporter.addl("jobs", Metric::Counter(c.clone()), labels("eu")).unwrap();
+        reporter.addl("jobs", Metric::Counter(StdCounter::new()), labels("us")).unwrap();
+        let registry = MetricRegistry::new();
+        registry.counter("registered").unwrap().inc();
+        reporter.attach(registry).unwrap();
+
+        let expected = vec![("jobs,region=eu".to_string(), 2),
+                            ("jobs,region=us".to_string(), 0),
+                            ("registered".to_string(), 1)];
+        assert_eq!(rx.recv().unwrap(), Some(expected.clone()));
+        assert_eq!(rx.recv().unwrap(), Some(expected));
+
+        reporter.remove("jobs").unwrap();
+        thread::sleep(Duration::from_millis(120));
+        reporter.stop().unwrap();
+        let reports: Vec<_> = rx.try_iter().collect();
+        // The last report comes before the flush, and the removed metrics are gone by then
+        assert_eq!(reports[reports.len() - 1], None);
+        assert_eq!(reports[reports.len() - 2], Some(vec![("registered".to_string(), 1)]));
+        assert!(reporter.stop().is_err());
+    }
+
+    #[test]
+    fn filters() {
+        let (tx, rx) = mpsc::channel();
+        let mut reporter = ScheduledReporter::builder("filtered", 50, Recorder(tx))
+            .filter(|name, _| name != "hidden")
+            .filter(|_, snapshot| matches!(*snapshot, MetricSnapshot::Counter(_)))
+            .build();
+        reporter.add("shown", Metric::Counter(StdCounter::new())).unwrap();
+        reporter.add("hidden", Metric::Counter(StdCounter::new())).unwrap();
+        reporter.add("gauge", Metric::Gauge(StdGauge::new())).unwrap();
+        // A tick may come before the metrics are all added
+        let reported = rx.iter().find(|reported| reported.as_ref().is_some_and(|r| !r.is_empty()));
+        assert_eq!(reported.unwrap(), Some(vec![("shown".to_string(), 0)]));
+        reporter.stop().unwrap();
+    }
+
+    #[test]
+    fn reports_are_stamped_with_their_tick() {
+        let (tx, rx) = mpsc::channel();
+        let mut reporter = ScheduledReporter::new("ticks", 200, Ticks(tx));
+        for _ in 0..2 {
+            let ts = rx.recv().unwrap();
+            let now = time::get_time();
+            assert_eq!(ts.nsec % 1_000_000, 0);
+            assert_eq!((ts.sec * 1000 + i64::from(ts.nsec) / 1_000_000) % 200, 0);
+            // Reported at the tick, not a period before it
+            assert!(ts <= now && now - ts < time::Duration::milliseconds(200));
+        }
+        reporter.stop().unwrap();
+    }
+
+    #[test]
+    fn stop_reports_and_flushes_at_once() {
+        let (tx, rx) = mpsc::channel();
+        let mut reporter = ScheduledReporter::new("hourly", 3_600_000, Recorder(tx));
+        reporter.add("jobs", Metric::Counter(StdCounter::new())).unwrap();
+        let started = Instant::now();
+        reporter.stop().unwrap();
+        assert!(started.elapsed() < Duration::from_secs(5));
+        let reports: Vec<_> = rx.try_iter().collect();
+        assert_eq!(reports[reports.len() - 2..],
+                   [Some(vec![("jobs".to_string(), 0)]), None]);
+    }
+}
@@ -0,0 +1,119 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use metrics::Metric;
+use registry::MetricRegistry;
+use reporter::{Reporter, ReporterError};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// `ReporterSet` holds reporters of any kind, for instance chosen at runtime
+/// from a configuration file, and fans every request out to all of them.
+///
+/// Every member is tried even when one fails; the first error is returned.
+pub struct ReporterSet {
+    reporter_name: String,
+    reporters: Vec<Box<dyn Reporter>>,
+}
+
+impl ReporterSet {
+    pub fn new<S: Into<String>>(reporter_name: S) -> Self {
+        ReporterSet {
+            reporter_name: reporter_name.into(),
+            reporters: Vec::new(),
+        }
+    }
+
+    /// Add a reporter to the set, failing if one with the same unique name is
+    /// already there.
+    pub fn push(&mut self, reporter: Box<dyn Reporter>) -> Result<(), ReporterError> {
+        let name = reporter.get_unique_reporter_name().to_string();
+        if self.reporters.iter().any(|r| r.get_unique_reporter_name() == name) {
+            return Err(ReporterError::DuplicateName(name));
+        }
+        self.reporters.push(reporter);
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.reporters.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.reporters.is_empty()
+    }
+
+    fn for_each<F>(&mut self, mut f: F) -> Result<(), ReporterError>
+        where F: FnMut(&mut dyn Reporter) -> Result<(), ReporterError>
+    {
+        let mut result = Ok(());
+        for reporter in &mut self.reporters {
+            let outcome = f(reporter.as_mut());
+            if result.is_ok() {
+                result = outcome;
+            }
+        }
+        result
+    }
+}
+
+impl Reporter for ReporterSet {
+    fn get_unique_reporter_name(&self) -> &str {
+        &self.reporter_name
+    }
+
+    fn stop(&mut self) -> Result<(), ReporterError> {
+        self.for_each(|r| r.stop())
+    }
+
+    fn addl(&mut self,
+            name: &str,
+            metric: Metric,
+            labels: Option<HashMap<String, String>>)
+            -> Result<(), ReporterError> {
+        self.for_each(|r| r.addl(name, metric.clone(), labels.clone()))
+    }
+
+    fn remove(&mut self, name: &str) -> Result<(), ReporterError> {
+        self.for_each(|r| r.remove(name))
+    }
+
+    fn attach(&mut self, registry: Arc<MetricRegistry>) -> Result<(), ReporterError> {
+        self.for_each(|r| r.attach(registry.clone()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use metrics::{Counter, Metric, StdCounter};
+    use reporter::{CarbonReporter, ConsoleReporter, Reporter, ReporterError};
+    use std::net::TcpListener;
+    use super::ReporterSet;
+
+    #[test]
+    fn fan_out_to_every_reporter() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let host_and_port = listener.local_addr().unwrap().to_string();
+
+        let mut reporters = ReporterSet::new("all");
+        reporters.push(Box::new(ConsoleReporter::new("console", 1))).unwrap();
+        reporters.push(Box::new(CarbonReporter::new("carbon", host_and_port, "set", 100)))
+            .unwrap();
+        assert_eq!(reporters.push(Box::new(ConsoleReporter::new("console", 1))),
+                   Err(ReporterError::DuplicateName("console".to_string())));
+        assert_eq!(reporters.len(), 2);
+
+        let c = StdCounter::new();
+        c.inc();
+        reporters.add("counter", Metric::Counter(c.clone())).unwrap();
+        reporters.remove("counter").unwrap();
+        reporters.stop().unwrap();
+
+        assert_eq!(reporters.add("counter", Metric::Counter(c)),
+                   Err(ReporterError::Stopped));
+        assert_eq!(reporters.stop(), Err(ReporterError::Stopped));
+    }
+}
@@ -0,0 +1,357 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// StatsdReporter sends the metrics to a StatsD server over UDP at a regular basis, tagged with
+// their labels the way DogStatsD reads them.
+use reporter::{pack_lines, ScheduledBackend, ScheduledReporter, Snapshot};
+use metrics::MetricSnapshot;
+use time::Timespec;
+use rand;
+use std::io::Error;
+use std::mem;
+use std::net::UdpSocket;
+use std::collections::{HashMap, HashSet};
+
+/// How the metrics are sent to StatsD.
+#[derive(Clone, Debug)]
+pub struct StatsdOptions {
+    /// The share of counter, histogram and timer lines that are sent, StatsD
+    /// scales the values back up. Gauges are always sent.
+    pub sample_rate: f64,
+    /// The largest datagram to send, lines are packed together up to it.
+    pub mtu: usize,
+}
+
+impl Default for StatsdOptions {
+    fn default() -> Self {
+        StatsdOptions {
+            sample_rate: 1.0,
+            // What fits in an ethernet frame along with the IP and UDP headers
+            mtu: 1432,
+        }
+    }
+}
+
+// Turns snapshots into StatsD lines. StatsD adds up what it is sent, so counts are sent as the
+// difference with the count sent previously.
+struct StatsdLines {
+    prefix: String,
+    options: StatsdOptions,
+    last_counts: HashMap<String, u64>,
+    // The series counted since the last call to `forget_unreported`
+    reported: HashSet<String>,
+}
+
+/// Sends the metrics to StatsD every period.
+///
+/// StatsD works out the distribution of histograms and timers from every
+/// value it is sent, while only their snapshots are known here. Each period
+/// their mean is sent once, with a sample rate telling StatsD how many values
+/// it stands for: the count, sum and mean StatsD computes are right, but its
+/// upper, lower and percentiles are those of the means. Where they matter,
+/// report the histograms and timers with a reporter sending their snapshots,
+/// e.g. `CarbonReporter`.
+pub struct StatsdReporter {
+    reporter: ScheduledReporter,
+}
+
+// Sends the lines of every report, the socket is bound on the first one
+struct StatsdBackend {
+    statsd: StatsdLines,
+    host_and_port: String,
+    socket: Option<UdpSocket>,
+}
+
+forward_to_scheduled_reporter!(StatsdReporter);
+
+impl StatsdLines {
+    fn new(prefix: String, options: StatsdOptions) -> Self {
+        StatsdLines {
+            prefix,
+            options,
+            last_counts: HashMap::new(),
+            reported: HashSet::new(),
+        }
+    }
+
+    fn lines(&mut self,
+             metric_name: &str,
+             snapshot: &MetricSnapshot,
+             labels: &HashMap<String, String>)
+             -> Vec<String> {
+        let name = if self.prefix.is_empty() {
+            sanitize(metric_name)
+        } else {
+            sanitize(&format!("{}.{}", self.prefix, metric_name))
+        };
+        let tags = tags(labels);
+        // The counts are kept apart for every set of tags
+        let series = format!("{}{}", name, tags);
+        let sample_rate = self.options.sample_rate;
+        let line = |value: String, kind: &str, rate: f64| {
+            let rate = if rate < 1.0 { format!("|@{}", rate) } else { String::new() };
+            format!("{}:{}|{}{}{}", name, value, kind, rate, tags)
+        };
+        let mut lines = Vec::new();
+        match snapshot {
+            MetricSnapshot::Counter(x) => {
+                let delta = self.delta(&series, x.value as u64);
+                lines.push(line(delta.to_string(), "c", sample_rate));
+            }
+            MetricSnapshot::Meter(x) => {
+                let delta = self.delta(&series, x.count.max(0) as u64);
+                lines.push(line(delta.to_string(), "c", sample_rate));
+            }
+            MetricSnapshot::Gauge(x) => {
+                // A signed value would change the gauge rather than set it
+                if x.value < 0 {
+                    lines.push(line("0".to_string(), "g", 1.0));
+                }
+                lines.push(line(x.value.to_string(), "g", 1.0));
+            }
+            // Only the snapshot of the distribution is known, its mean stands for every value
+            // recorded since the last report, which the rate tells StatsD the number of
+            MetricSnapshot::Histogram(x) => {
+                let recorded = self.delta(&series, x.count);
+                if recorded > 0 {
+                    lines.push(line(x.mean.to_string(), "h", sample_rate / recorded as f64));
+                }
+            }
+            MetricSnapshot::Timer(x) => {
+                let recorded = self.delta(&series, x.meter.count.max(0) as u64);
+                if recorded > 0 {
+                    let mean_ms = x.histogram.mean / 1_000_000.0;
+                    lines.push(line(mean_ms.to_string(), "ms", sample_rate / recorded as f64));
+                }
+            }
+        }
+        // Sampling leaves the counts alone, StatsD makes up for the lines that are not sent
+        let sampled = match *snapshot {
+            MetricSnapshot::Gauge(_) => false,
+            _ => sample_rate < 1.0,
+        };
+        if sampled && rand::random::<f64>() >= sample_rate {
+            lines.clear();
+        }
+        lines
+    }
+
+    // A count that went down was cleared in between, all of it is new
+    fn delta(&mut self, series: &str, count: u64) -> u64 {
+        self.reported.insert(series.to_string());
+        match self.last_counts.insert(series.to_string(), count) {
+            Some(last) if last <= count => count - last,
+            _ => count,
+        }
+    }
+
+    // Called after every report, so that the counts of removed series are not kept forever and
+    // one added again starts from nothing
+    fn forget_unreported(&mut self) {
+        let reported = mem::take(&mut self.reported);
+        self.last_counts.retain(|series, _| reported.contains(series));
+    }
+}
+
+// StatsD splits lines on these
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            ':' | '|' | '@' | '#' | '\n' => '_',
+            c => c,
+        })
+        .collect()
+}
+
+// DogStatsD tags, |#k:v,k2:v2, sorted so every report tags a series the same
+fn tags(labels: &HashMap<String, String>) -> String {
+    if labels.is_empty() {
+        return String::new();
+    }
+    let clean = |s: &str| {
+        s.chars()
+            .map(|c| match c {
+                ',' | '|' | '#' | '\n' => '_',
+                c => c,
+            })
+            .collect::<String>()
+    };
+    let mut tags: Vec<String> = labels.iter()
+        .map(|(k, v)| format!("{}:{}", clean(k).replace(':', "_"), clean(v)))
+        .collect();
+    tags.sort();
+    format!("|#{}", tags.join(","))
+}
+
+fn send(socket: &UdpSocket, host_and_port: &str, datagrams: Vec<String>) -> Result<(), Error> {
+    for datagram in datagrams {
+        socket.send_to(datagram.as_bytes(), host_and_port)?;
+    }
+    Ok(())
+}
+
+impl StatsdReporter {
+    pub fn new<S1: Into<String>, S2: Into<String>, S3: Into<String>>(reporter_name: S1,
+                                                                     host_and_port: S2,
+                                                                     prefix: S3,
+                                                                     delay_ms: u64)
+                                                                     -> Self {
+        StatsdReporter::with_options(reporter_name,
+                                     host_and_port,
+                                     prefix,
+                                     delay_ms,
+                                     StatsdOptions::default())
+    }
+
+    /// Create a reporter sampling and batching the lines as configured.
+    pub fn with_options<S1, S2, S3>(reporter_name: S1,
+                                    host_and_port: S2,
+                                    prefix: S3,
+                                    delay_ms: u64,
+                                    options: StatsdOptions)
+                                    -> Self
+        where S1: Into<String>,
+              S2: Into<String>,
+              S3: Into<String>
+    {
+        let backend = StatsdBackend {
+            statsd: StatsdLines::new(prefix.into(), options),
+            host_and_port: host_and_port.into(),
+            socket: None,
+        };
+        StatsdReporter { reporter: ScheduledReporter::new(reporter_name, delay_ms, backend) }
+    }
+}
+
+impl ScheduledBackend for StatsdBackend {
+    fn report(&mut self, snapshot: &Snapshot, _timestamp: Timespec) {
+        if self.socket.is_none() {
+            self.socket = UdpSocket::bind("0.0.0.0:0").ok();
+        }
+        let mut lines = Vec::new();
+        for metric in &snapshot.metrics {
+            lines.extend(self.statsd.lines(&metric.name, &metric.snapshot, &metric.labels));
+        }
+        self.statsd.forget_unreported();
+        if let Some(ref socket) = self.socket {
+            // UDP only fails locally, e.g. when the host does not resolve, try again next time
+            let _ = send(socket, &self.host_and_port, pack_lines(lines, self.statsd.options.mtu));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use metrics::{Counter, Gauge, Histogram, Metric, StdCounter, StdGauge, StdHistogram,
+                  StdTimer, Timer};
+    use std::collections::HashMap;
+    use std::net::UdpSocket;
+    use std::time::Duration;
+    use super::{StatsdLines, StatsdOptions, StatsdReporter};
+    use reporter::Reporter;
+
+    fn labels(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|&(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn counters_are_sent_as_deltas() {
+        let c = StdCounter::new();
+        let mut statsd = StatsdLines::new("app".to_string(), StatsdOptions::default());
+        let tags = labels(&[("region", "eu"), ("host", "a")]);
+
+        c.add(3);
+        assert_eq!(statsd.lines("requests", &Metric::Counter(c.clone()).snapshot(), &tags),
+                   vec!["app.requests:3|c|#host:a,region:eu"]);
+        c.add(2);
+        assert_eq!(statsd.lines("requests", &Metric::Counter(c.clone()).snapshot(), &tags),
+                   vec!["app.requests:2|c|#host:a,region:eu"]);
+        c.clear();
+        c.inc();
+        assert_eq!(statsd.lines("requests", &Metric::Counter(c).snapshot(), &tags),
+                   vec!["app.requests:1|c|#host:a,region:eu"]);
+    }
+
+    #[test]
+    fn removed_series_are_forgotten() {
+        let mut statsd = StatsdLines::new(String::new(), StatsdOptions::default());
+        let no_labels = HashMap::new();
+        let c = StdCounter::new();
+        c.add(5);
+        assert_eq!(statsd.lines("jobs", &Metric::Counter(c).snapshot(), &no_labels),
+                   vec!["jobs:5|c"]);
+        statsd.forget_unreported();
+        // A report without it, then it is added again
+        statsd.forget_unreported();
+        let c = StdCounter::new();
+        c.add(7);
+        assert_eq!(statsd.lines("jobs", &Metric::Counter(c).snapshot(), &no_labels),
+                   vec!["jobs:7|c"]);
+    }
+
+    #[test]
+    fn gauges_histograms_and_timers() {
+        let mut statsd = StatsdLines::new(String::new(), StatsdOptions::default());
+        let no_labels = HashMap::new();
+
+        let g = StdGauge::new();
+        g.set(-4);
+        assert_eq!(statsd.lines("temp:c", &Metric::Gauge(g).snapshot(), &no_labels),
+                   vec!["temp_c:0|g", "temp_c:-4|g"]);
+
+        let h = StdHistogram::new();
+        h.update(2);
+        h.update(4);
+        assert_eq!(statsd.lines("size", &Metric::Histogram(h.clone()).snapshot(), &no_labels),
+                   vec!["size:3|h|@0.5"]);
+        assert!(statsd.lines("size", &Metric::Histogram(h).snapshot(), &no_labels).is_empty());
+
+        let t = StdTimer::new();
+        t.update(Duration::from_millis(3));
+        assert_eq!(statsd.lines("latency", &Metric::Timer(t).snapshot(), &no_labels),
+                   vec!["latency:3|ms"]);
+    }
+
+    #[test]
+    fn sampled_lines_carry_the_rate() {
+        let options = StatsdOptions {
+            sample_rate: 0.5,
+            ..StatsdOptions::default()
+        };
+        let mut statsd = StatsdLines::new(String::new(), options);
+        let c = StdCounter::new();
+        let sent: Vec<String> = (0..100)
+            .flat_map(|_| statsd.lines("hits", &Metric::Counter(c.clone()).snapshot(),
+                                       &HashMap::new()))
+            .collect();
+        assert!(sent.iter().all(|line| line == "hits:0|c|@0.5"));
+        assert!(!sent.is_empty() && sent.len() < 100);
+
+        // Gauges are never sampled, whatever their name
+        let g = StdGauge::new();
+        let sent = (0..100)
+            .flat_map(|_| statsd.lines("g", &Metric::Gauge(g.clone()).snapshot(), &HashMap::new()))
+            .count();
+        assert_eq!(sent, 100);
+    }
+
+    #[test]
+    fn reporter() {
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        socket.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        let address = socket.local_addr().unwrap().to_string();
+
+        let c = StdCounter::new();
+        c.add(5);
+        let mut reporter = StatsdReporter::new("statsd", address, "app", 100);
+        reporter.addl("jobs", Metric::Counter(c), Some(labels(&[("queue", "high")]))).unwrap();
+
+        let mut buf = [0; 1500];
+        let (len, _) = socket.recv_from(&mut buf).unwrap();
+        reporter.stop().unwrap();
+        assert_eq!(String::from_utf8_lossy(&buf[..len]), "app.jobs:5|c|#queue:high");
+    }
+}
@@ -0,0 +1,561 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// WhisperReporter writes the metrics straight into the Whisper files Carbon would keep, for
+// when there is no Carbon daemon to send them to. The files are laid out like Carbon lays them
+// out, a file per dotted path.
+// refer to https://graphite.readthedocs.io/en/latest/whisper.html
+use reporter::{ScheduledBackend, ScheduledReporter, Snapshot};
+use reporter::carbon::{paths, CarbonLabels};
+use time::Timespec;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Error, ErrorKind, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+// An interval and a value, as big endian u32 and f64
+const POINT_SIZE: u64 = 12;
+// Aggregation method, max retention, x files factor and archive count
+const METADATA_SIZE: u64 = 16;
+// Offset, seconds per point and points of an archive
+const ARCHIVE_INFO_SIZE: u64 = 12;
+
+/// How the points of an archive are rolled up into a point of the next,
+/// lower precision archive.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WhisperAggregation {
+    Average,
+    Sum,
+    Last,
+    Max,
+    Min,
+}
+
+/// An archive of a Whisper file, keeping `points` points `seconds_per_point`
+/// apart.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WhisperArchive {
+    pub seconds_per_point: u32,
+    pub points: u32,
+}
+
+/// How the Whisper files are created. Files that already exist are written
+/// as they were created.
+#[derive(Clone, Debug)]
+pub struct WhisperOptions {
+    /// From the highest precision to the lowest. The seconds per point of an
+    /// archive divide those of the next, which keeps points for longer.
+    pub archives: Vec<WhisperArchive>,
+    pub aggregation: WhisperAggregation,
+    /// The share of the points of an archive that must be known for them to
+    /// be rolled up into the next archive.
+    pub x_files_factor: f32,
+}
+
+impl Default for WhisperOptions {
+    fn default() -> Self {
+        WhisperOptions {
+            // A minute for a day, five minutes for 30 days and an hour for a year
+            archives: vec![WhisperArchive::new(60, 1440),
+                           WhisperArchive::new(300, 8640),
+                           WhisperArchive::new(3600, 8760)],
+            aggregation: WhisperAggregation::Average,
+            x_files_factor: 0.5,
+        }
+    }
+}
+
+impl WhisperArchive {
+    pub fn new(seconds_per_point: u32, points: u32) -> Self {
+        WhisperArchive {
+            seconds_per_point,
+            points,
+        }
+    }
+
+    fn retention(&self) -> u64 {
+        u64::from(self.seconds_per_point) * u64::from(self.points)
+    }
+}
+
+impl WhisperAggregation {
+    fn code(self) -> u32 {
+        match self {
+            WhisperAggregation::Average => 1,
+            WhisperAggregation::Sum => 2,
+            WhisperAggregation::Last => 3,
+            WhisperAggregation::Max => 4,
+            WhisperAggregation::Min => 5,
+        }
+    }
+
+    fn from_code(code: u32) -> Result<Self, Error> {
+        match code {
+            1 => Ok(WhisperAggregation::Average),
+            2 => Ok(WhisperAggregation::Sum),
+            3 => Ok(WhisperAggregation::Last),
+            4 => Ok(WhisperAggregation::Max),
+            5 => Ok(WhisperAggregation::Min),
+            _ => Err(invalid(format!("unsupported aggregation method {}", code))),
+        }
+    }
+
+    fn aggregate(self, values: &[f64]) -> f64 {
+        match self {
+            WhisperAggregation::Average => values.iter().sum::<f64>() / values.len() as f64,
+            WhisperAggregation::Sum => values.iter().sum(),
+            WhisperAggregation::Last => values[values.len() - 1],
+            WhisperAggregation::Max => values.iter().cloned().fold(f64::MIN, f64::max),
+            WhisperAggregation::Min => values.iter().cloned().fold(f64::MAX, f64::min),
+        }
+    }
+}
+
+impl WhisperOptions {
+    // Whisper takes the same archives as carbon's storage-schemas.conf does
+    fn validate(&self) -> Result<(), Error> {
+        if self.archives.is_empty() {
+            return Err(invalid("at least one archive is needed".to_string()));
+        }
+        for archive in &self.archives {
+            if archive.seconds_per_point == 0 || archive.points == 0 {
+                return Err(invalid(format!("{:?} keeps no points", archive)));
+            }
+            // The header keeps the longest retention in 32 bits
+            if archive.retention() > u64::from(u32::MAX) {
+                return Err(invalid(format!("{:?} keeps points for too long", archive)));
+            }
+        }
+        for pair in self.archives.windows(2) {
+            let (higher, lower) = (pair[0], pair[1]);
+            if lower.seconds_per_point <= higher.seconds_per_point ||
+               lower.seconds_per_point % higher.seconds_per_point != 0 {
+                return Err(invalid(format!("the seconds per point of {:?} must be a larger \
+                                            multiple of those of {:?}",
+                                           lower,
+                                           higher)));
+            }
+            if lower.retention() <= higher.retention() {
+                return Err(invalid(format!("{:?} must keep points for longer than {:?}",
+                                           lower,
+                                           higher)));
+            }
+            if higher.points < lower.seconds_per_point / higher.seconds_per_point {
+                return Err(invalid(format!("{:?} does not keep enough points to roll up into \
+                                            {:?}",
+                                           higher,
+                                           lower)));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn invalid(message: String) -> Error {
+    Error::new(ErrorKind::InvalidInput, message)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct ArchiveInfo {
+    offset: u64,
+    seconds_per_point: u32,
+    points: u32,
+}
+
+impl ArchiveInfo {
+    fn retention(&self) -> u64 {
+        u64::from(self.seconds_per_point) * u64::from(self.points)
+    }
+
+    fn size(&self) -> u64 {
+        u64::from(self.points) * POINT_SIZE
+    }
+}
+
+#[derive(Debug)]
+struct Header {
+    aggregation: WhisperAggregation,
+    max_retention: u32,
+    x_files_factor: f32,
+    archives: Vec<ArchiveInfo>,
+}
+
+fn read_u32(file: &mut File) -> Result<u32, Error> {
+    let mut buf = [0; 4];
+    file.read_exact(&mut buf)?;
+    Ok(u32::from_be_bytes(buf))
+}
+
+fn read_header(file: &mut File) -> Result<Header, Error> {
+    file.seek(SeekFrom::Start(0))?;
+    let aggregation = WhisperAggregation::from_code(read_u32(file)?)?;
+    let max_retention = read_u32(file)?;
+    let x_files_factor = f32::from_bits(read_u32(file)?);
+    let archive_count = read_u32(file)?;
+    let mut archives = Vec::new();
+    for _ in 0..archive_count {
+        archives.push(ArchiveInfo {
+            offset: u64::from(read_u32(file)?),
+            seconds_per_point: read_u32(file)?,
+            points: read_u32(file)?,
+        });
+    }
+    Ok(Header {
+        aggregation,
+        max_retention,
+        x_files_factor,
+        archives,
+    })
+}
+
+// Create a file with all of its points zeroed, which is what Whisper reads as unknown
+fn create(path: &Path, options: &WhisperOptions) -> Result<(), Error> {
+    if let Some(directory) = path.parent() {
+        fs::create_dir_all(directory)?;
+    }
+    let mut header = Vec::new();
+    let max_retention = options.archives.iter().map(|a| a.retention()).max().unwrap_or(0);
+    header.extend_from_slice(&options.aggregation.code().to_be_bytes());
+    header.extend_from_slice(&(max_retention as u32).to_be_bytes());
+    header.extend_from_slice(&options.x_files_factor.to_bits().to_be_bytes());
+    header.extend_from_slice(&(options.archives.len() as u32).to_be_bytes());
+    let mut offset = METADATA_SIZE + ARCHIVE_INFO_SIZE * options.archives.len() as u64;
+    for archive in &options.archives {
+        header.extend_from_slice(&(offset as u32).to_be_bytes());
+        header.extend_from_slice(&archive.seconds_per_point.to_be_bytes());
+        header.extend_from_slice(&archive.points.to_be_bytes());
+        offset += u64::from(archive.points) * POINT_SIZE;
+    }
+    let mut file = OpenOptions::new().write(true).create_new(true).open(path)?;
+    file.set_len(offset)?;
+    file.write_all(&header)
+}
+
+fn read_point(file: &mut File, offset: u64) -> Result<(u32, f64), Error> {
+    let mut buf = [0; 12];
+    file.seek(SeekFrom::Start(offset))?;
+    file.read_exact(&mut buf)?;
+    let interval = u32::from_be_bytes([buf[0], buf[1], buf[2], buf[3]]);
+    let mut value = [0; 8];
+    value.copy_from_slice(&buf[4..]);
+    Ok((interval, f64::from_be_bytes(value)))
+}
+
+fn write_point(file: &mut File, offset: u64, interval: u32, value: f64) -> Result<(), Error> {
+    let mut buf = Vec::with_capacity(POINT_SIZE as usize);
+    buf.extend_from_slice(&interval.to_be_bytes());
+    buf.extend_from_slice(&value.to_be_bytes());
+    file.seek(SeekFrom::Start(offset))?;
+    file.write_all(&buf)
+}
+
+// An archive is a ring, its first point is the base that the others are placed from
+fn point_offset(file: &mut File, archive: &ArchiveInfo, interval: u32) -> Result<u64, Error> {
+    let (base_interval, _) = read_point(file, archive.offset)?;
+    if base_interval == 0 {
+        return Ok(archive.offset);
+    }
+    let points = (i64::from(interval) - i64::from(base_interval))
+        .div_euclid(i64::from(archive.seconds_per_point));
+    let distance = (points * POINT_SIZE as i64).rem_euclid(archive.size() as i64);
+    Ok(archive.offset + distance as u64)
+}
+
+// Roll the points of the higher precision archive covering the point of the lower one up into
+// it, unless too few of them are known
+fn propagate(file: &mut File,
+             header: &Header,
+             timestamp: u32,
+             higher: &ArchiveInfo,
+             lower: &ArchiveInfo)
+             -> Result<bool, Error> {
+    let lower_interval = timestamp - timestamp % lower.seconds_per_point;
+    let first_offset = point_offset(file, higher, lower_interval)? - higher.offset;
+    let count = lower.seconds_per_point / higher.seconds_per_point;
+    let mut known = Vec::new();
+    for i in 0..count {
+        let offset = higher.offset + (first_offset + u64::from(i) * POINT_SIZE) % higher.size();
+        let (interval, value) = read_point(file, offset)?;
+        if interval == lower_interval + i * higher.seconds_per_point {
+            known.push(value);
+        }
+    }
+    if known.is_empty() || (known.len() as f32 / count as f32) < header.x_files_factor {
+        return Ok(false);
+    }
+    let offset = point_offset(file, lower, lower_interval)?;
+    write_point(file, offset, lower_interval, header.aggregation.aggregate(&known))?;
+    Ok(true)
+}
+
+// Write into the highest precision archive keeping the timestamp, then roll up into the lower
+// ones, as `whisper.update` does
+fn update(path: &Path,
+          options: &WhisperOptions,
+          timestamp: i64,
+          value: f64,
+          now: i64)
+          -> Result<(), Error> {
+    if !path.exists() {
+        create(path, options)?;
+    }
+    let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+    let header = read_header(&mut file)?;
+    let age = now - timestamp;
+    if age < 0 || age >= i64::from(header.max_retention) || timestamp > i64::from(u32::MAX) {
+        return Err(invalid(format!("timestamp {} is not covered by any archive", timestamp)));
+    }
+    let timestamp = timestamp as u32;
+    let index = header.archives
+        .iter()
+        .position(|archive| archive.retention() > age as u64)
+        .unwrap_or(header.archives.len() - 1);
+    let archive = header.archives[index];
+    let interval = timestamp - timestamp % archive.seconds_per_point;
+    let offset = point_offset(&mut file, &archive, interval)?;
+    write_point(&mut file, offset, interval, value)?;
+    let mut higher = archive;
+    for lower in &header.archives[index + 1..] {
+        if !propagate(&mut file, &header, timestamp, &higher, lower)? {
+            break;
+        }
+        higher = *lower;
+    }
+    Ok(())
+}
+
+// a.b.c is kept in a/b/c.wsp, as Carbon keeps it
+fn whisper_path(directory: &Path, metric_path: &str) -> PathBuf {
+    let mut path = directory.to_path_buf();
+    for segment in metric_path.split('.').filter(|segment| !segment.is_empty()) {
+        path.push(segment.replace('/', "_"));
+    }
+    path.set_extension("wsp");
+    path
+}
+
+pub struct WhisperReporter {
+    reporter: ScheduledReporter,
+}
+
+// Writes every datapoint into its own file
+struct WhisperBackend {
+    prefix: String,
+    directory: PathBuf,
+    options: WhisperOptions,
+}
+
+forward_to_scheduled_reporter!(WhisperReporter);
+
+impl WhisperReporter {
+    pub fn new<S1, P, S2>(reporter_name: S1, directory: P, prefix: S2, delay_ms: u64) -> Self
+        where S1: Into<String>,
+              P: Into<PathBuf>,
+              S2: Into<String>
+    {
+        WhisperReporter::with_options(reporter_name,
+                                      directory,
+                                      prefix,
+                                      delay_ms,
+                                      WhisperOptions::default())
+            .expect("the default archives are valid")
+    }
+
+    /// Create a reporter whose files are created with the given archives,
+    /// which are checked the way Whisper checks them.
+    pub fn with_options<S1, P, S2>(reporter_name: S1,
+                                   directory: P,
+                                   prefix: S2,
+                                   delay_ms: u64,
+                                   options: WhisperOptions)
+                                   -> Result<Self, Error>
+        where S1: Into<String>,
+              P: Into<PathBuf>,
+              S2: Into<String>
+    {
+        options.validate()?;
+        let backend = WhisperBackend {
+            prefix: prefix.into(),
+            directory: directory.into(),
+            options,
+        };
+        Ok(WhisperReporter { reporter: ScheduledReporter::new(reporter_name, delay_ms, backend) })
+    }
+}
+
+impl ScheduledBackend for WhisperBackend {
+    fn report(&mut self, snapshot: &Snapshot, timestamp: Timespec) {
+        // Carbon keeps tagged series apart from the paths, so labels are path segments here
+        for metric in &snapshot.metrics {
+            for (metric_path, value) in paths(&metric.name,
+                                              &metric.sorted_labels(),
+                                              CarbonLabels::PathSegments,
+                                              &metric.snapshot,
+                                              &self.prefix) {
+                if let Ok(value) = value.parse() {
+                    // A file that can't be written is tried again next time
+                    let path = whisper_path(&self.directory, &metric_path);
+                    let _ = update(&path, &self.options, timestamp.sec, value, timestamp.sec);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use metrics::{Counter, Metric, StdCounter};
+    use std::env;
+    use std::fs::{self, File};
+    use std::path::{Path, PathBuf};
+    use std::process;
+    use std::thread;
+    use std::time::Duration;
+    use super::{read_header, read_point, update, whisper_path, WhisperAggregation,
+                WhisperArchive, WhisperOptions, WhisperReporter};
+    use reporter::Reporter;
+
+    fn directory(test: &str) -> PathBuf {
+        let directory = env::temp_dir().join(format!("whisper-{}-{}", test, process::id()));
+        let _ = fs::remove_dir_all(&directory);
+        directory
+    }
+
+    fn options(aggregation: WhisperAggregation, x_files_factor: f32) -> WhisperOptions {
+        WhisperOptions {
+            archives: vec![WhisperArchive::new(1, 10), WhisperArchive::new(5, 12)],
+            aggregation,
+            x_files_factor,
+        }
+    }
+
+    // The known points of an archive, oldest first
+    fn fetch(path: &Path, archive: usize) -> Vec<(u32, f64)> {
+        let mut file = File::open(path).unwrap();
+        let archive = read_header(&mut file).unwrap().archives[archive];
+        let mut points: Vec<(u32, f64)> = (0..u64::from(archive.points))
+            .map(|i| read_point(&mut file, archive.offset + i * 12).unwrap())
+            .filter(|&(interval, _)| interval != 0)
+            .collect();
+        points.sort_by_key(|&(interval, _)| interval);
+        points
+    }
+
+    #[test]
+    fn create_and_roll_up() {
+        let path = directory("roll-up").join("a.wsp");
+        let options = options(WhisperAggregation::Sum, 0.0);
+        for (i, ts) in (1_000_000..1_000_005).enumerate() {
+            update(&path, &options, ts, i as f64 + 1.0, 1_000_004).unwrap();
+        }
+
+        let mut file = File::open(&path).unwrap();
+        let header = read_header(&mut file).unwrap();
+        assert_eq!(header.aggregation, WhisperAggregation::Sum);
+        assert_eq!(header.max_retention, 60);
+        assert_eq!((header.archives[0].offset, header.archives[1].offset), (40, 160));
+        assert_eq!(file.metadata().unwrap().len(), 40 + 22 * 12);
+        assert_eq!(fetch(&path, 0).len(), 5);
+        assert_eq!(fetch(&path, 1), vec![(1_000_000, 15.0)]);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn x_files_factor() {
+        let path = directory("x-files-factor").join("a.wsp");
+        let options = options(WhisperAggregation::Average, 0.5);
+        update(&path, &options, 1_000_000, 1.0, 1_000_004).unwrap();
+        update(&path, &options, 1_000_001, 2.0, 1_000_004).unwrap();
+        assert_eq!(fetch(&path, 1), vec![]);
+        update(&path, &options, 1_000_003, 6.0, 1_000_004).unwrap();
+        assert_eq!(fetch(&path, 1), vec![(1_000_000, 3.0)]);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn archives_wrap_around() {
+        let path = directory("wrap-around").join("a.wsp");
+        let options = options(WhisperAggregation::Max, 0.0);
+        for ts in 1_000_000..1_000_015 {
+            update(&path, &options, ts, ts as f64, ts).unwrap();
+        }
+        let points = fetch(&path, 0);
+        assert_eq!(points.len(), 10);
+        assert_eq!(points[0], (1_000_005, 1_000_005.0));
+        assert_eq!(points[9], (1_000_014, 1_000_014.0));
+        assert_eq!(fetch(&path, 1),
+                   vec![(1_000_000, 1_000_004.0), (1_000_005, 1_000_009.0),
+                        (1_000_010, 1_000_014.0)]);
+        // Older than the archives keep
+        assert!(update(&path, &options, 1_000_000, 1.0, 1_000_060).is_err());
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn retention_edge_goes_to_the_next_archive() {
+        let path = directory("retention-edge").join("a.wsp");
+        let options = options(WhisperAggregation::Last, 0.0);
+        // Exactly as old as the first archive keeps, so only the second one covers it
+        update(&path, &options, 1_000_000, 1.0, 1_000_010).unwrap();
+        assert_eq!(fetch(&path, 0), vec![]);
+        assert_eq!(fetch(&path, 1), vec![(1_000_000, 1.0)]);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn validate_archives() {
+        let archives = |archives: &[(u32, u32)]| {
+            WhisperOptions {
+                    archives: archives.iter().map(|&(s, p)| WhisperArchive::new(s, p)).collect(),
+                    ..WhisperOptions::default()
+                }
+                .validate()
+        };
+        assert!(archives(&[(1, 10), (5, 12)]).is_ok());
+        assert!(archives(&[]).is_err());
+        assert!(archives(&[(5, 12), (1, 10)]).is_err());
+        assert!(archives(&[(2, 10), (5, 12)]).is_err());
+        assert!(archives(&[(1, 10), (5, 2)]).is_err());
+        assert!(archives(&[(1, 2), (5, 12)]).is_err());
+        assert!(archives(&[(1, u32::MAX)]).is_ok());
+        assert!(archives(&[(2, u32::MAX)]).is_err());
+        assert!(WhisperOptions::default().validate().is_ok());
+    }
+
+    #[test]
+    fn paths() {
+        assert_eq!(whisper_path(Path::new("/var/whisper"), "app.jobs.count"),
+                   Path::new("/var/whisper/app/jobs/count.wsp"));
+        assert_eq!(whisper_path(Path::new("w"), "app..a/b"), Path::new("w/app/a_b.wsp"));
+    }
+
+    #[test]
+    fn reporter() {
+        let directory = directory("reporter");
+        let c = StdCounter::new();
+        c.add(3);
+        let mut reporter = WhisperReporter::with_options("whisper",
+                                                         directory.clone(),
+                                                         "app",
+                                                         100,
+                                                         options(WhisperAggregation::Last, 0.0))
+            .unwrap();
+        let labels = Some([("region".to_string(), "eu".to_string())].iter().cloned().collect());
+        reporter.addl("jobs", Metric::Counter(c), labels).unwrap();
+
+        let path = directory.join("app/jobs/region/eu.wsp");
+        for _ in 0..50 {
+            if path.exists() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(100));
+        }
+        reporter.stop().unwrap();
+        let points = fetch(&path, 0);
+        assert!(!points.is_empty());
+        assert_eq!(points[points.len() - 1].1, 3.0);
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -62,7 +62,7 @@ impl EWMA {
     pub fn new_by_alpha(alpha: f64) -> Self {
         EWMA {
             uncounted: AtomicUsize::new(0),
-            alpha: alpha,
+            alpha,
             rate: 0.0,
             init: false,
         }
@@ -76,6 +76,8 @@ impl EWMA {
 }
 
 #[cfg(test)]
+// The expected rates are kept as they were first written down
+#[allow(clippy::bool_assert_comparison, clippy::excessive_precision)]
 mod test {
     use super::*;
 